@@ -15,7 +15,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     };
     let action = action_cp.as_slice();
 
-    util::is_system_off(&parser)?;
+    util::require_action_enabled(&parser, action)?;
     parser.parse_cell()?;
 
     if action == b"start_account_auction"
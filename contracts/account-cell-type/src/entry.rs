@@ -1,16 +1,18 @@
 use alloc::boxed::Box;
 use alloc::vec;
+use alloc::vec::Vec;
 
 use ckb_std::ckb_constants::Source;
 use ckb_std::ckb_types::prelude::*;
 use ckb_std::high_level;
 use das_core::constants::*;
 use das_core::error::*;
+use das_core::witness_parser::cross_chain_proof::CrossChainProof;
 use das_core::witness_parser::WitnessesParser;
 use das_core::{assert as das_assert, code_to_error, data_parser, debug, sign_util, util, verifiers, warn};
 use das_dynamic_libs::constants::DynLibName;
 use das_dynamic_libs::sign_lib::SignLib;
-use das_dynamic_libs::{load_1_method, load_lib, log_loading, new_context};
+use das_dynamic_libs::{load_lib, load_methods, log_loading, new_context};
 use das_map::map::Map;
 use das_map::util as map_util;
 use das_types::constants::*;
@@ -28,7 +30,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     let action = action_cp.as_slice();
 
     if action != b"init_account_chain" {
-        util::is_system_off(&parser)?;
+        util::require_action_enabled(&parser, action)?;
     }
 
     debug!(
@@ -113,15 +115,40 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                     )?;
                 }
                 b"edit_manager" => {
+                    // The owner may edit the permissions of the manager only, so the manager is not required to change then.
+                    let records_changed = input_cell_witness_reader.records().as_slice()
+                        != output_cell_witness_reader.records().as_slice();
+                    let input_lock = high_level::load_cell_lock(input_account_cells[0], Source::Input)?;
+                    let output_lock = high_level::load_cell_lock(output_account_cells[0], Source::Output)?;
+                    let changed_lock = if records_changed && input_lock.as_slice() == output_lock.as_slice() {
+                        None
+                    } else {
+                        Some("manager")
+                    };
+
                     verifiers::account_cell::verify_account_cell_consistent_with_exception(
                         input_account_cells[0],
                         output_account_cells[0],
                         &input_cell_witness_reader,
                         &output_cell_witness_reader,
-                        Some("manager"),
+                        changed_lock,
                         vec![],
-                        vec!["last_edit_manager_at"],
+                        vec!["last_edit_manager_at", "records"],
                     )?;
+                    verifiers::account_cell::verify_only_manager_permissions_changed(
+                        output_account_cells[0],
+                        input_cell_witness_reader.records(),
+                        output_cell_witness_reader.records(),
+                    )?;
+                    if records_changed {
+                        verifiers::account_cell::verify_records_keys(&parser, output_cell_witness_reader.records())?;
+                    }
+                    if input_lock.as_slice() != output_lock.as_slice() {
+                        verifiers::account_cell::verify_manager_permissions_belong_to_new_manager(
+                            output_account_cells[0],
+                            output_cell_witness_reader.records(),
+                        )?;
+                    }
                 }
                 b"edit_records" => {
                     verifiers::account_cell::verify_account_cell_consistent_with_exception(
@@ -134,6 +161,16 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                         vec!["records", "last_edit_records_at"],
                     )?;
                     verifiers::account_cell::verify_records_keys(&parser, output_cell_witness_reader.records())?;
+                    verifiers::record_value::verify_records_values(
+                        &parser,
+                        Some(input_cell_witness_reader.records()),
+                        output_cell_witness_reader.records(),
+                    )?;
+                    verifiers::account_cell::verify_records_changes_permitted(
+                        input_account_cells[0],
+                        input_cell_witness_reader.records(),
+                        output_cell_witness_reader.records(),
+                    )?;
                 }
                 b"lock_account_for_cross_chain" => {
                     verifiers::account_cell::verify_account_cell_consistent_with_exception(
@@ -157,169 +194,237 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
 
             util::exec_by_type_id(&parser, TypeScript::EIP712Lib, &[])?;
         }
-        b"renew_account" => {
-            parser.parse_cell()?;
+        b"start_transfer_account" | b"accept_transfer_account" | b"cancel_transfer_account" => {
+            verifiers::account_cell::verify_unlock_role(action, &parser.params)?;
 
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
-            let prices = parser.configs.price()?.prices();
-            let config_main = parser.configs.main()?;
-            let config_account = parser.configs.account()?;
+            parser.parse_cell()?;
 
             let (input_account_cells, output_account_cells) = util::load_self_cells_in_inputs_and_outputs()?;
             verifiers::common::verify_cell_number("AccountCell", &input_account_cells, 1, &output_account_cells, 1)?;
 
+            debug!("Verify if there is no redundant cells in inputs.");
+
+            let sender_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
+            verifiers::misc::verify_no_more_cells_with_same_lock(
+                sender_lock.as_reader(),
+                &input_account_cells,
+                Source::Input,
+            )?;
+
             let input_cell_witness = util::parse_account_cell_witness(&parser, input_account_cells[0], Source::Input)?;
             let input_cell_witness_reader = input_cell_witness.as_reader();
             let output_cell_witness =
                 util::parse_account_cell_witness(&parser, output_account_cells[0], Source::Output)?;
             let output_cell_witness_reader = output_cell_witness.as_reader();
 
-            verifiers::account_cell::verify_account_capacity_not_decrease(
-                input_account_cells[0],
-                output_account_cells[0],
-            )?;
-            verifiers::account_cell::verify_account_cell_consistent_with_exception(
+            let config_account = parser.configs.account()?;
+
+            verify_transaction_fee_spent_correctly(
+                action,
+                config_account,
                 input_account_cells[0],
                 output_account_cells[0],
-                &input_cell_witness_reader,
-                &output_cell_witness_reader,
-                None,
-                vec!["expired_at"],
-                vec![],
             )?;
-
-            debug!("Verify if the AccountCell is locked for cross chain.");
-
-            let status = u8::from(input_cell_witness_reader.status());
-            das_assert!(
-                status != (AccountStatus::LockedForCrossChain as u8),
-                AccountCellErrorCode::AccountCellStatusLocked,
-                "inputs[{}] The AccountCell has been locked for cross chain, it is required to unlock first for renew.",
-                input_account_cells[0]
-            );
-
-            debug!("Verify if the AccountCell has been expired.");
-
-            let ret = verifiers::account_cell::verify_account_expiration(
+            verifiers::account_cell::verify_account_expiration(
                 config_account,
                 input_account_cells[0],
                 Source::Input,
                 timestamp,
-            );
-            if let Err(err) = ret {
-                das_assert!(
-                    err.as_i8() == AccountCellErrorCode::AccountCellInExpirationGracePeriod as i8,
-                    AccountCellErrorCode::AccountCellHasExpired,
-                    "The AccountCell has been expired."
-                );
-            } else {
-                // Ok
-            }
+            )?;
+            verifiers::account_cell::verify_account_data_consistent(
+                input_account_cells[0],
+                output_account_cells[0],
+                vec![],
+            )?;
 
-            debug!("Verify if there is no redundant cells in inputs.");
+            match action {
+                b"start_transfer_account" => {
+                    verify_action_throttle(
+                        action,
+                        config_account,
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        timestamp,
+                    )?;
+                    verifiers::account_cell::verify_raw_status_conversion(
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        AccountStatus::Normal as u8,
+                        ACCOUNT_STATUS_PENDING_TRANSFER,
+                    )?;
+                    verifiers::account_cell::verify_account_witness_consistent(
+                        input_account_cells[0],
+                        output_account_cells[0],
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        vec!["status", "last_transfer_account_at"],
+                    )?;
 
-            let sender_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
-            let balance_cells = util::find_balance_cells(config_main, sender_lock.as_reader(), Source::Input)?;
-            let all_cells = [input_account_cells.clone(), balance_cells.clone()].concat();
-            verifiers::misc::verify_no_more_cells_with_same_lock(sender_lock.as_reader(), &all_cells, Source::Input)?;
+                    verify_pending_owner_set(input_account_cells[0], output_account_cells[0])?;
+                }
+                b"accept_transfer_account" => {
+                    verifiers::account_cell::verify_raw_status_conversion(
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        ACCOUNT_STATUS_PENDING_TRANSFER,
+                        AccountStatus::Normal as u8,
+                    )?;
 
-            debug!("Verify if the profit is distribute correctly.");
-            // TODO Unify the following codes to calculate profit from duration.
+                    debug!("Verify if the transfer is accepted within the window.");
 
-            let mut profit_map = Map::new();
-            let das_wallet_lock = Script::from(das_wallet_lock());
+                    let started_at = u64::from(input_cell_witness_reader.last_transfer_account_at());
+                    das_assert!(
+                        timestamp <= started_at + ACCOUNT_TRANSFER_ACCEPTANCE_WINDOW,
+                        AccountCellErrorCode::AccountCellTransferAcceptanceExpired,
+                        "The transfer should be accepted within {}s since it started. (started_at: {}, current: {})",
+                        ACCOUNT_TRANSFER_ACCEPTANCE_WINDOW,
+                        started_at,
+                        timestamp
+                    );
 
-            let (input_income_cells, output_income_cells) = util::find_cells_by_type_id_in_inputs_and_outputs(
-                ScriptType::Type,
-                config_main.type_id_table().income_cell(),
-            )?;
+                    let accepted_at = u64::from(output_cell_witness_reader.last_transfer_account_at());
+                    das_assert!(
+                        accepted_at == timestamp,
+                        AccountCellErrorCode::AccountCellThrottle,
+                        "The AccountCell.last_transfer_account_at in outputs should be the same as the timestamp in the TimeCell.(expected: {}, current: {})",
+                        timestamp,
+                        accepted_at
+                    );
 
-            let mut exist_capacity = 0;
-            if input_income_cells.len() == 1 {
-                let input_income_cell_witness =
-                    util::parse_income_cell_witness(&parser, input_income_cells[0], Source::Input)?;
-                let input_income_cell_witness_reader = input_income_cell_witness.as_reader();
+                    verifiers::account_cell::verify_account_witness_consistent(
+                        input_account_cells[0],
+                        output_account_cells[0],
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        vec!["status", "last_transfer_account_at", "records"],
+                    )?;
+                    verifiers::account_cell::verify_account_witness_record_empty(
+                        &output_cell_witness_reader,
+                        output_account_cells[0],
+                        Source::Output,
+                    )?;
 
-                for item in input_income_cell_witness_reader.records().iter() {
-                    if util::is_reader_eq(item.belong_to(), das_wallet_lock.as_reader()) {
-                        exist_capacity += u64::from(item.capacity());
-                    }
+                    verify_pending_owner_accepted(input_account_cells[0], output_account_cells[0])?;
                 }
-            }
+                b"cancel_transfer_account" => {
+                    verifiers::account_cell::verify_raw_status_conversion(
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        ACCOUNT_STATUS_PENDING_TRANSFER,
+                        AccountStatus::Normal as u8,
+                    )?;
+                    verifiers::account_cell::verify_account_witness_consistent(
+                        input_account_cells[0],
+                        output_account_cells[0],
+                        &input_cell_witness_reader,
+                        &output_cell_witness_reader,
+                        vec!["status"],
+                    )?;
 
-            let output_income_cell_witness =
-                util::parse_income_cell_witness(&parser, output_income_cells[0], Source::Output)?;
-            let output_income_cell_witness_reader = output_income_cell_witness.as_reader();
-            let mut paid = 0;
-            for item in output_income_cell_witness_reader.records().iter() {
-                if util::is_reader_eq(item.belong_to(), das_wallet_lock.as_reader()) {
-                    paid += u64::from(item.capacity());
+                    // The pending owner is removed by replacing the manager, the owner may choose any manager here.
+                    verify_owner_lock_unchanged(input_account_cells[0], output_account_cells[0])?;
                 }
+                _ => unreachable!(),
             }
 
-            das_assert!(
-                paid > exist_capacity,
-                ErrorCode::IncomeCellConsolidateConditionNotSatisfied,
-                "outputs[{}] There is some record in outputs has less capacity than itself in inputs which is not allowed. (belong_to: {})",
-                output_income_cells[0],
-                das_wallet_lock
-            );
+            util::exec_by_type_id(&parser, TypeScript::EIP712Lib, &[])?;
+        }
+        b"renew_account" => {
+            parser.parse_cell()?;
 
-            paid -= exist_capacity;
+            let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
-            map_util::add(&mut profit_map, das_wallet_lock.as_slice().to_vec(), paid);
-            verifiers::income_cell::verify_income_cells(&parser, profit_map)?;
+            let prices = parser.configs.price()?.prices();
+            let config_main = parser.configs.main()?;
+            let config_account = parser.configs.account()?;
 
-            debug!("Check if the renewal duration is longer than or equal to one year.");
+            let (input_account_cells, output_account_cells) = util::load_self_cells_in_inputs_and_outputs()?;
 
-            let input_data = util::load_cell_data(input_account_cells[0], Source::Input)?;
-            let output_data = util::load_cell_data(output_account_cells[0], Source::Output)?;
-            let input_expired_at = data_parser::account_cell::get_expired_at(&input_data);
-            let output_expired_at = data_parser::account_cell::get_expired_at(&output_data);
-            let duration = output_expired_at - input_expired_at;
+            debug!("Verify if the number of AccountCells is correct.");
 
+            // The AccountCells can be renewed in batch, they are paired by their positions in inputs and outputs.
             das_assert!(
-                duration >= 365 * 86400,
-                AccountCellErrorCode::AccountCellRenewDurationMustLongerThanYear,
-                "The AccountCell renew should be longer than 1 year. (current: {}, expected: >= 31_536_000)",
-                duration
+                !input_account_cells.is_empty() && input_account_cells.len() == output_account_cells.len(),
+                ErrorCode::InvalidTransactionStructure,
+                "There should be at least one AccountCell in inputs and the same number of AccountCells in outputs. (inputs: {}, outputs: {})",
+                input_account_cells.len(),
+                output_account_cells.len()
             );
 
-            debug!("Check if the expired_at field has been updated correctly based on the capacity paid by the user.");
+            let sender_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
+            let mut renewals = vec![];
+            for (&input_index, &output_index) in input_account_cells.iter().zip(output_account_cells.iter()) {
+                let owner_lock = util::derive_owner_lock_from_cell(input_index, Source::Input)?;
+                das_assert!(
+                    owner_lock.as_slice() == sender_lock.as_slice(),
+                    ErrorCode::InvalidTransactionStructure,
+                    "inputs[{}] All the AccountCells renewed in one transaction should belong to the same owner.",
+                    input_index
+                );
+
+                let renewal =
+                    verify_account_renewal(&parser, config_account, prices, timestamp, input_index, output_index)?;
+                renewals.push(renewal);
+            }
 
-            let length_in_price = util::get_length_in_price(output_cell_witness_reader.account().len() as u64);
-            // Find out register price in from ConfigCellRegister.
-            let price = prices
-                .iter()
-                .find(|item| u8::from(item.length()) == length_in_price)
-                .ok_or(ErrorCode::ItemMissing)?;
+            debug!("Verify if there is no redundant cells in inputs.");
 
-            let renew_price_in_usd = u64::from(price.renew()); // x USD
-            let quote = util::load_oracle_data(OracleCellType::Quote)?;
+            let balance_cells = util::find_balance_cells(config_main, sender_lock.as_reader(), Source::Input)?;
 
-            let yearly_capacity = util::calc_yearly_capacity(renew_price_in_usd, quote, 0);
-            das_assert!(
-                paid >= yearly_capacity,
-                AccountCellErrorCode::AccountCellRenewDurationMustLongerThanYear,
-                "The paid capacity should be at least 1 year. (current: {}, expected: >= {}",
-                paid,
-                yearly_capacity
-            );
+            let (input_income_cells, output_income_cells) = util::find_cells_by_type_id_in_inputs_and_outputs(
+                ScriptType::Type,
+                config_main.type_id_table().income_cell(),
+            )?;
 
-            // Renew price for 1 year in CKB = x ÷ y .
-            let expected_duration = util::calc_duration_from_paid(paid, renew_price_in_usd, quote, 0);
-            // The duration can be floated within the range of one day.
-            das_assert!(
-                duration >= expected_duration - 86400 && duration <= expected_duration + 86400,
-                AccountCellErrorCode::AccountCellRenewDurationBiggerThanPayed,
-                "The duration should be equal to {} +/- 86400s. (current: duration({}), calculation: (paid({}) / (renew_price({}) / quote({}) * 100_000_000) ) * 86400 * 365)",
-                expected_duration,
-                duration,
-                paid,
-                renew_price_in_usd,
-                quote
-            );
+            let stablecoin_settings_opt = match util::get_payment_mode(&parser.params)? {
+                PaymentMode::Capacity => {
+                    das_assert!(
+                        output_income_cells.len() == 1,
+                        ErrorCode::InvalidTransactionStructure,
+                        "There should be 1 IncomeCell in outputs when paying by CKB. (current: {})",
+                        output_income_cells.len()
+                    );
+                    None
+                }
+                PaymentMode::Stablecoin => {
+                    let settings = util::get_stablecoin_settings(config_main)?;
+                    das_assert!(
+                        settings.is_some(),
+                        ErrorCode::StablecoinPaymentError,
+                        "Paying by stablecoin is not supported, because there is no stablecoin settings in ConfigCellMain."
+                    );
+                    das_assert!(
+                        input_income_cells.is_empty() && output_income_cells.is_empty(),
+                        ErrorCode::StablecoinPaymentError,
+                        "There should be no IncomeCell when paying by stablecoin."
+                    );
+                    settings
+                }
+            };
+
+            let udt_cells = match stablecoin_settings_opt {
+                Some(settings) => verifiers::udt::find_udt_cells(
+                    data_parser::stablecoin_settings::get_type_hash(settings),
+                    sender_lock.as_reader(),
+                    Source::Input,
+                )?,
+                None => vec![],
+            };
+            let all_cells = [input_account_cells.clone(), balance_cells.clone(), udt_cells].concat();
+            verifiers::misc::verify_no_more_cells_with_same_lock(sender_lock.as_reader(), &all_cells, Source::Input)?;
+
+            let paid = match stablecoin_settings_opt {
+                Some(settings) => verify_renew_paid_by_stablecoin(settings, sender_lock.as_reader(), &renewals)?,
+                None => verify_renew_paid_by_capacity(
+                    &parser,
+                    config_main,
+                    &input_income_cells,
+                    &output_income_cells,
+                    &renewals,
+                )?,
+            };
 
             debug!("Verify if sender get their change properly.");
 
@@ -479,6 +584,9 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 util::hex_string(expired_account_next)
             );
 
+            // The removal of the account is verified by the script group of the AccountRegistryCell.
+            verifiers::account_registry::verify_registry_cell_required(config_main)?;
+
             debug!("Verify if all the refunds has been refund properly.");
 
             let expired_account_capacity = high_level::load_cell_capacity(input_cells[1], Source::Input)?;
@@ -953,7 +1061,8 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
 
             debug!("Verify if the lock.args is changed during the unlock transaction.");
 
-            // CAREFUL! The owner lock may be changed or not changed, only the keepers know it, so we skip verification here.
+            // CAREFUL! The owner lock may be changed or not changed, only the keepers or the proof of the release event know it,
+            // so we skip verification here.
             let input_lock =
                 high_level::load_cell_lock(input_account_cells[0], Source::Input).map_err(Error::<ErrorCode>::from)?;
             let input_args = input_lock.as_reader().args().raw_data();
@@ -1006,7 +1115,21 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
 
             verify_account_is_unlocked_for_cross_chain(output_account_cells[0], &output_cell_witness_reader)?;
 
-            verify_multi_sign(input_account_cells[0], config_main.das_lock_type_id_table())?;
+            match parser.params.get(1) {
+                Some(proof_bytes) => {
+                    // The new owner comes from the release event on the other chain, so no keeper is trusted here.
+                    let proof = CrossChainProof::from_slice(proof_bytes.as_reader().raw_data())?;
+                    verify_unlock_proof(
+                        &proof,
+                        input_account_cells[0],
+                        input_cell_witness_reader.id().raw_data(),
+                        output_args,
+                    )?;
+                }
+                None => {
+                    verify_multi_sign(input_account_cells[0], config_main.das_lock_type_id_table())?;
+                }
+            }
         }
         b"confirm_expired_account_auction" => {
             parser.parse_cell()?;
@@ -1188,6 +1311,224 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     Ok(())
 }
 
+/// Verify the renewal of one pair of AccountCells, return the input index, the renewal duration and the yearly renew
+/// price in USD of the AccountCell.
+fn verify_account_renewal(
+    parser: &WitnessesParser,
+    config_account: ConfigCellAccountReader,
+    prices: PriceConfigListReader,
+    timestamp: u64,
+    input_index: usize,
+    output_index: usize,
+) -> Result<(usize, u64, u64), Box<dyn ScriptError>> {
+    let input_cell_witness = util::parse_account_cell_witness(parser, input_index, Source::Input)?;
+    let input_cell_witness_reader = input_cell_witness.as_reader();
+    let output_cell_witness = util::parse_account_cell_witness(parser, output_index, Source::Output)?;
+    let output_cell_witness_reader = output_cell_witness.as_reader();
+
+    verifiers::account_cell::verify_account_capacity_not_decrease(input_index, output_index)?;
+    verifiers::account_cell::verify_account_cell_consistent_with_exception(
+        input_index,
+        output_index,
+        &input_cell_witness_reader,
+        &output_cell_witness_reader,
+        None,
+        vec!["expired_at"],
+        vec![],
+    )?;
+
+    debug!("Verify if the AccountCell is locked for cross chain.");
+
+    let status = u8::from(input_cell_witness_reader.status());
+    das_assert!(
+        status != (AccountStatus::LockedForCrossChain as u8),
+        AccountCellErrorCode::AccountCellStatusLocked,
+        "inputs[{}] The AccountCell has been locked for cross chain, it is required to unlock first for renew.",
+        input_index
+    );
+
+    debug!("Verify if the AccountCell has been expired.");
+
+    let ret = verifiers::account_cell::verify_account_expiration(config_account, input_index, Source::Input, timestamp);
+    if let Err(err) = ret {
+        das_assert!(
+            err.as_i8() == AccountCellErrorCode::AccountCellInExpirationGracePeriod as i8,
+            AccountCellErrorCode::AccountCellHasExpired,
+            "inputs[{}] The AccountCell has been expired.",
+            input_index
+        );
+    } else {
+        // Ok
+    }
+
+    debug!("Check if the renewal duration is longer than or equal to one year.");
+
+    let input_data = util::load_cell_data(input_index, Source::Input)?;
+    let output_data = util::load_cell_data(output_index, Source::Output)?;
+    let input_expired_at = data_parser::account_cell::get_expired_at(&input_data);
+    let output_expired_at = data_parser::account_cell::get_expired_at(&output_data);
+    let duration = output_expired_at - input_expired_at;
+
+    das_assert!(
+        duration >= 365 * 86400,
+        AccountCellErrorCode::AccountCellRenewDurationMustLongerThanYear,
+        "outputs[{}] The AccountCell renew should be longer than 1 year. (current: {}, expected: >= 31_536_000)",
+        output_index,
+        duration
+    );
+
+    let length_in_price = util::get_length_in_price(output_cell_witness_reader.account().len() as u64);
+    // Find out register price in from ConfigCellRegister.
+    let price = prices
+        .iter()
+        .find(|item| u8::from(item.length()) == length_in_price)
+        .ok_or(ErrorCode::ItemMissing)?;
+
+    let renew_price_in_usd = u64::from(price.renew()); // x USD
+
+    Ok((input_index, duration, renew_price_in_usd))
+}
+
+/// Verify the renewal paid by capacity through the IncomeCell, return the paid capacity.
+fn verify_renew_paid_by_capacity(
+    parser: &WitnessesParser,
+    config_main: ConfigCellMainReader,
+    input_income_cells: &[usize],
+    output_income_cells: &[usize],
+    renewals: &[(usize, u64, u64)],
+) -> Result<u64, Box<dyn ScriptError>> {
+    debug!("Verify if the profit is distribute correctly.");
+    // TODO Unify the following codes to calculate profit from duration.
+
+    let mut profit_map = Map::new();
+    let das_wallet_lock = Script::from(das_wallet_lock());
+
+    let mut exist_capacity = 0;
+    if input_income_cells.len() == 1 {
+        let input_income_cell_witness = util::parse_income_cell_witness(parser, input_income_cells[0], Source::Input)?;
+        let input_income_cell_witness_reader = input_income_cell_witness.as_reader();
+
+        for item in input_income_cell_witness_reader.records().iter() {
+            if util::is_reader_eq(item.belong_to(), das_wallet_lock.as_reader()) {
+                exist_capacity += u64::from(item.capacity());
+            }
+        }
+    }
+
+    let output_income_cell_witness = util::parse_income_cell_witness(parser, output_income_cells[0], Source::Output)?;
+    let output_income_cell_witness_reader = output_income_cell_witness.as_reader();
+    let mut paid = 0;
+    for item in output_income_cell_witness_reader.records().iter() {
+        if util::is_reader_eq(item.belong_to(), das_wallet_lock.as_reader()) {
+            paid += u64::from(item.capacity());
+        }
+    }
+
+    das_assert!(
+        paid > exist_capacity,
+        ErrorCode::IncomeCellConsolidateConditionNotSatisfied,
+        "outputs[{}] There is some record in outputs has less capacity than itself in inputs which is not allowed. (belong_to: {})",
+        output_income_cells[0],
+        das_wallet_lock
+    );
+
+    // The payment of all the AccountCells is aggregated into one record of the DAS wallet.
+    paid -= exist_capacity;
+
+    map_util::add(&mut profit_map, das_wallet_lock.as_slice().to_vec(), paid);
+    verifiers::income_cell::verify_income_cells(parser, profit_map)?;
+
+    debug!("Check if the expired_at field has been updated correctly based on the capacity paid by the user.");
+
+    let quote = util::load_quote(parser)?;
+    verify_renew_durations(renewals, paid as u128, |renew_price_in_usd| {
+        util::calc_yearly_capacity(renew_price_in_usd, quote, 0) as u128
+    })?;
+
+    Ok(paid)
+}
+
+/// Verify the renewal paid by the stablecoin to the DAS wallet, return the capacity occupied by the UDT cells.
+fn verify_renew_paid_by_stablecoin(
+    settings: &[u8],
+    sender_lock: ckb_std::ckb_types::packed::ScriptReader,
+    renewals: &[(usize, u64, u64)],
+) -> Result<u64, Box<dyn ScriptError>> {
+    let udt_type_hash = data_parser::stablecoin_settings::get_type_hash(settings);
+    let decimals = data_parser::stablecoin_settings::get_decimals(settings);
+    let das_wallet_lock = das_wallet_lock();
+
+    let (paid, occupied_capacity) =
+        verifiers::udt::verify_udt_payment(udt_type_hash, sender_lock, das_wallet_lock.as_reader())?;
+
+    debug!("Check if the expired_at field has been updated correctly based on the stablecoin paid by the user.");
+
+    verify_renew_durations(renewals, paid, |renew_price_in_usd| {
+        util::calc_yearly_udt_amount(renew_price_in_usd, decimals)
+    })?;
+
+    Ok(occupied_capacity)
+}
+
+/// Verify the renewal durations of all the AccountCells match the total payment.
+///
+/// The share of each AccountCell is calculated from its own duration and yearly price. The durations can be floated
+/// within the range of one day, but the tolerance is shared by the whole batch rather than granted to every AccountCell,
+/// so the payment should not differ from the sum of the shares by more than one day of the cheapest AccountCell.
+fn verify_renew_durations(
+    renewals: &[(usize, u64, u64)],
+    paid: u128,
+    calc_yearly_amount: impl Fn(u64) -> u128,
+) -> Result<(), Box<dyn ScriptError>> {
+    const YEAR: u128 = 365 * 86400;
+    const DAY: u128 = 86400;
+
+    // All the amounts below are multiplied by YEAR to avoid rounding the share of each AccountCell.
+    let mut total_yearly_amount = 0u128;
+    let mut total_amount = 0u128;
+    let mut tolerance = u128::MAX;
+    for &(input_index, duration, renew_price_in_usd) in renewals.iter() {
+        let yearly_amount = calc_yearly_amount(renew_price_in_usd);
+        let amount = yearly_amount * duration as u128;
+        let daily_amount = yearly_amount * DAY;
+
+        debug!(
+            "inputs[{}] The AccountCell is renewed for {}s, its share should be {} / {} . (renew_price: {})",
+            input_index, duration, amount, YEAR, renew_price_in_usd
+        );
+
+        total_yearly_amount += yearly_amount;
+        total_amount += amount;
+        if daily_amount < tolerance {
+            tolerance = daily_amount;
+        }
+    }
+
+    das_assert!(
+        paid >= total_yearly_amount,
+        AccountCellErrorCode::AccountCellRenewDurationMustLongerThanYear,
+        "The payment should be at least 1 year for every AccountCell. (current: {}, expected: >= {})",
+        paid,
+        total_yearly_amount
+    );
+
+    let paid_amount = paid * YEAR;
+    let min_total_amount = total_amount.saturating_sub(tolerance);
+    let max_total_amount = total_amount + tolerance;
+    das_assert!(
+        paid_amount >= min_total_amount && paid_amount <= max_total_amount,
+        AccountCellErrorCode::AccountCellRenewDurationBiggerThanPayed,
+        "The payment should match the durations with +/- 86400s for the whole batch. (current: {} / {}, expected: {}..={} / {})",
+        paid_amount,
+        YEAR,
+        min_total_amount,
+        max_total_amount,
+        YEAR
+    );
+
+    Ok(())
+}
+
 fn verify_transaction_fee_spent_correctly(
     action: &[u8],
     config: ConfigCellAccountReader,
@@ -1209,7 +1550,7 @@ fn verify_transaction_fee_spent_correctly(
     let account_length = data_parser::account_cell::get_account(&input_data).len() as u64;
 
     let fee = match action {
-        b"transfer_account" => u64::from(config.transfer_account_fee()),
+        b"transfer_account" | b"start_transfer_account" => u64::from(config.transfer_account_fee()),
         b"edit_manager" => u64::from(config.edit_manager_fee()),
         b"edit_records" => u64::from(config.edit_records_fee()),
         _ => u64::from(config.common_fee()),
@@ -1267,7 +1608,7 @@ fn verify_action_throttle<'a>(
         return Err(code_to_error!(ErrorCode::InvalidTransactionStructure));
     } else {
         match action {
-            b"transfer_account" => assert_action_throttle!(
+            b"transfer_account" | b"start_transfer_account" => assert_action_throttle!(
                 input_witness_reader,
                 output_witness_reader,
                 transfer_account_throttle,
@@ -1295,6 +1636,73 @@ fn verify_action_throttle<'a>(
     Ok(())
 }
 
+fn load_account_lock_args(index: usize, source: Source) -> Result<Vec<u8>, Box<dyn ScriptError>> {
+    let lock = high_level::load_cell_lock(index, source)?;
+    Ok(lock.as_reader().args().raw_data().to_vec())
+}
+
+fn verify_owner_lock_unchanged(input_index: usize, output_index: usize) -> Result<(), Box<dyn ScriptError>> {
+    let input_args = load_account_lock_args(input_index, Source::Input)?;
+    let output_args = load_account_lock_args(output_index, Source::Output)?;
+    let (input_owner_type, input_owner_args, _, _) = data_parser::das_lock_args::get_owner_and_manager(&input_args)?;
+    let (output_owner_type, output_owner_args, _, _) = data_parser::das_lock_args::get_owner_and_manager(&output_args)?;
+
+    das_assert!(
+        input_owner_type == output_owner_type && input_owner_args == output_owner_args,
+        AccountCellErrorCode::AccountCellOwnerLockShouldNotBeModified,
+        "outputs[{}] The owner lock args in AccountCell.lock should be consistent in inputs and outputs.",
+        output_index
+    );
+
+    Ok(())
+}
+
+/// Verify the pending owner of the two-step transfer is set in the manager part of the AccountCell.lock.
+fn verify_pending_owner_set(input_index: usize, output_index: usize) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the pending owner is set properly.");
+
+    verify_owner_lock_unchanged(input_index, output_index)?;
+
+    let output_args = load_account_lock_args(output_index, Source::Output)?;
+    let (owner_type, owner_args, pending_owner_type, pending_owner_args) =
+        data_parser::das_lock_args::get_owner_and_manager(&output_args)?;
+
+    das_assert!(
+        owner_type != pending_owner_type || owner_args != pending_owner_args,
+        AccountCellErrorCode::AccountCellPendingOwnerInvalid,
+        "outputs[{}] The pending owner should be different from the owner.",
+        output_index
+    );
+
+    Ok(())
+}
+
+/// Verify the pending owner of the two-step transfer becomes both the owner and the manager of the AccountCell.
+fn verify_pending_owner_accepted(input_index: usize, output_index: usize) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the pending owner becomes the owner.");
+
+    let input_args = load_account_lock_args(input_index, Source::Input)?;
+    let output_args = load_account_lock_args(output_index, Source::Output)?;
+    let (_, _, pending_owner_type, pending_owner_args) =
+        data_parser::das_lock_args::get_owner_and_manager(&input_args)?;
+    let (owner_type, owner_args, manager_type, manager_args) =
+        data_parser::das_lock_args::get_owner_and_manager(&output_args)?;
+
+    das_assert!(
+        owner_type == pending_owner_type
+            && owner_args == pending_owner_args
+            && manager_type == pending_owner_type
+            && manager_args == pending_owner_args,
+        AccountCellErrorCode::AccountCellPendingOwnerInvalid,
+        "outputs[{}] Both the owner and the manager should be the pending owner. (expected: 0x{}{})",
+        output_index,
+        util::hex_string(&[pending_owner_type]),
+        util::hex_string(pending_owner_args)
+    );
+
+    Ok(())
+}
+
 fn verify_account_is_locked_for_cross_chain<'a>(
     output_account_index: usize,
     output_witness_reader: &Box<dyn AccountCellDataReaderMixer + 'a>,
@@ -1372,7 +1780,10 @@ fn verify_multi_sign(
     let mut ckb_multi_context = new_context!();
     log_loading!(DynLibName::CKBMultisig, type_id_table);
     let ckb_multi_lib = load_lib!(ckb_multi_context, DynLibName::CKBMultisig, type_id_table);
-    sign_lib.ckb_multisig = load_1_method!(ckb_multi_lib);
+    sign_lib.set_methods(
+        DynLibName::CKBMultisig,
+        load_methods!(ckb_multi_lib, DynLibName::CKBMultisig),
+    );
 
     if cfg!(not(feature = "dev")) {
         sign_lib
@@ -1388,3 +1799,34 @@ fn verify_multi_sign(
 
     Ok(())
 }
+
+#[cfg(any(feature = "dev", feature = "local"))]
+fn verify_unlock_proof(
+    proof: &CrossChainProof,
+    input_account_index: usize,
+    account_id: &[u8],
+    output_lock_args: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    verifiers::cross_chain::verify_unlock_proof::<verifiers::cross_chain::StandInLightClient>(
+        proof,
+        input_account_index,
+        account_id,
+        output_lock_args,
+    )
+}
+
+/// Unlocking by proof is disabled on these networks until the light clients of the other chains are deployed, the
+/// keepers' multisig is the only way to unlock for now.
+#[cfg(any(feature = "testnet", feature = "mainnet"))]
+fn verify_unlock_proof(
+    _proof: &CrossChainProof,
+    input_account_index: usize,
+    _account_id: &[u8],
+    _output_lock_args: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    warn!(
+        "inputs[{}] Unlocking by the proof of the release event is not supported on this network.",
+        input_account_index
+    );
+    Err(code_to_error!(ErrorCode::CrossChainProofInvalid))
+}
@@ -25,7 +25,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     };
     let action = action_cp.as_slice();
 
-    util::is_system_off(&parser)?;
+    util::require_action_enabled(&parser, action)?;
 
     debug!(
         "Route to {:?} action ...",
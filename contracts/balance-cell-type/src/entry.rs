@@ -21,7 +21,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         util::find_cells_by_type_id(ScriptType::Lock, das_lock_reader.code_hash().into(), Source::Output)?;
 
     let mut parser = WitnessesParser::new()?;
-    let mut is_unknown_action = false;
+    let mut should_exec_eip712_lib = false;
 
     if input_cells.len() > 0 {
         debug!("Check if cells with das-lock in inputs has correct typed data hash in its signature witness.");
@@ -32,11 +32,17 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         };
         let action = action_cp.as_slice();
 
-        parser.parse_cell()?;
+        // The witnesses of cells are never read here, so only index them.
+        parser.parse_cell_lazily()?;
 
         // Because the semantic requirement of each action, some other type script is required to generate DAS_MESSAGE field in EIP712 properly.
         match action {
-            b"transfer_account" | b"edit_manager" | b"edit_records" => {
+            b"transfer_account"
+            | b"start_transfer_account"
+            | b"accept_transfer_account"
+            | b"cancel_transfer_account"
+            | b"edit_manager"
+            | b"edit_records" => {
                 util::require_type_script(
                     &parser,
                     TypeScript::AccountCellType,
@@ -92,16 +98,28 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                     ErrorCode::InvalidTransactionStructure,
                 )?;
             }
-            b"enable_sub_account" | b"update_sub_account" => {
+            b"renew_account" => {
+                util::require_type_script(
+                    &parser,
+                    TypeScript::AccountCellType,
+                    Source::Input,
+                    ErrorCode::InvalidTransactionStructure,
+                )?;
+                // The account-cell-type do not execute eip712-lib when renewing, so the BalanceCell need to do it.
+                should_exec_eip712_lib = true;
+            }
+            b"enable_sub_account" | b"config_sub_account" | b"update_sub_account" => {
                 util::require_type_script(
                     &parser,
                     TypeScript::SubAccountCellType,
                     Source::Output,
                     ErrorCode::InvalidTransactionStructure,
                 )?;
+                // The sub-account-cell-type do not execute eip712-lib, so the BalanceCell need to do it.
+                should_exec_eip712_lib = true;
             }
             _ => {
-                is_unknown_action = true;
+                should_exec_eip712_lib = true;
             }
         }
     } else {
@@ -113,7 +131,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         verifiers::balance_cell::verify_das_lock_always_with_type(config_main_reader)?;
     }
 
-    if input_cells.len() > 0 && is_unknown_action {
+    if input_cells.len() > 0 && should_exec_eip712_lib {
         util::exec_by_type_id(&parser, TypeScript::EIP712Lib, &[])?;
     }
 
@@ -6,12 +6,15 @@ use ckb_std::ckb_constants::Source;
 use ckb_std::debug;
 use ckb_std::high_level::{load_cell_lock_hash, load_cell_type};
 use das_core::constants::*;
+use das_core::data_parser::config_cell;
 use das_core::error::*;
 use das_core::witness_parser::WitnessesParser;
 use das_core::{assert, code_to_error, util, warn};
 use das_types::constants::DataType;
 use das_types::prelude::Entity;
 
+use crate::validators;
+
 pub fn main() -> Result<(), Box<dyn ScriptError>> {
     debug!("====== Running config-cell-type ======");
 
@@ -21,7 +24,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         None => return Err(code_to_error!(ErrorCode::ActionNotSupported)),
     };
 
-    // ⚠️ NEVER use util::is_system_off here! That will make it impossible to turn the system back on by updating the ConfigCellMain. ⚠️
+    // ⚠️ NEVER use util::is_system_off or util::require_action_enabled here! That will make it impossible to turn the system back on by updating the ConfigCellMain. ⚠️
 
     if action == b"config" {
         debug!("Route to config action ...");
@@ -51,6 +54,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         // Define DAS official super lock.
         let super_lock = super_lock();
         let super_lock_hash = util::blake2b_256(super_lock.as_slice());
+        let mut timestamp_opt = None;
         for (i, output_cell_index) in output_cells.into_iter().enumerate() {
             // The ConfigCell in outputs must has the same lock script as super lock.
             // Why we do not limit the input ConfigCell's lock script is because when super lock need to be updated,
@@ -65,6 +69,17 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
             );
 
             let output_config_id = get_config_id(output_cell_index, Source::Output)?;
+            let output_data = util::load_cell_data(output_cell_index, Source::Output)?;
+
+            assert!(
+                config_cell::is_valid_length(&output_data),
+                ErrorCode::InvalidCellData,
+                "outputs[{}] The data of {:?} should be {} or {} bytes.",
+                output_cell_index,
+                output_config_id,
+                config_cell::HASH_LENGTH,
+                config_cell::STAGED_DATA_LENGTH
+            );
 
             if input_cells.len() > 0 {
                 let input_cell_index = input_cells[i];
@@ -75,6 +90,19 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                     ErrorCode::InvalidTransactionStructure,
                     "The Config ID in ConfigCells should be the same order in both inputs and outputs."
                 );
+
+                let input_data = util::load_cell_data(input_cell_index, Source::Input)?;
+                verify_config_update(&parser, &mut timestamp_opt, output_config_id, &input_data, &output_data)?;
+            } else {
+                assert!(
+                    config_cell::get_pending_opt(&output_data).is_none(),
+                    ErrorCode::ConfigCellStagingError,
+                    "outputs[{}] The newly created {:?} should not contain a pending update.",
+                    output_cell_index,
+                    output_config_id
+                );
+
+                validators::validate_config(&parser, output_config_id, &config_cell::get_active_hash(&output_data))?;
             }
         }
     } else {
@@ -85,6 +113,116 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     Ok(())
 }
 
+fn load_timestamp(timestamp_opt: &mut Option<u64>) -> Result<u64, Box<dyn ScriptError>> {
+    match timestamp_opt {
+        Some(timestamp) => Ok(*timestamp),
+        None => {
+            let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+            *timestamp_opt = Some(timestamp);
+            Ok(timestamp)
+        }
+    }
+}
+
+/// Any update of the config should be staged as a pending entity at first, and it can only replace the active entity
+/// after CONFIG_CELL_ACTIVATION_DELAY, the only exception is switching the status of ConfigCellMain in emergency.
+///
+/// Other scripts only read the active entity, so an effective pending entity must be promoted here before it is used.
+fn verify_config_update(
+    parser: &WitnessesParser,
+    timestamp_opt: &mut Option<u64>,
+    config_id: DataType,
+    input_data: &[u8],
+    output_data: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    let input_active_hash = config_cell::get_active_hash(input_data);
+    let input_pending_opt = config_cell::get_pending_opt(input_data);
+    let output_active_hash = config_cell::get_active_hash(output_data);
+    let output_pending_opt = config_cell::get_pending_opt(output_data);
+
+    debug!(
+        "Check if the active {:?} is replaced only by the effective pending one ...",
+        config_id
+    );
+
+    let mut effective_hash = input_active_hash;
+    let mut input_pending_is_effective = false;
+    if let Some((pending_hash, effective_at)) = input_pending_opt {
+        if load_timestamp(timestamp_opt)? >= effective_at {
+            effective_hash = pending_hash;
+            input_pending_is_effective = true;
+        }
+    }
+
+    if output_active_hash != effective_hash {
+        assert!(
+            config_id == DataType::ConfigCellMain,
+            ErrorCode::ConfigCellStagingError,
+            "The active {:?} can not be modified directly, it should be staged as a pending update at first.",
+            config_id
+        );
+
+        verify_status_only_change(parser, &effective_hash, &output_active_hash)?;
+        validators::validate_config(parser, config_id, &output_active_hash)?;
+    }
+
+    debug!("Check if the pending {:?} is staged with enough delay ...", config_id);
+
+    if let Some((pending_hash, effective_at)) = output_pending_opt {
+        if !input_pending_is_effective && output_pending_opt == input_pending_opt {
+            debug!("The pending {:?} is not modified, skip.", config_id);
+        } else {
+            let timestamp = load_timestamp(timestamp_opt)?;
+
+            assert!(
+                effective_at >= timestamp + CONFIG_CELL_ACTIVATION_DELAY,
+                ErrorCode::ConfigCellStagingError,
+                "The pending {:?} should take effect at least {}s later.(current: {}, effective_at: {})",
+                config_id,
+                CONFIG_CELL_ACTIVATION_DELAY,
+                timestamp,
+                effective_at
+            );
+
+            validators::validate_config(parser, config_id, &pending_hash)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_status_only_change(
+    parser: &WitnessesParser,
+    current_hash: &[u8; 32],
+    new_hash: &[u8; 32],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if only the status of ConfigCellMain is modified in emergency ...");
+
+    let (_, current) = validators::load_config_witness(parser, DataType::ConfigCellMain, current_hash)?;
+    let (_, new) = validators::load_config_witness(parser, DataType::ConfigCellMain, new_hash)?;
+
+    // The status is the first field of ConfigCellMain and it is a fixed size Uint8, so the offsets of all fields keep the
+    // same when only the status is modified, then the molecule header tells where the status byte is.
+    let status_offset = current
+        .get(4..8)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+        .ok_or(ErrorCode::ConfigCellWitnessDecodingError)?;
+    let is_status_only_change = current.len() == new.len()
+        && current
+            .iter()
+            .zip(new.iter())
+            .enumerate()
+            .all(|(i, (a, b))| i == status_offset || a == b);
+
+    assert!(
+        is_status_only_change,
+        ErrorCode::ConfigCellStagingError,
+        "Only the status of ConfigCellMain can be modified directly, the other fields should be staged as a pending update at first."
+    );
+
+    Ok(())
+}
+
 fn get_config_id(cell_index: usize, source: Source) -> Result<DataType, Box<dyn ScriptError>> {
     let cell_type = load_cell_type(cell_index, source)
         .map_err(|e| Error::<ErrorCode>::from(e))?
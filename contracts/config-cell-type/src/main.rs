@@ -12,6 +12,7 @@
 
 // define modules
 mod entry;
+mod validators;
 
 use ckb_std::default_alloc;
 
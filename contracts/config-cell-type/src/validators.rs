@@ -0,0 +1,291 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::result::Result;
+
+use ckb_std::debug;
+use das_core::constants::*;
+use das_core::error::*;
+use das_core::witness_parser::WitnessesParser;
+use das_core::{assert, code_to_error, util, warn};
+use das_types::constants::{DataType, WITNESS_HEADER_BYTES, WITNESS_LENGTH_BYTES, WITNESS_TYPE_BYTES};
+use das_types::packed::*;
+use das_types::prelude::Entity;
+
+/// Find the witness of the config entity with the given hash and verify its content according to the config ID.
+pub fn validate_config(
+    parser: &WitnessesParser,
+    config_id: DataType,
+    expected_hash: &[u8; 32],
+) -> Result<(), Box<dyn ScriptError>> {
+    let (i, raw) = load_config_witness(parser, config_id, expected_hash)?;
+
+    debug!("witnesses[{:>2}] Validating the content of {:?} ...", i, config_id);
+
+    macro_rules! decode {
+        ($entity_type:ty) => {
+            <$entity_type>::from_compatible_slice(&raw).map_err(|e| {
+                warn!("witnesses[{:>2}] Decoding {:?} failed: {}", i, config_id, e);
+                ErrorCode::ConfigCellWitnessDecodingError
+            })?
+        };
+    }
+
+    match config_id {
+        DataType::ConfigCellAccount => validate_account(i, decode!(ConfigCellAccount).as_reader()),
+        DataType::ConfigCellMain => validate_main(i, decode!(ConfigCellMain).as_reader()),
+        DataType::ConfigCellPrice => validate_price(i, decode!(ConfigCellPrice).as_reader()),
+        DataType::ConfigCellProfitRate => validate_profit_rate(i, decode!(ConfigCellProfitRate).as_reader()),
+        DataType::ConfigCellCharSetEmoji
+        | DataType::ConfigCellCharSetDigit
+        | DataType::ConfigCellCharSetEn
+        | DataType::ConfigCellCharSetZhHans
+        | DataType::ConfigCellCharSetZhHant
+        | DataType::ConfigCellCharSetJa
+        | DataType::ConfigCellCharSetKo
+        | DataType::ConfigCellCharSetRu
+        | DataType::ConfigCellCharSetTr
+        | DataType::ConfigCellCharSetTh
+        | DataType::ConfigCellCharSetVi => validate_char_set(i, config_id, &raw),
+        _ => {
+            debug!("witnesses[{:>2}] There is no validator for {:?}, skip.", i, config_id);
+            Ok(())
+        }
+    }
+}
+
+/// Find the witness of the config entity with the given hash, the witness is returned without header and type.
+pub fn load_config_witness(
+    parser: &WitnessesParser,
+    config_id: DataType,
+    expected_hash: &[u8; 32],
+) -> Result<(usize, Vec<u8>), Box<dyn ScriptError>> {
+    // There may be witnesses of the same DataType for both the inputs and outputs, so the hash is used to tell them apart.
+    for &(i, data_type) in parser.witnesses.iter() {
+        if data_type != config_id {
+            continue;
+        }
+
+        let witness = util::load_das_witnesses(i)?;
+        let entity = witness
+            .get((WITNESS_HEADER_BYTES + WITNESS_TYPE_BYTES)..)
+            .ok_or(ErrorCode::ConfigCellWitnessDecodingError)?;
+
+        if &util::blake2b_256(entity) == expected_hash {
+            return Ok((i, entity.to_vec()));
+        }
+    }
+
+    warn!(
+        "Can not find the witness of {:?} which matches the hash in the cell data.(expected_hash: 0x{})",
+        config_id,
+        util::hex_string(expected_hash)
+    );
+    Err(code_to_error!(ErrorCode::ConfigIsPartialMissing))
+}
+
+fn validate_account(i: usize, config: ConfigCellAccountReader) -> Result<(), Box<dyn ScriptError>> {
+    macro_rules! assert_throttle {
+        ($field:ident) => {
+            assert!(
+                u32::from(config.$field()) > 0,
+                ErrorCode::ConfigCellIsInvalid,
+                "witnesses[{:>2}] The ConfigCellAccount.{} should be greater than 0.",
+                i,
+                stringify!($field)
+            );
+        };
+    }
+
+    assert_throttle!(transfer_account_throttle);
+    assert_throttle!(edit_manager_throttle);
+    assert_throttle!(edit_records_throttle);
+
+    Ok(())
+}
+
+fn validate_main(i: usize, config: ConfigCellMainReader) -> Result<(), Box<dyn ScriptError>> {
+    macro_rules! assert_type_id {
+        ($table:ident, $field:ident) => {
+            assert!(
+                config.$table().$field().raw_data() != &[0u8; 32],
+                ErrorCode::ConfigCellIsInvalid,
+                "witnesses[{:>2}] The ConfigCellMain.{}.{} should not be empty.",
+                i,
+                stringify!($table),
+                stringify!($field)
+            );
+        };
+    }
+
+    assert_type_id!(type_id_table, account_cell);
+    assert_type_id!(type_id_table, apply_register_cell);
+    assert_type_id!(type_id_table, account_sale_cell);
+    assert_type_id!(type_id_table, account_auction_cell);
+    assert_type_id!(type_id_table, balance_cell);
+    assert_type_id!(type_id_table, income_cell);
+    assert_type_id!(type_id_table, offer_cell);
+    assert_type_id!(type_id_table, pre_account_cell);
+    assert_type_id!(type_id_table, proposal_cell);
+    assert_type_id!(type_id_table, reverse_record_cell);
+    assert_type_id!(type_id_table, reverse_record_root_cell);
+    assert_type_id!(type_id_table, sub_account_cell);
+    assert_type_id!(type_id_table, eip712_lib);
+
+    assert_type_id!(das_lock_type_id_table, ckb_signhash);
+    assert_type_id!(das_lock_type_id_table, ckb_multisig);
+    assert_type_id!(das_lock_type_id_table, ed25519);
+    assert_type_id!(das_lock_type_id_table, eth);
+    assert_type_id!(das_lock_type_id_table, tron);
+    assert_type_id!(das_lock_type_id_table, doge);
+
+    Ok(())
+}
+
+fn validate_price(i: usize, config: ConfigCellPriceReader) -> Result<(), Box<dyn ScriptError>> {
+    let mut found = [false; ACCOUNT_MAX_PRICED_LENGTH as usize];
+    for item in config.prices().iter() {
+        let length = u8::from(item.length());
+
+        assert!(
+            length >= 1 && length <= ACCOUNT_MAX_PRICED_LENGTH,
+            ErrorCode::ConfigCellIsInvalid,
+            "witnesses[{:>2}] The length of prices in ConfigCellPrice should be in range 1..={}, but {} found.",
+            i,
+            ACCOUNT_MAX_PRICED_LENGTH,
+            length
+        );
+
+        let index = (length - 1) as usize;
+        assert!(
+            !found[index],
+            ErrorCode::ConfigCellIsInvalid,
+            "witnesses[{:>2}] The price of length {} in ConfigCellPrice is duplicated.", i, length
+        );
+
+        found[index] = true;
+    }
+
+    for (index, item) in found.iter().enumerate() {
+        assert!(
+            *item,
+            ErrorCode::ConfigCellIsInvalid,
+            "witnesses[{:>2}] The price of length {} in ConfigCellPrice is missing.",
+            i,
+            index + 1
+        );
+    }
+
+    Ok(())
+}
+
+fn validate_profit_rate(i: usize, config: ConfigCellProfitRateReader) -> Result<(), Box<dyn ScriptError>> {
+    // Every group of profit rates is distributed from the same profit, so their sum should not exceed RATE_BASE too.
+    macro_rules! assert_rates {
+        ($group:expr, $( $field:ident ),+) => {{
+            let mut total = 0u64;
+            $(
+                let rate = u32::from(config.$field()) as u64;
+                assert!(
+                    rate <= RATE_BASE,
+                    ErrorCode::ConfigCellIsInvalid,
+                    "witnesses[{:>2}] The ConfigCellProfitRate.{} should be less than or equal to {}, but {} found.",
+                    i,
+                    stringify!($field),
+                    RATE_BASE,
+                    rate
+                );
+                total += rate;
+            )+
+
+            assert!(
+                total <= RATE_BASE,
+                ErrorCode::ConfigCellIsInvalid,
+                "witnesses[{:>2}] The sum of the profit rates of {} should be less than or equal to {}, but {} found.",
+                i,
+                $group,
+                RATE_BASE,
+                total
+            );
+        }};
+    }
+
+    assert_rates!("register", channel, inviter, proposal_create, proposal_confirm);
+    assert_rates!("income", income_consolidate);
+    assert_rates!("sale", sale_buyer_inviter, sale_buyer_channel, sale_das);
+    assert_rates!(
+        "auction",
+        auction_bidder_inviter,
+        auction_bidder_channel,
+        auction_das,
+        auction_prev_bidder
+    );
+
+    Ok(())
+}
+
+fn validate_char_set(i: usize, config_id: DataType, raw: &[u8]) -> Result<(), Box<dyn ScriptError>> {
+    let length = match raw.get(..WITNESS_LENGTH_BYTES) {
+        Some(length_bytes) => {
+            let mut tmp = [0u8; 4];
+            tmp.copy_from_slice(length_bytes);
+            u32::from_le_bytes(tmp) as usize
+        }
+        None => {
+            warn!("witnesses[{:>2}] The data of {:?} is empty.", i, config_id);
+            return Err(code_to_error!(ErrorCode::ConfigCellWitnessDecodingError));
+        }
+    };
+
+    assert!(
+        raw.len() == length && raw.len() > WITNESS_LENGTH_BYTES,
+        ErrorCode::ConfigCellWitnessDecodingError,
+        "witnesses[{:>2}] The {:?} should have length of {} bytes, but {} bytes found.",
+        i,
+        config_id,
+        length,
+        raw.len()
+    );
+
+    // Skip the length and the global flag, the rest is chars which are all ended with 0x00.
+    let data = match raw[(WITNESS_LENGTH_BYTES + 1)..].split_last() {
+        Some((last, data)) => {
+            assert!(
+                *last == 0,
+                ErrorCode::ConfigCellIsInvalid,
+                "witnesses[{:>2}] Every char in {:?} should be ended with 0x00.",
+                i,
+                config_id
+            );
+            data
+        }
+        None => return Ok(()),
+    };
+
+    // The chars are not required to be in any order, because they are only looked up one by one.
+    let mut chars = Vec::new();
+    for char in data.split(|byte| *byte == 0) {
+        assert!(
+            !char.is_empty(),
+            ErrorCode::ConfigCellIsInvalid,
+            "witnesses[{:>2}] There should be no empty char in {:?}.",
+            i,
+            config_id
+        );
+
+        chars.push(char);
+    }
+
+    chars.sort_unstable();
+    for pair in chars.windows(2) {
+        assert!(
+            pair[0] != pair[1],
+            ErrorCode::ConfigCellIsInvalid,
+            "witnesses[{:>2}] The char 0x{} in {:?} is duplicated.",
+            i,
+            util::hex_string(pair[0]),
+            config_id
+        );
+    }
+
+    Ok(())
+}
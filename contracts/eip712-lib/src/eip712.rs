@@ -1,7 +1,6 @@
 use alloc::boxed::Box;
 use alloc::collections::btree_map::BTreeMap;
-use alloc::format;
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 
@@ -11,19 +10,24 @@ use ckb_std::error::SysError;
 use ckb_std::high_level;
 use das_core::constants::*;
 use das_core::error::*;
-use das_core::sign_util::calc_digest_by_input_group;
+use das_core::sign_util::{calc_digest_by_input_group, is_contract_wallet_lock, is_personal_sign_lock};
 use das_core::witness_parser::WitnessesParser;
-use das_core::{assert as das_assert, code_to_error, data_parser, debug, util, warn};
+use das_core::{assert as das_assert, code_to_error, data_parser, debug, util, verifiers, warn};
+use das_dynamic_libs::constants::DynLibName;
+use das_dynamic_libs::sign_lib::SignLib;
+use das_dynamic_libs::{load_lib, load_methods, log_loading, new_context};
 use das_types::constants::{DasLockType, DataType, LockRole};
 use das_types::mixer::AccountCellDataMixer;
 use das_types::packed as das_packed;
 use das_types::prelude::*;
+use eip712::builder::{TypedAction, TypedAddress, TypedCell, TypedCellData, TypedScript, TypedTransaction};
 use eip712::eip712::*;
+use eip712::hash_data;
+use eip712::semantic::{self, SemanticRecord};
 use eip712::util::*;
-use eip712::{hash_data, typed_data_v4};
 
-const DATA_OMIT_SIZE: usize = 20;
-const PARAM_OMIT_SIZE: usize = 10;
+const EIP712_SCHEMA_V1: u8 = 1;
+const EIP712_SCHEMA_V2: u8 = 2;
 
 pub fn verify_eip712_hashes(
     parser: &WitnessesParser,
@@ -43,6 +47,7 @@ pub fn verify_eip712_hashes(
         _ => 0,
     };
     let mut input_groups_idxs: BTreeMap<Vec<u8>, Vec<usize>> = BTreeMap::new();
+    let mut eth_groups_idxs: BTreeMap<Vec<u8>, Vec<usize>> = BTreeMap::new();
     loop {
         let ret = high_level::load_cell_lock(i, Source::Input);
         match ret {
@@ -56,13 +61,16 @@ pub fn verify_eip712_hashes(
                     } else {
                         data_parser::das_lock_args::get_owner_type(lock_reader.args().raw_data())
                     };
-                    if type_of_args != DasLockType::ETHTypedData as u8 {
+                    if type_of_args == DasLockType::ETHTypedData as u8 {
+                        input_groups_idxs.entry(args.to_vec()).or_default().push(i);
+                    } else if type_of_args == DasLockType::ETH as u8 {
+                        // The ETH signatures may be EIP-191 personal_sign or contract wallet attestations, they need to be checked later.
+                        eth_groups_idxs.entry(args.to_vec()).or_default().push(i);
+                    } else {
                         debug!(
                             "Inputs[{}] is not the address type supporting EIP712, skip verification for hash.",
                             i
                         );
-                    } else {
-                        input_groups_idxs.entry(args.to_vec()).or_default().push(i);
                     }
                 }
             }
@@ -84,15 +92,41 @@ pub fn verify_eip712_hashes(
         debug!("Check if hashes of typed data in witnesses is correct ...");
 
         let (digest_and_hash, eip712_chain_id) = tx_to_digest(input_groups_idxs)?;
-        let mut typed_data = tx_to_eip712_typed_data(&parser, eip712_chain_id, tx_to_das_message)?;
+        // Both versions of typed data are accepted during the migration, so they are only generated when some signatures need them.
+        let mut typed_data_v1 = None;
+        let mut typed_data_v2 = None;
         for index in digest_and_hash.keys() {
             let item = digest_and_hash.get(index).unwrap();
+            let typed_data = match item.schema_version {
+                EIP712_SCHEMA_V1 => {
+                    if typed_data_v1.is_none() {
+                        typed_data_v1 = Some(tx_to_eip712_typed_data(
+                            &parser,
+                            eip712_chain_id.clone(),
+                            tx_to_das_message,
+                        )?);
+                    }
+                    typed_data_v1.as_mut().unwrap()
+                }
+                _ => {
+                    if typed_data_v2.is_none() {
+                        typed_data_v2 = Some(tx_to_eip712_typed_data_v2(
+                            &parser,
+                            eip712_chain_id.clone(),
+                            tx_to_das_message,
+                        )?);
+                    }
+                    typed_data_v2.as_mut().unwrap()
+                }
+            };
+
             let digest = util::hex_string(&item.digest);
             typed_data.digest(digest.clone());
             let expected_hash = hash_data(&typed_data).unwrap();
 
             debug!(
-                "Calculated hash of EIP712 typed data with digest.(digest: 0x{}, hash: 0x{})",
+                "Calculated hash of EIP712 typed data v{} with digest.(digest: 0x{}, hash: 0x{})",
+                item.schema_version,
                 digest,
                 util::hex_string(&expected_hash)
             );
@@ -111,6 +145,96 @@ pub fn verify_eip712_hashes(
         }
     }
 
+    let role = required_role_opt.unwrap_or(LockRole::Owner);
+    verify_eth_signs(parser, eth_groups_idxs, role, tx_to_das_message)?;
+
+    Ok(())
+}
+
+fn verify_eth_signs(
+    parser: &WitnessesParser,
+    input_groups_idxs: BTreeMap<Vec<u8>, Vec<usize>>,
+    role: LockRole,
+    tx_to_das_message: fn(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>>,
+) -> Result<(), Box<dyn ScriptError>> {
+    let mut personal_signs = Vec::new();
+    let mut attestations = Vec::new();
+    for (args, input_group_idxs) in input_groups_idxs {
+        let init_witness_idx = input_group_idxs[0];
+        let (digest, witness_args_lock) = calc_digest_by_input_group(DasLockType::ETH, input_group_idxs)?;
+        let pubkey_hash = if role == LockRole::Manager {
+            data_parser::das_lock_args::get_manager_lock_args(&args).to_vec()
+        } else {
+            data_parser::das_lock_args::get_owner_lock_args(&args).to_vec()
+        };
+
+        if is_personal_sign_lock(&witness_args_lock) {
+            personal_signs.push((init_witness_idx, pubkey_hash, digest, witness_args_lock));
+        } else if is_contract_wallet_lock(&witness_args_lock) {
+            attestations.push((init_witness_idx, pubkey_hash, digest, witness_args_lock));
+        }
+    }
+
+    if personal_signs.is_empty() && attestations.is_empty() {
+        debug!("There is no EIP-191 personal_sign signature or contract wallet attestation in witnesses, skip checking them ...");
+        return Ok(());
+    }
+
+    let config_main = parser.configs.main()?;
+    let mut sign_lib = SignLib::new();
+    // ⚠️ This must be present at the top level, as we will need to use the libraries later.
+    let mut eth_context = new_context!();
+    // CAREFUL We need to skip the final verification here because transactions are often change when developing, that will break all tests contains EIP191 verification.
+    if cfg!(not(feature = "dev")) {
+        log_loading!(DynLibName::ETH, config_main.das_lock_type_id_table());
+        let eth_lib = load_lib!(eth_context, DynLibName::ETH, config_main.das_lock_type_id_table());
+        sign_lib.set_methods(DynLibName::ETH, load_methods!(eth_lib, DynLibName::ETH));
+    }
+
+    if !personal_signs.is_empty() {
+        debug!("Check if EIP-191 personal_sign signatures in witnesses is correct ...");
+
+        let das_message = tx_to_das_message(parser)?;
+        for (index, pubkey_hash, digest, witness_args_lock) in personal_signs {
+            let hash = to_personal_sign_hash(&das_message, &digest);
+
+            debug!(
+                "Inputs[{}] Calculated hash of EIP191 personal_sign message with digest.(digest: 0x{}, hash: 0x{})",
+                index,
+                util::hex_string(&digest),
+                util::hex_string(&hash)
+            );
+
+            if cfg!(not(feature = "dev")) {
+                let ret = sign_lib.validate(
+                    DasLockType::ETH,
+                    0i32,
+                    hash,
+                    witness_args_lock[..SECP_SIGNATURE_SIZE].to_vec(),
+                    pubkey_hash,
+                );
+                das_assert!(
+                    ret.is_ok(),
+                    ErrorCode::EIP712SignatureError,
+                    "Inputs[{}] The EIP191 personal_sign signature is invalid.(error_code: {:?})",
+                    index,
+                    ret.err()
+                );
+            }
+        }
+    }
+
+    for (index, wallet_address, digest, witness_args_lock) in attestations {
+        verifiers::contract_wallet::verify_attestation(
+            &sign_lib,
+            config_main,
+            index,
+            &wallet_address,
+            &digest,
+            &witness_args_lock,
+        )?;
+    }
+
     Ok(())
 }
 
@@ -131,6 +255,7 @@ pub fn verify_eip712_hashes_if_has_das_lock(
 struct DigestAndHash {
     digest: [u8; 32],
     typed_data_hash: [u8; 32],
+    schema_version: u8,
 }
 
 fn tx_to_digest(
@@ -143,13 +268,25 @@ fn tx_to_digest(
 
         let (message, witness_args_lock) = calc_digest_by_input_group(DasLockType::ETHTypedData, input_group_idxs)?;
 
+        // The signatures of v1 typed data have no version byte, the signatures of other versions have the version byte at the end.
+        let v1_signature_size = SECP_SIGNATURE_SIZE + CKB_HASH_DIGEST + EIP712_CHAINID_SIZE;
+        let schema_version = if witness_args_lock.len() == v1_signature_size {
+            EIP712_SCHEMA_V1
+        } else if witness_args_lock.len() == v1_signature_size + EIP712_SCHEMA_VERSION_SIZE {
+            witness_args_lock[v1_signature_size]
+        } else {
+            0
+        };
+
         das_assert!(
-            witness_args_lock.len() == SECP_SIGNATURE_SIZE + CKB_HASH_DIGEST + EIP712_CHAINID_SIZE,
+            schema_version == EIP712_SCHEMA_V1 || schema_version == EIP712_SCHEMA_V2,
             ErrorCode::EIP712SignatureError,
-            "Inputs[{}] The length of signature is invalid.(current: {}, expected: {})",
+            "Inputs[{}] The length or the schema version of signature is invalid.(length: {}, expected_length: {} or {}, schema_version: {})",
             init_witness_idx,
             witness_args_lock.len(),
-            SECP_SIGNATURE_SIZE + CKB_HASH_DIGEST + EIP712_CHAINID_SIZE
+            v1_signature_size,
+            v1_signature_size + EIP712_SCHEMA_VERSION_SIZE,
+            schema_version
         );
 
         if eip712_chain_id.is_empty() {
@@ -164,6 +301,7 @@ fn tx_to_digest(
             DigestAndHash {
                 digest: message,
                 typed_data_hash: typed_data_hash.try_into().unwrap(),
+                schema_version,
             },
         );
     }
@@ -176,183 +314,164 @@ pub fn tx_to_eip712_typed_data(
     chain_id: Vec<u8>,
     tx_to_das_message: fn(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>>,
 ) -> Result<TypedDataV4, Box<dyn ScriptError>> {
-    let type_id_table = parser.configs.main()?.type_id_table();
+    let typed_data = tx_to_typed_transaction(parser, chain_id, tx_to_das_message, EIP712_SCHEMA_V1)?.to_typed_data_v1();
 
-    let plain_text = tx_to_das_message(parser)?;
-    let tx_action = to_typed_action(parser)?;
-    let (inputs_capacity, inputs) = to_typed_cells(parser, type_id_table, Source::Input)?;
-    let (outputs_capacity, outputs) = to_typed_cells(parser, type_id_table, Source::Output)?;
-    let inputs_capacity_str = to_semantic_capacity(inputs_capacity);
-    let outputs_capacity_str = to_semantic_capacity(outputs_capacity);
+    debug!("Extracted typed data: {}", typed_data.to_json());
 
-    let fee_str = if outputs_capacity <= inputs_capacity {
-        to_semantic_capacity(inputs_capacity - outputs_capacity)
-    } else {
-        format!("-{}", to_semantic_capacity(outputs_capacity - inputs_capacity))
-    };
+    Ok(typed_data)
+}
 
-    let chain_id_num = u64::from_be_bytes(chain_id.try_into().unwrap()).to_string();
-    let typed_data = typed_data_v4!({
-        types: {
-            EIP712Domain: {
-                chainId: "uint256",
-                name: "string",
-                verifyingContract: "address",
-                version: "string"
-            },
-            Action: {
-                action: "string",
-                params: "string"
-            },
-            Cell: {
-                capacity: "string",
-                lock: "string",
-                type: "string",
-                data: "string",
-                extraData: "string"
-            },
-            Transaction: {
-                DAS_MESSAGE: "string",
-                inputsCapacity: "string",
-                outputsCapacity: "string",
-                fee: "string",
-                action: "Action",
-                inputs: "Cell[]",
-                outputs: "Cell[]",
-                digest: "bytes32"
-            }
-        },
-        primaryType: "Transaction",
-        domain: {
-            chainId: chain_id_num,
-            name: "da.systems",
-            verifyingContract: "0x0000000000000000000000000000000020210722",
-            version: "1"
-        },
-        message: {
-            DAS_MESSAGE: plain_text,
-            inputsCapacity: inputs_capacity_str,
-            outputsCapacity: outputs_capacity_str,
-            fee: fee_str,
-            action: tx_action,
-            inputs: inputs,
-            outputs: outputs,
-            digest: ""
-        }
-    });
+/// Generate the v2 typed data, see `TypedTransaction::to_typed_data_v2` for the differences from v1.
+pub fn tx_to_eip712_typed_data_v2(
+    parser: &WitnessesParser,
+    chain_id: Vec<u8>,
+    tx_to_das_message: fn(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>>,
+) -> Result<TypedDataV4, Box<dyn ScriptError>> {
+    let typed_data = tx_to_typed_transaction(parser, chain_id, tx_to_das_message, EIP712_SCHEMA_V2)?.to_typed_data_v2();
 
-    #[cfg(debug_assertions)]
-    // WARNING The keys in output may be in wrong camecase, it is OK, it is just cause by the `debug!` macro
-    // print all keys base on the struct in Rust.
-    debug!("Extracted typed data: {}", typed_data);
+    debug!("Extracted typed data v2: {}", typed_data.to_json());
 
     Ok(typed_data)
 }
 
+/// Collect the information of the current transaction, the typed data is built from it by the shared builder in eip712 lib,
+/// so the off-chain services can build the same typed data from a mock transaction.
+fn tx_to_typed_transaction(
+    parser: &WitnessesParser,
+    chain_id: Vec<u8>,
+    tx_to_das_message: fn(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>>,
+    schema_version: u8,
+) -> Result<TypedTransaction, Box<dyn ScriptError>> {
+    let type_id_table = parser.configs.main()?.type_id_table();
+
+    let das_message = tx_to_das_message(parser)?;
+    let action = String::from_utf8(parser.action.clone()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+    let params = parser.params.iter().map(|param| param.raw_data().to_vec()).collect();
+    let records = if schema_version == EIP712_SCHEMA_V1 {
+        Vec::new()
+    } else {
+        to_typed_records(parser)?
+    };
+    let (inputs_capacity, inputs) = to_typed_cells(parser, type_id_table, Source::Input, schema_version)?;
+    let (outputs_capacity, outputs) = to_typed_cells(parser, type_id_table, Source::Output, schema_version)?;
+
+    Ok(TypedTransaction {
+        chain_id: u64::from_be_bytes(chain_id.try_into().unwrap()),
+        das_message,
+        action: TypedAction { action, params },
+        records,
+        inputs_capacity,
+        outputs_capacity,
+        inputs,
+        outputs,
+    })
+}
+
 pub fn to_semantic_address(
     parser: &WitnessesParser,
     lock_reader: das_packed::ScriptReader,
     role: LockRole,
 ) -> Result<String, Box<dyn ScriptError>> {
-    let address;
-    let hash_type: Vec<u8> = lock_reader.hash_type().as_slice().to_vec();
-    let code_hash = lock_reader.code_hash().raw_data().to_vec();
-    let args = lock_reader.args().raw_data().to_vec();
+    to_typed_address(parser, lock_reader, role)?
+        .to_semantic()
+        .map_err(|_| code_to_error!(ErrorCode::EIP712SematicError))
+}
 
-    match parser.get_lock_script_type(lock_reader) {
+/// Extract the payload of the address from the lock, the rendering is done by `TypedAddress::to_semantic` in eip712 lib.
+fn to_typed_address(
+    parser: &WitnessesParser,
+    lock_reader: das_packed::ScriptReader,
+    role: LockRole,
+) -> Result<TypedAddress, Box<dyn ScriptError>> {
+    let address = match parser.get_lock_script_type(lock_reader) {
         Some(LockScript::DasLock) => {
             // If this is a das-lock, convert it to address base on args.
             let args_in_bytes = lock_reader.args().raw_data();
             let das_lock_type =
                 DasLockType::try_from(args_in_bytes[0]).map_err(|_| ErrorCode::EIP712SerializationError)?;
-            match das_lock_type {
-                DasLockType::CKBSingle => {
-                    let pubkey_hash = if role == LockRole::Owner {
-                        data_parser::das_lock_args::get_owner_lock_args(args_in_bytes).to_vec()
-                    } else {
-                        data_parser::das_lock_args::get_manager_lock_args(args_in_bytes).to_vec()
-                    };
+            let args = if role == LockRole::Owner {
+                data_parser::das_lock_args::get_owner_lock_args(args_in_bytes).to_vec()
+            } else {
+                data_parser::das_lock_args::get_manager_lock_args(args_in_bytes).to_vec()
+            };
 
-                    address = format!(
-                        "{}",
-                        to_short_address(vec![0], pubkey_hash)
-                            .map_err(|_| Error::new(ErrorCode::EIP712SematicError, String::new()))?
-                    )
-                }
-                DasLockType::ETH | DasLockType::ETHTypedData => {
-                    let pubkey_hash = if role == LockRole::Owner {
-                        data_parser::das_lock_args::get_owner_lock_args(args_in_bytes).to_vec()
-                    } else {
-                        data_parser::das_lock_args::get_manager_lock_args(args_in_bytes).to_vec()
-                    };
-                    address = format!("0x{}", util::hex_string(&pubkey_hash));
-                }
-                DasLockType::TRON | DasLockType::Doge => {
-                    let pubkey_hash = if role == LockRole::Owner {
-                        data_parser::das_lock_args::get_owner_lock_args(args_in_bytes)
-                    } else {
-                        data_parser::das_lock_args::get_manager_lock_args(args_in_bytes)
-                    };
-
-                    address = match das_lock_type {
-                        DasLockType::TRON => format!("{}", to_tron_address(pubkey_hash)),
-                        DasLockType::Doge => format!("{}", to_doge_address(pubkey_hash)),
-                        _ => unreachable!(),
-                    };
+            match das_lock_type {
+                DasLockType::CKBSingle => TypedAddress::Ckb(args),
+                DasLockType::ETH | DasLockType::ETHTypedData => TypedAddress::Eth(args),
+                DasLockType::TRON => TypedAddress::Tron(args),
+                DasLockType::Doge => TypedAddress::Doge(args),
+                DasLockType::BTC => {
+                    let pubkey_hash = args.get(1..).ok_or(ErrorCode::EIP712SematicError)?.to_vec();
+                    match args[0] {
+                        BTC_ADDR_TYPE_P2PKH => TypedAddress::BtcP2PKH(pubkey_hash),
+                        BTC_ADDR_TYPE_P2WPKH => TypedAddress::BtcP2WPKH(pubkey_hash),
+                        _ => return Err(code_to_error!(ErrorCode::EIP712SematicError)),
+                    }
                 }
+                // The args of MIXIN is the Ed25519 public key rather than a hash of it.
+                DasLockType::MIXIN => TypedAddress::Ed25519(args),
                 _ => return Err(code_to_error!(ErrorCode::EIP712SematicError)),
             }
         }
-        Some(LockScript::Secp256k1Blake160SignhashLock) => {
-            // If this is a secp256k1_blake160_signhash_all lock, convert it to short address.
-            let args = lock_reader.args().raw_data().to_vec();
-            address = format!(
-                "{}",
-                to_short_address(vec![0], args)
-                    .map_err(|_| Error::new(ErrorCode::EIP712SematicError, String::new()))?
-            )
-        }
-        _ => {
-            // If this is a other lock, convert it to full address.
-            address = format!(
-                "{}",
-                to_full_address(code_hash, hash_type, args)
-                    .map_err(|_| Error::new(ErrorCode::EIP712SematicError, String::new()))?
-            );
-        }
-    }
+        // If this is a secp256k1_blake160_signhash_all lock, convert it to short address.
+        Some(LockScript::Secp256k1Blake160SignhashLock) => TypedAddress::Ckb(lock_reader.args().raw_data().to_vec()),
+        // If this is a other lock, convert it to full address.
+        _ => TypedAddress::Full {
+            code_hash: lock_reader.code_hash().raw_data().to_vec(),
+            hash_type: lock_reader.hash_type().as_slice()[0],
+            args: lock_reader.args().raw_data().to_vec(),
+        },
+    };
 
-    // debug!("lock: {} => address: {}", lock_reader, address);
     Ok(address)
 }
 
-fn to_typed_action(parser: &WitnessesParser) -> Result<Value, Box<dyn ScriptError>> {
-    let action = String::from_utf8(parser.action.clone()).map_err(|_| ErrorCode::EIP712SerializationError)?;
-    let mut params = Vec::new();
-    for param in parser.params.iter() {
-        if param.len() > 10 {
-            params.push(format!(
-                "0x{}...",
-                util::hex_string(&param.raw_data()[..PARAM_OMIT_SIZE])
-            ));
-        } else {
-            params.push(format!("0x{}", util::hex_string(&param.raw_data())));
+pub fn to_semantic_records(
+    parser: &WitnessesParser,
+    index: usize,
+    source: Source,
+) -> Result<Vec<SemanticRecord>, Box<dyn ScriptError>> {
+    let witness = util::parse_account_cell_witness(parser, index, source)?;
+    let witness_reader = witness.as_reader();
+
+    let mut records = Vec::new();
+    for record in witness_reader.records().iter() {
+        records.push(SemanticRecord::from_raw(
+            record.record_type().raw_data(),
+            record.record_key().raw_data(),
+            record.record_label().raw_data(),
+            record.record_value().raw_data(),
+        ));
+    }
+
+    Ok(records)
+}
+
+fn to_typed_records(parser: &WitnessesParser) -> Result<Vec<(String, SemanticRecord)>, Box<dyn ScriptError>> {
+    let mut records = Vec::new();
+    if parser.action.as_slice() == b"edit_records" {
+        let type_id_table_reader = parser.configs.main()?.type_id_table();
+        let (input_cells, output_cells) =
+            util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
+        let old_records = to_semantic_records(parser, input_cells[0], Source::Input)?;
+        let new_records = to_semantic_records(parser, output_cells[0], Source::Output)?;
+
+        for (operation, record) in semantic::records_diff(&old_records, &new_records) {
+            records.push((String::from(operation), record.clone()));
         }
     }
 
-    Ok(typed_data_v4!(@object {
-        action: action,
-        params: params.join(",")
-    }))
+    Ok(records)
 }
 
 fn to_typed_cells(
     parser: &WitnessesParser,
     type_id_table_reader: das_packed::TypeIdTableReader,
     source: Source,
-) -> Result<(u64, Value), Box<dyn ScriptError>> {
+    schema_version: u8,
+) -> Result<(u64, Vec<TypedCell>), Box<dyn ScriptError>> {
     let mut i = 0;
-    let mut cells: Vec<Value> = Vec::new();
+    let mut cells: Vec<TypedCell> = Vec::new();
     let mut total_capacity = 0;
     loop {
         let ret = high_level::load_cell(i, source);
@@ -370,26 +489,25 @@ fn to_typed_cells(
                     continue;
                 }
 
-                let capacity = to_semantic_capacity(capacity_in_shannon);
-                let lock = to_typed_script(
-                    parser,
-                    ScriptType::Lock,
-                    das_packed::ScriptReader::from(cell.lock().as_reader()),
-                );
-
-                macro_rules! extract_and_push {
-                    ($cell_data_to_str:ident, $cell_witness_to_str:ident, $data_type:expr, $type_:expr) => {
-                        let data = $cell_data_to_str(&data_in_bytes)?;
-                        let extra_data = $cell_witness_to_str(parser, &data_in_bytes[..32], $data_type, i, source)?;
-                        cells.push(
-                            typed_data_v4!(@object {
-                                capacity: capacity,
-                                lock: lock,
-                                type: $type_,
-                                data: data,
-                                extraData: extra_data
-                            })
-                        )
+                let lock_script = cell.lock();
+                let lock_reader = das_packed::ScriptReader::from(lock_script.as_reader());
+                let lock = to_typed_script(parser, ScriptType::Lock, lock_reader);
+
+                macro_rules! push_cell {
+                    ($type_:expr, $data:expr) => {
+                        // The owner is only for display since v2, so the locks which can not be converted to address are left empty.
+                        let owner = if schema_version == EIP712_SCHEMA_V1 {
+                            None
+                        } else {
+                            to_typed_address(parser, lock_reader, LockRole::Owner).ok()
+                        };
+                        cells.push(TypedCell {
+                            capacity: capacity_in_shannon,
+                            owner,
+                            lock,
+                            type_: $type_,
+                            data: $data,
+                        })
                     };
                 }
 
@@ -410,36 +528,24 @@ fn to_typed_cells(
                         match type_script_reader.code_hash() {
                             // Handle cells which with DAS type script.
                             x if util::is_reader_eq(x, type_id_table_reader.account_cell()) => {
-                                extract_and_push!(
-                                    to_semantic_account_cell_data,
-                                    to_semantic_account_witness,
+                                let data = to_typed_account_cell_data(
+                                    parser,
+                                    &data_in_bytes,
                                     DataType::AccountCellData,
-                                    type_
-                                );
+                                    i,
+                                    source,
+                                )?;
+                                push_cell!(Some(type_), data);
                             }
                             // Handle cells which with unknown type script.
                             _ => {
-                                let data = to_typed_common_data(&data_in_bytes);
-                                cells.push(typed_data_v4!(@object {
-                                    capacity: capacity,
-                                    lock: lock,
-                                    type: type_,
-                                    data: data,
-                                    extraData: ""
-                                }));
+                                push_cell!(Some(type_), TypedCellData::Raw(data_in_bytes));
                             }
                         }
                     }
                     // Handle cells which has no type script.
                     _ => {
-                        let data = to_typed_common_data(&data_in_bytes);
-                        cells.push(typed_data_v4!(@object {
-                            capacity: capacity,
-                            lock: lock,
-                            type: "",
-                            data: data,
-                            extraData: ""
-                        }));
+                        push_cell!(None, TypedCellData::Raw(data_in_bytes));
                     }
                 }
             }
@@ -454,82 +560,52 @@ fn to_typed_cells(
         i += 1;
     }
 
-    Ok((total_capacity, Value::Array(cells)))
+    Ok((total_capacity, cells))
 }
 
-fn to_typed_script(parser: &WitnessesParser, script_type: ScriptType, script: das_packed::ScriptReader) -> String {
-    let code_hash = if script_type == ScriptType::Lock {
+fn to_typed_script(parser: &WitnessesParser, script_type: ScriptType, script: das_packed::ScriptReader) -> TypedScript {
+    let name = if script_type == ScriptType::Lock {
         match parser.get_lock_script_type(script) {
-            Some(LockScript::AlwaysSuccessLock) => String::from("always-success"),
-            Some(LockScript::DasLock) => String::from("das-lock"),
-            Some(LockScript::Secp256k1Blake160SignhashLock) => String::from("account-cell-type"),
-            Some(LockScript::Secp256k1Blake160MultisigLock) => String::from("account-sale-cell-type"),
-            _ => format!(
-                "0x{}...",
-                util::hex_string(&script.code_hash().raw_data().as_ref()[0..DATA_OMIT_SIZE])
-            ),
+            Some(LockScript::AlwaysSuccessLock) => Some("always-success"),
+            Some(LockScript::DasLock) => Some("das-lock"),
+            Some(LockScript::Secp256k1Blake160SignhashLock) => Some("account-cell-type"),
+            Some(LockScript::Secp256k1Blake160MultisigLock) => Some("account-sale-cell-type"),
+            _ => None,
         }
     } else {
         match parser.get_type_script_type(script) {
-            Some(TypeScript::ApplyRegisterCellType) => String::from("apply-register-cell-type"),
-            Some(TypeScript::AccountCellType) => String::from("account-cell-type"),
-            Some(TypeScript::AccountSaleCellType) => String::from("account-sale-cell-type"),
-            Some(TypeScript::AccountAuctionCellType) => String::from("account-auction-cell-type"),
-            Some(TypeScript::BalanceCellType) => String::from("balance-cell-type"),
-            Some(TypeScript::ConfigCellType) => String::from("config-cell-type"),
-            Some(TypeScript::IncomeCellType) => String::from("income-cell-type"),
-            Some(TypeScript::OfferCellType) => String::from("offer-cell-type"),
-            Some(TypeScript::PreAccountCellType) => String::from("pre-account-cell-type"),
-            Some(TypeScript::ProposalCellType) => String::from("proposal-cell-type"),
-            Some(TypeScript::ReverseRecordCellType) => String::from("reverse-record-cell-type"),
-            Some(TypeScript::SubAccountCellType) => String::from("sub-account-cell-type"),
-            _ => format!(
-                "0x{}...",
-                util::hex_string(&script.code_hash().raw_data().as_ref()[0..DATA_OMIT_SIZE])
-            ),
+            Some(TypeScript::ApplyRegisterCellType) => Some("apply-register-cell-type"),
+            Some(TypeScript::AccountCellType) => Some("account-cell-type"),
+            Some(TypeScript::AccountSaleCellType) => Some("account-sale-cell-type"),
+            Some(TypeScript::AccountAuctionCellType) => Some("account-auction-cell-type"),
+            Some(TypeScript::BalanceCellType) => Some("balance-cell-type"),
+            Some(TypeScript::ConfigCellType) => Some("config-cell-type"),
+            Some(TypeScript::IncomeCellType) => Some("income-cell-type"),
+            Some(TypeScript::OfferCellType) => Some("offer-cell-type"),
+            Some(TypeScript::PreAccountCellType) => Some("pre-account-cell-type"),
+            Some(TypeScript::ProposalCellType) => Some("proposal-cell-type"),
+            Some(TypeScript::ReverseRecordCellType) => Some("reverse-record-cell-type"),
+            Some(TypeScript::SubAccountCellType) => Some("sub-account-cell-type"),
+            _ => None,
         }
     };
 
-    let hash_type = util::hex_string(script.hash_type().as_slice());
-    let args_in_bytes = script.args().raw_data();
-    let args = if args_in_bytes.len() > DATA_OMIT_SIZE {
-        util::hex_string(&args_in_bytes[0..DATA_OMIT_SIZE]) + "..."
-    } else {
-        util::hex_string(args_in_bytes.as_ref())
-    };
-
-    String::new() + &code_hash + ",0x" + &hash_type + ",0x" + &args
-}
-
-fn to_typed_common_data(data_in_bytes: &[u8]) -> String {
-    if data_in_bytes.len() > DATA_OMIT_SIZE {
-        format!("0x{}", util::hex_string(&data_in_bytes[0..DATA_OMIT_SIZE]) + "...")
-    } else if !data_in_bytes.is_empty() {
-        format!("0x{}", util::hex_string(data_in_bytes))
-    } else {
-        String::new()
+    TypedScript {
+        name: name.map(String::from),
+        code_hash: script.code_hash().raw_data().to_vec(),
+        hash_type: script.hash_type().as_slice()[0],
+        args: script.args().raw_data().to_vec(),
     }
 }
 
-fn to_semantic_account_cell_data(data_in_bytes: &[u8]) -> Result<String, Box<dyn ScriptError>> {
-    let account_in_bytes = data_parser::account_cell::get_account(data_in_bytes);
-    let expired_at = data_parser::account_cell::get_expired_at(data_in_bytes);
-    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
-    Ok(format!(
-        "{{ account: {}, expired_at: {} }}",
-        account,
-        &expired_at.to_string()
-    ))
-}
-
-fn to_semantic_account_witness(
+fn to_typed_account_cell_data(
     parser: &WitnessesParser,
-    expected_hash: &[u8],
+    data_in_bytes: &[u8],
     data_type: DataType,
     index: usize,
     source: Source,
-) -> Result<String, Box<dyn ScriptError>> {
-    let (version, _, entity) = parser.verify_with_hash_and_get(expected_hash, data_type, index, source)?;
+) -> Result<TypedCellData, Box<dyn ScriptError>> {
+    let (version, _, entity) = parser.verify_with_hash_and_get(&data_in_bytes[..32], data_type, index, source)?;
     let witness: Box<dyn AccountCellDataMixer> = if version == 2 {
         Box::new(
             das_packed::AccountCellDataV2::from_slice(entity.as_reader().raw_data()).map_err(|_| {
@@ -547,14 +623,12 @@ fn to_semantic_account_witness(
     };
     let witness_reader = witness.as_reader();
 
-    let status = u8::from(witness_reader.status());
-    let records_hash = util::blake2b_256(witness_reader.records().as_slice());
-
-    Ok(format!(
-        "{{ status: {}, records_hash: 0x{} }}",
-        status,
-        util::hex_string(&records_hash)
-    ))
+    Ok(TypedCellData::AccountCell {
+        account: data_parser::account_cell::get_account(data_in_bytes).to_vec(),
+        expired_at: data_parser::account_cell::get_expired_at(data_in_bytes),
+        status: u8::from(witness_reader.status()),
+        records_hash: util::blake2b_256(witness_reader.records().as_slice()).to_vec(),
+    })
 }
 
 #[cfg(test)]
@@ -1,22 +1,25 @@
 use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 use ckb_std::ckb_constants::Source;
 use ckb_std::error::SysError;
 use ckb_std::high_level;
 use das_core::constants::*;
 use das_core::error::*;
+use das_core::witness_parser::sub_account::SubAccountWitnessesParser;
 use das_core::witness_parser::WitnessesParser;
-use das_core::{assert, code_to_error, data_parser, debug, util, warn};
+use das_core::{assert, code_to_error, data_parser, debug, util, verifiers, warn};
 use das_map::map::Map;
 use das_map::util as map_util;
-use das_types::constants::{DataType, LockRole};
+use das_types::constants::{DataType, LockRole, SubAccountConfigFlag};
 use das_types::packed::*;
 use das_types::prelude::*;
+use eip712::semantic;
 use eip712::util::to_semantic_capacity;
 
-use super::eip712::{to_semantic_address, verify_eip712_hashes_if_has_das_lock};
+use super::eip712::{to_semantic_address, to_semantic_records, verify_eip712_hashes_if_has_das_lock};
 
 pub fn main() -> Result<(), Box<dyn ScriptError>> {
     debug!("====== EIP712 Lib ======");
@@ -32,8 +35,15 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
 
     let func = match action {
         b"transfer_account" => transfer_account_to_semantic,
+        b"start_transfer_account" => start_transfer_account_to_semantic,
+        b"accept_transfer_account" => accept_transfer_account_to_semantic,
+        b"cancel_transfer_account" => cancel_transfer_account_to_semantic,
         b"edit_manager" => edit_manager_to_semantic,
         b"edit_records" => edit_records_to_semantic,
+        b"renew_account" => renew_account_to_semantic,
+        b"enable_sub_account" => enable_sub_account_to_semantic,
+        b"config_sub_account" => config_sub_account_to_semantic,
+        b"update_sub_account" => update_sub_account_to_semantic,
         b"start_account_sale" => start_account_sale_to_semantic,
         b"cancel_account_sale" => cancel_account_sale_to_semantic,
         b"buy_account" => buy_account_to_semantic,
@@ -74,9 +84,44 @@ fn transfer_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<
     Ok(format!("TRANSFER THE ACCOUNT {} TO {}", account, to_address))
 }
 
+fn load_account_from_input_account_cell(parser: &WitnessesParser) -> Result<(String, usize), Box<dyn ScriptError>> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let (input_cells, output_cells) =
+        util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
+
+    // Parse account from the data of the AccountCell in inputs.
+    let data_in_bytes = util::load_cell_data(input_cells[0], Source::Input)?;
+    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+    Ok((account, output_cells[0]))
+}
+
+fn start_transfer_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let (account, output_cell) = load_account_from_input_account_cell(parser)?;
+
+    // The pending owner is kept in the manager part of the AccountCell's lock script in outputs.
+    let to_lock = high_level::load_cell_lock(output_cell, Source::Output)?;
+    let to_address = to_semantic_address(parser, to_lock.as_reader().into(), LockRole::Manager)?;
+
+    Ok(semantic::start_transfer_account_to_semantic(&account, &to_address))
+}
+
+fn accept_transfer_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let (account, _) = load_account_from_input_account_cell(parser)?;
+
+    Ok(semantic::accept_transfer_account_to_semantic(&account))
+}
+
+fn cancel_transfer_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let (account, _) = load_account_from_input_account_cell(parser)?;
+
+    Ok(semantic::cancel_transfer_account_to_semantic(&account))
+}
+
 fn edit_manager_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
-    let (input_cells, _output_cells) =
+    let (input_cells, output_cells) =
         util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
 
     // Parse account from the data of the AccountCell in inputs.
@@ -84,13 +129,34 @@ fn edit_manager_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn
     let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
     let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
 
-    // TODO Improve semantic message of this transaction.
-    Ok(format!("EDIT MANAGER OF ACCOUNT {}", account))
+    // Parse the new manager address from the AccountCell's lock script in outputs.
+    let to_lock = high_level::load_cell_lock(output_cells[0], Source::Output)?;
+    let to_address = to_semantic_address(parser, to_lock.as_reader().into(), LockRole::Manager)?;
+
+    // The permissions of the new manager should be shown to the owner too.
+    let witness = util::parse_account_cell_witness(parser, output_cells[0], Source::Output)?;
+    let permissions = verifiers::account_cell::get_manager_permissions(
+        witness.as_reader().records(),
+        to_lock.as_reader().args().raw_data(),
+    )
+    .into_iter()
+    .map(|prefix| String::from_utf8(prefix).map_err(|_| ErrorCode::EIP712SerializationError))
+    .collect::<Result<Vec<_>, _>>()?;
+
+    if permissions.is_empty() {
+        Ok(semantic::edit_manager_to_semantic(&account, &to_address))
+    } else {
+        Ok(semantic::edit_manager_with_permissions_to_semantic(
+            &account,
+            &to_address,
+            &permissions,
+        ))
+    }
 }
 
 fn edit_records_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
-    let (input_cells, _output_cells) =
+    let (input_cells, output_cells) =
         util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
 
     // Parse account from the data of the AccountCell in inputs.
@@ -98,8 +164,116 @@ fn edit_records_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn
     let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
     let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
 
-    // TODO Improve semantic message of this transaction.
-    Ok(format!("EDIT RECORDS OF ACCOUNT {}", account))
+    let old_records = to_semantic_records(parser, input_cells[0], Source::Input)?;
+    let new_records = to_semantic_records(parser, output_cells[0], Source::Output)?;
+
+    Ok(semantic::edit_records_to_semantic(&account, &old_records, &new_records))
+}
+
+fn renew_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let (input_cells, output_cells) =
+        util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
+
+    // The AccountCells may be renewed in batch, they are paired by their positions in inputs and outputs.
+    let mut accounts = Vec::new();
+    for (input_index, output_index) in input_cells.iter().zip(output_cells.iter()) {
+        // Parse account from the data of the AccountCell in inputs.
+        let data_in_bytes = util::load_cell_data(*input_index, Source::Input)?;
+        let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+        let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+        // Parse the new expiration time from the data of the AccountCell in outputs.
+        let data_out_bytes = util::load_cell_data(*output_index, Source::Output)?;
+        let expired_at = data_parser::account_cell::get_expired_at(&data_out_bytes);
+
+        accounts.push((account, expired_at));
+    }
+
+    if accounts.len() == 1 {
+        let (account, expired_at) = &accounts[0];
+        Ok(semantic::renew_account_to_semantic(account, *expired_at))
+    } else {
+        Ok(semantic::batch_renew_account_to_semantic(&accounts))
+    }
+}
+
+fn enable_sub_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let account_cells =
+        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_cell(), Source::Input)?;
+
+    // Parse account from the data of the AccountCell in inputs.
+    let data_in_bytes = util::load_cell_data(account_cells[0], Source::Input)?;
+    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+    Ok(semantic::enable_sub_account_to_semantic(&account))
+}
+
+fn config_sub_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let account_cells =
+        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_cell(), Source::Input)?;
+    let sub_account_cells = util::find_cells_by_type_id(
+        ScriptType::Type,
+        type_id_table_reader.sub_account_cell(),
+        Source::Output,
+    )?;
+
+    // Parse account from the data of the AccountCell in inputs.
+    let data_in_bytes = util::load_cell_data(account_cells[0], Source::Input)?;
+    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+    // Parse the new rules from the data of the SubAccountCell in outputs and the rule witnesses.
+    let sub_account_data = util::load_cell_data(sub_account_cells[0], Source::Output)?;
+    let flag = data_parser::sub_account_cell::get_flag(&sub_account_data).unwrap_or(SubAccountConfigFlag::Manual);
+    let (price_rules_count, preserved_rules_count) = match flag {
+        SubAccountConfigFlag::CustomRule => {
+            let sub_account_parser = SubAccountWitnessesParser::new(flag)?;
+            let price_rules = sub_account_parser.get_rules(&sub_account_data, DataType::SubAccountPriceRule)?;
+            let preserved_rules = sub_account_parser.get_rules(&sub_account_data, DataType::SubAccountPreservedRule)?;
+
+            (
+                price_rules.map(|rules| rules.len()).unwrap_or(0),
+                preserved_rules.map(|rules| rules.len()).unwrap_or(0),
+            )
+        }
+        _ => (0, 0),
+    };
+    let price_rules_hash = data_parser::sub_account_cell::get_price_rules_hash(&sub_account_data).unwrap_or(&[0u8; 10]);
+    let preserved_rules_hash =
+        data_parser::sub_account_cell::get_preserved_rules_hash(&sub_account_data).unwrap_or(&[0u8; 10]);
+
+    Ok(semantic::config_sub_account_to_semantic(
+        &account,
+        (price_rules_count, price_rules_hash),
+        (preserved_rules_count, preserved_rules_hash),
+    ))
+}
+
+fn update_sub_account_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let account_cells =
+        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_cell(), Source::CellDep)?;
+    let sub_account_cells =
+        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.sub_account_cell(), Source::Input)?;
+
+    // Parse account from the data of the AccountCell in cell_deps.
+    let data_in_bytes = util::load_cell_data(account_cells[0], Source::CellDep)?;
+    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+    // Count the sub-accounts to be updated from the SubAccount witnesses.
+    let sub_account_data = util::load_cell_data(sub_account_cells[0], Source::Input)?;
+    let flag = data_parser::sub_account_cell::get_flag(&sub_account_data).unwrap_or(SubAccountConfigFlag::Manual);
+    let sub_account_parser = SubAccountWitnessesParser::new(flag)?;
+
+    Ok(semantic::update_sub_account_to_semantic(
+        &account,
+        sub_account_parser.len(),
+    ))
 }
 
 fn start_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Box<dyn ScriptError>> {
@@ -24,7 +24,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     };
     let action = action_cp.as_slice();
 
-    util::is_system_off(&parser)?;
+    util::require_action_enabled(&parser, action)?;
 
     debug!(
         "Route to {:?} action ...",
@@ -137,6 +137,12 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
             let das_wallet_lock = das_wallet_lock();
             creators.push(das_wallet_lock.into());
 
+            debug!("Release the vested capacity of vesting records to their beneficiaries.");
+
+            let type_id_table = parser.configs.main()?.type_id_table();
+            let (input_records, unvested_records) =
+                release_vesting_records(type_id_table.income_cell(), input_records)?;
+
             debug!("Classify all income records in inputs for comparing them with outputs later.");
 
             let (records_should_transfer, mut records_should_keep, need_pad) = classify_income_records(
@@ -145,6 +151,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 income_cell_basic_capacity,
                 income_cell_min_transfer_capacity,
                 input_records,
+                unvested_records,
             );
 
             #[cfg(debug_assertions)]
@@ -224,7 +231,6 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
 
             debug!("Check if transfer as expected.");
 
-            let type_id_table = parser.configs.main()?.type_id_table();
             let das_lock = das_lock();
             let das_lock_reader = das_lock.as_reader();
             let mut records_used_for_pad = Vec::new();
@@ -381,6 +387,191 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 );
             }
         }
+        b"withdraw_income" => {
+            debug!("Find out IncomeCells ...");
+
+            let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+            verifiers::common::verify_cell_number("IncomeCell", &input_cells, 1, &output_cells, 1)?;
+
+            verifiers::misc::verify_always_success_lock(output_cells[0], Source::Output)?;
+
+            parser.parse_cell()?;
+
+            let config_income = parser.configs.income()?;
+            let income_cell_basic_capacity = u64::from(config_income.basic_capacity());
+            let income_cell_max_records = u32::from(config_income.max_records()) as usize;
+            let income_consolidate_profit_rate = u32::from(parser.configs.profit_rate()?.income_consolidate()) as u64;
+
+            let input_income_cell_witness = util::parse_income_cell_witness(&parser, input_cells[0], Source::Input)?;
+            let output_income_cell_witness = util::parse_income_cell_witness(&parser, output_cells[0], Source::Output)?;
+
+            #[cfg(debug_assertions)]
+            das_core::inspect::income_cell(
+                Source::Input,
+                input_cells[0],
+                None,
+                Some(input_income_cell_witness.as_reader()),
+            );
+            #[cfg(debug_assertions)]
+            das_core::inspect::income_cell(
+                Source::Output,
+                output_cells[0],
+                None,
+                Some(output_income_cell_witness.as_reader()),
+            );
+
+            let creator = input_income_cell_witness.creator();
+            assert!(
+                util::is_entity_eq(&creator, &output_income_cell_witness.creator()),
+                ErrorCode::IncomeCellWithdrawError,
+                "outputs[{}] The creator of the IncomeCell should not be modified.",
+                output_cells[0]
+            );
+            assert!(
+                output_income_cell_witness.records().len() <= income_cell_max_records,
+                ErrorCode::IncomeCellWithdrawError,
+                "outputs[{}] The IncomeCell can not store more than {} records.",
+                output_cells[0],
+                income_cell_max_records
+            );
+
+            let mut input_records = Vec::new();
+            for record in input_income_cell_witness.records().into_iter() {
+                input_records = merge_record(input_records, record);
+            }
+
+            let mut output_records = Vec::new();
+            let mut records_total_capacity = 0;
+            for record in output_income_cell_witness.records().into_iter() {
+                records_total_capacity += u64::from(record.capacity());
+                output_records = merge_record(output_records, record);
+            }
+
+            let cell_capacity = high_level::load_cell_capacity(output_cells[0], Source::Output)?;
+            assert!(
+                records_total_capacity == cell_capacity,
+                ErrorCode::IncomeCellCapacityError,
+                "outputs[{}] The IncomeCell.capacity should be always equal to the total capacity of its records. (expected: {}, current: {})",
+                output_cells[0],
+                records_total_capacity,
+                cell_capacity
+            );
+            assert!(
+                cell_capacity >= income_cell_basic_capacity,
+                ErrorCode::IncomeCellCapacityError,
+                "outputs[{}] The IncomeCell.capacity should be always greater than or equal to {} shannon.",
+                output_cells[0],
+                income_cell_basic_capacity
+            );
+
+            debug!("Check if the withdrawn records are unlocked by their owners.");
+
+            let type_id_table = parser.configs.main()?.type_id_table();
+            let das_wallet_lock: Script = das_wallet_lock().into();
+            let mut total_fee = 0;
+            let mut withdrawn_count = 0;
+            for (belong_to, input_capacity) in input_records.iter() {
+                // The record of DAS is only used to collect the fee of withdrawing, so it can not be withdrawn here.
+                if util::is_entity_eq(belong_to, &das_wallet_lock) {
+                    continue;
+                }
+
+                let output_capacity = get_record_capacity(&output_records, belong_to);
+                if output_capacity == *input_capacity {
+                    continue;
+                }
+
+                // The vesting record can only be released by consolidate_income, so it can not be withdrawn here.
+                assert!(
+                    !verifiers::income_cell::is_vesting_record(belong_to.as_reader(), type_id_table.income_cell()),
+                    ErrorCode::IncomeCellVestingRecordInvalid,
+                    "outputs[{}] The vesting record can not be withdrawn, it can only be released by consolidate_income. (belong_to: {})",
+                    output_cells[0],
+                    belong_to
+                );
+
+                assert!(
+                    output_capacity < *input_capacity,
+                    ErrorCode::IncomeCellWithdrawError,
+                    "outputs[{}] The capacity of a record can not be increased when withdrawing. (belong_to: {}, expected: <= {}, current: {})",
+                    output_cells[0],
+                    belong_to,
+                    input_capacity,
+                    output_capacity
+                );
+
+                let cells = util::find_cells_by_script(ScriptType::Lock, belong_to.as_reader().into(), Source::Input)?;
+                assert!(
+                    cells.len() > 0,
+                    ErrorCode::IncomeCellWithdrawError,
+                    "There should be at least one cell with the same lock of the withdrawn record in inputs. (belong_to: {})",
+                    belong_to
+                );
+
+                let capacity_withdrawn = input_capacity - output_capacity;
+                // If the record belongs to the IncomeCell creator, it should not take fee from it, but the basic capacity must be kept.
+                let fee = if util::is_entity_eq(belong_to, &creator) {
+                    assert!(
+                        output_capacity >= income_cell_basic_capacity,
+                        ErrorCode::IncomeCellWithdrawError,
+                        "outputs[{}] The record of the creator should keep at least the basic capacity of the IncomeCell. (expected: >= {}, current: {})",
+                        output_cells[0],
+                        income_cell_basic_capacity,
+                        output_capacity
+                    );
+
+                    0
+                } else {
+                    capacity_withdrawn - capacity_withdrawn / RATE_BASE * (RATE_BASE - income_consolidate_profit_rate)
+                };
+
+                debug!(
+                    "  {{ belong_to.args: {}, capacity_withdrawn: {}, fee: {} }}",
+                    belong_to.args(),
+                    capacity_withdrawn,
+                    fee
+                );
+
+                total_fee += fee;
+                withdrawn_count += 1;
+            }
+
+            assert!(
+                withdrawn_count > 0,
+                ErrorCode::IncomeCellWithdrawError,
+                "There should be at least one record withdrawn from the IncomeCell."
+            );
+
+            debug!("Check if the other records are kept and the fee is paid to DAS.");
+
+            for (belong_to, output_capacity) in output_records.iter() {
+                if util::is_entity_eq(belong_to, &das_wallet_lock) {
+                    continue;
+                }
+
+                assert!(
+                    input_records
+                        .iter()
+                        .any(|record| util::is_entity_eq(&record.0, belong_to)),
+                    ErrorCode::IncomeCellWithdrawError,
+                    "outputs[{}] There should be no new record created when withdrawing. (belong_to: {}, capacity: {})",
+                    output_cells[0],
+                    belong_to,
+                    output_capacity
+                );
+            }
+
+            let expected_das_capacity = get_record_capacity(&input_records, &das_wallet_lock) + total_fee;
+            let current_das_capacity = get_record_capacity(&output_records, &das_wallet_lock);
+            assert!(
+                current_das_capacity == expected_das_capacity,
+                ErrorCode::IncomeCellWithdrawError,
+                "outputs[{}] The fee of withdrawing should be added to the record of DAS. (expected: {}, current: {})",
+                output_cells[0],
+                expected_das_capacity,
+                current_das_capacity
+            );
+        }
         b"confirm_proposal" => {
             util::require_type_script(
                 &parser,
@@ -430,18 +621,74 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     Ok(())
 }
 
-fn merge_record(mut input_records: Vec<(Script, u64)>, record: IncomeRecord) -> Vec<(Script, u64)> {
+fn merge_record(input_records: Vec<(Script, u64)>, record: IncomeRecord) -> Vec<(Script, u64)> {
+    merge_capacity(input_records, record.belong_to(), u64::from(record.capacity()))
+}
+
+fn merge_capacity(mut input_records: Vec<(Script, u64)>, belong_to: Script, capacity: u64) -> Vec<(Script, u64)> {
     for exist_record in input_records.iter_mut() {
-        if util::is_entity_eq(&exist_record.0, &record.belong_to()) {
-            exist_record.1 += u64::from(record.capacity());
+        if util::is_entity_eq(&exist_record.0, &belong_to) {
+            exist_record.1 += capacity;
             return input_records;
         }
     }
 
-    input_records.push((record.belong_to(), u64::from(record.capacity())));
+    input_records.push((belong_to, capacity));
     input_records
 }
 
+/// Split the vesting records into the transferable part which belongs to the beneficiary and the unvested remainder.
+///
+/// The transferable part is merged into the records of the beneficiary, so it is classified like any other record, while
+/// the unvested remainder must be kept in the IncomeCell with the same belong_to.
+fn release_vesting_records(
+    income_cell_type_id: HashReader,
+    input_records: Vec<(Script, u64)>,
+) -> Result<(Vec<(Script, u64)>, Vec<(Script, u64)>), Box<dyn ScriptError>> {
+    let mut timestamp_opt = None;
+    let mut records = Vec::new();
+    let mut unvested_records = Vec::new();
+
+    for (belong_to, capacity) in input_records.into_iter() {
+        if !verifiers::income_cell::is_vesting_record(belong_to.as_reader(), income_cell_type_id) {
+            records = merge_capacity(records, belong_to, capacity);
+            continue;
+        }
+
+        // The TimeCell is only required when there is any vesting record.
+        let timestamp = match timestamp_opt {
+            Some(timestamp) => timestamp,
+            None => {
+                let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+                timestamp_opt = Some(timestamp);
+                timestamp
+            }
+        };
+
+        let vesting = verifiers::income_cell::parse_vesting_record(belong_to.as_reader())?;
+        let transferable = verifiers::income_cell::calc_transferable_capacity(&vesting, capacity, timestamp);
+
+        if transferable > 0 {
+            records = merge_capacity(records, vesting.beneficiary, transferable);
+        }
+        if capacity > transferable {
+            unvested_records.push((belong_to, capacity - transferable));
+        }
+    }
+
+    Ok((records, unvested_records))
+}
+
+fn get_record_capacity(records: &[(Script, u64)], belong_to: &Script) -> u64 {
+    for record in records.iter() {
+        if util::is_entity_eq(&record.0, belong_to) {
+            return record.1;
+        }
+    }
+
+    0
+}
+
 fn calc_total_records_capacity(records: Iter<(Script, u64, bool)>) -> u64 {
     // There is no reduce method here, so we use for...in instead.
     let mut total = 0;
@@ -458,6 +705,7 @@ fn classify_income_records(
     income_cell_basic_capacity: u64,
     income_cell_min_transfer_capacity: u64,
     input_records: Vec<(Script, u64)>,
+    unvested_records: Vec<(Script, u64)>,
 ) -> (Vec<(Script, u64, bool)>, Vec<(Script, u64, bool)>, bool) {
     let mut records_should_transfer = Vec::new();
     let mut records_should_keep = Vec::new();
@@ -482,6 +730,11 @@ fn classify_income_records(
         }
     }
 
+    // The unvested remainder of vesting records can not be transferred in any case.
+    for record in unvested_records.into_iter() {
+        records_should_keep.push((record.0, record.1, false));
+    }
+
     let remain_capacity = calc_total_records_capacity(records_should_keep.iter());
 
     // Calculate how many IncomeCell is required in outputs.
@@ -24,7 +24,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     };
     let action = action_cp.as_slice();
 
-    util::is_system_off(&parser)?;
+    util::require_action_enabled(&parser, action)?;
 
     let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
 
@@ -0,0 +1,283 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::result::Result;
+
+use ckb_std::ckb_constants::Source;
+use das_core::constants::*;
+use das_core::error::*;
+use das_core::verifiers::account_registry::{self, AccountRegistryMode};
+use das_core::witness_parser::WitnessesParser;
+use das_core::{assert, code_to_error, data_parser, debug, util, verifiers};
+use das_types::prelude::*;
+
+/// The AccountRegistryCell shares the code of pre-account-cell-type, so this is the entry of its own script group.
+pub fn main(parser: &mut WitnessesParser, action: &[u8]) -> Result<(), Box<dyn ScriptError>> {
+    debug!("====== Running pre-account-cell-type for AccountRegistryCell ======");
+
+    let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+
+    match action {
+        b"create_account_registry" => {
+            util::require_super_lock()?;
+
+            verifiers::common::verify_cell_number("AccountRegistryCell", &input_cells, 0, &output_cells, 1)?;
+            verifiers::misc::verify_always_success_lock(output_cells[0], Source::Output)?;
+
+            let (root, migration_finished) = account_registry::load_registry(output_cells[0], Source::Output)?;
+            assert!(
+                root == [0u8; 32] && !migration_finished,
+                PreAccountCellErrorCode::AccountRegistryCellError,
+                "outputs[{}] The initial outputs_data of AccountRegistryCell should be 32 bytes of 0x00.",
+                output_cells[0]
+            );
+        }
+        b"finish_account_registry_migration" => {
+            util::require_super_lock()?;
+
+            parser.parse_cell()?;
+            let config_main = parser.configs.main()?;
+
+            assert!(
+                account_registry::get_mode(config_main)? != AccountRegistryMode::Off,
+                PreAccountCellErrorCode::AccountRegistryIsNotEnabled,
+                "The migration of AccountRegistryCell is not started yet."
+            );
+
+            verifiers::common::verify_cell_number("AccountRegistryCell", &input_cells, 1, &output_cells, 1)?;
+            verifiers::common::verify_cell_consistent_with_exception(
+                "AccountRegistryCell",
+                input_cells[0],
+                output_cells[0],
+                vec![CellField::Data],
+            )?;
+
+            let (prev_root, prev_finished) = account_registry::load_registry(input_cells[0], Source::Input)?;
+            let (current_root, current_finished) = account_registry::load_registry(output_cells[0], Source::Output)?;
+
+            assert!(
+                prev_root == current_root && !prev_finished && current_finished,
+                PreAccountCellErrorCode::AccountRegistryCellError,
+                "outputs[{}] Only the migration flag of AccountRegistryCell can be set from 0x00 to 0x01.",
+                output_cells[0]
+            );
+        }
+        b"migrate_account_registry" => {
+            parser.parse_cell()?;
+            let config_main = parser.configs.main()?;
+
+            assert!(
+                account_registry::get_mode(config_main)? != AccountRegistryMode::Off,
+                PreAccountCellErrorCode::AccountRegistryIsNotEnabled,
+                "The migration of AccountRegistryCell is not started yet."
+            );
+
+            verify_registry_cell_updated(&input_cells, &output_cells)?;
+
+            debug!("Collect the existing AccountCells in cell_deps ...");
+
+            let dep_account_cells = util::find_cells_by_type_id(
+                ScriptType::Type,
+                config_main.type_id_table().account_cell(),
+                Source::CellDep,
+            )?;
+
+            assert!(
+                !dep_account_cells.is_empty(),
+                ErrorCode::InvalidTransactionStructure,
+                "There should be at least 1 AccountCell in cell_deps to be migrated."
+            );
+
+            let mut accounts = Vec::new();
+            for index in dep_account_cells {
+                let data = util::load_cell_data(index, Source::CellDep)?;
+                accounts.push((
+                    data_parser::account_cell::get_id(&data).to_vec(),
+                    data_parser::account_cell::get_account(&data).to_vec(),
+                ));
+            }
+
+            let proof = match parser.params.get(0) {
+                Some(val) => val.as_reader().raw_data().to_vec(),
+                None => vec![],
+            };
+            let prev_root = account_registry::load_root(input_cells[0], Source::Input)?;
+            let current_root = account_registry::load_root(output_cells[0], Source::Output)?;
+
+            account_registry::verify_accounts_registered(prev_root, current_root, &accounts, &proof)
+                .map_err(|_| code_to_error!(PreAccountCellErrorCode::AccountAlreadyExistOrProofInvalid))?;
+        }
+        b"pre_register" => {
+            parser.parse_cell()?;
+            let config_main = parser.configs.main()?;
+
+            assert!(
+                account_registry::get_mode(config_main)? != AccountRegistryMode::Off,
+                PreAccountCellErrorCode::AccountRegistryIsNotEnabled,
+                "The AccountRegistryCell is not enabled for registration yet."
+            );
+
+            verify_registry_cell_updated(&input_cells, &output_cells)?;
+
+            // The SMT root transition is verified by the script group of the PreAccountCell, here we only need to make sure
+            // there is one and only one PreAccountCell created.
+            let pre_account_cell_type = util::type_id_to_script(config_main.type_id_table().pre_account_cell());
+            let (input_pre_account_cells, output_pre_account_cells) = util::find_cells_by_script_in_inputs_and_outputs(
+                ScriptType::Type,
+                pre_account_cell_type.as_reader().into(),
+            )?;
+
+            verifiers::common::verify_cell_number(
+                "PreAccountCell",
+                &input_pre_account_cells,
+                0,
+                &output_pre_account_cells,
+                1,
+            )?;
+        }
+        b"refund_pre_register" => {
+            parser.parse_cell()?;
+            let config_main = parser.configs.main()?;
+
+            verify_registry_cell_updated(&input_cells, &output_cells)?;
+
+            debug!("Collect the accounts of the refunded PreAccountCells ...");
+
+            // Only the PreAccountCells can be refunded here, the AccountRegistryCell itself has different args.
+            let pre_account_cell_type = util::type_id_to_script(config_main.type_id_table().pre_account_cell());
+            let input_pre_account_cells = util::find_cells_by_script(
+                ScriptType::Type,
+                pre_account_cell_type.as_reader().into(),
+                Source::Input,
+            )?;
+
+            // CAREFUL! The PreAccountCells created before the migration started have never been inserted, if the same
+            // account has been registered again since then, its key belongs to the newer registration.
+            let migration_started_at = account_registry::get_migration_started_at(config_main)?;
+            let mut accounts = Vec::new();
+            for index in input_pre_account_cells {
+                let data = util::load_cell_data(index, Source::Input)?;
+                let witness = util::parse_pre_account_cell_witness(parser, index, Source::Input)?;
+                let witness_reader = witness.as_reader();
+                if u64::from(witness_reader.created_at()) < migration_started_at {
+                    debug!(
+                        "inputs[{}] Skip the PreAccountCell created before the migration started.",
+                        index
+                    );
+                    continue;
+                }
+
+                let mut account = witness_reader.account().as_readable();
+                account.extend(ACCOUNT_SUFFIX.as_bytes());
+
+                accounts.push((data_parser::pre_account_cell::get_id(&data).to_vec(), account));
+            }
+
+            if accounts.is_empty() {
+                verify_registry_root_unchanged(&input_cells, &output_cells)?;
+            } else {
+                verify_accounts_removed(parser, &input_cells, &output_cells, &accounts, false)?;
+            }
+        }
+        b"recycle_expired_account" => {
+            parser.parse_cell()?;
+            let config_main = parser.configs.main()?;
+
+            verify_registry_cell_updated(&input_cells, &output_cells)?;
+
+            debug!("Collect the accounts of the recycled AccountCells ...");
+
+            let (input_account_cells, output_account_cells) = util::find_cells_by_type_id_in_inputs_and_outputs(
+                ScriptType::Type,
+                config_main.type_id_table().account_cell(),
+            )?;
+
+            let mut output_account_ids = Vec::new();
+            for index in output_account_cells {
+                let data = util::load_cell_data(index, Source::Output)?;
+                output_account_ids.push(data_parser::account_cell::get_id(&data).to_vec());
+            }
+
+            let mut accounts = Vec::new();
+            for index in input_account_cells {
+                let data = util::load_cell_data(index, Source::Input)?;
+                let account_id = data_parser::account_cell::get_id(&data).to_vec();
+                if !output_account_ids.contains(&account_id) {
+                    accounts.push((account_id, data_parser::account_cell::get_account(&data).to_vec()));
+                }
+            }
+
+            verify_accounts_removed(parser, &input_cells, &output_cells, &accounts, true)?;
+        }
+        _ => return Err(code_to_error!(ErrorCode::ActionNotSupported)),
+    }
+
+    Ok(())
+}
+
+fn verify_registry_cell_updated(input_cells: &[usize], output_cells: &[usize]) -> Result<(), Box<dyn ScriptError>> {
+    verifiers::common::verify_cell_number("AccountRegistryCell", input_cells, 1, output_cells, 1)?;
+    verifiers::common::verify_cell_consistent_with_exception(
+        "AccountRegistryCell",
+        input_cells[0],
+        output_cells[0],
+        vec![CellField::Data],
+    )?;
+
+    let (_, prev_finished) = account_registry::load_registry(input_cells[0], Source::Input)?;
+    let (_, current_finished) = account_registry::load_registry(output_cells[0], Source::Output)?;
+
+    assert!(
+        prev_finished == current_finished,
+        PreAccountCellErrorCode::AccountRegistryCellError,
+        "outputs[{}] The migration flag of AccountRegistryCell can only be changed by finish_account_registry_migration.",
+        output_cells[0]
+    );
+
+    Ok(())
+}
+
+fn verify_registry_root_unchanged(input_cells: &[usize], output_cells: &[usize]) -> Result<(), Box<dyn ScriptError>> {
+    let prev_root = account_registry::load_root(input_cells[0], Source::Input)?;
+    let current_root = account_registry::load_root(output_cells[0], Source::Output)?;
+
+    assert!(
+        prev_root == current_root,
+        PreAccountCellErrorCode::AccountRegistryCellError,
+        "outputs[{}] The root of AccountRegistryCell should not be changed, because no account is removed.",
+        output_cells[0]
+    );
+
+    Ok(())
+}
+
+/// Verify the accounts are removed from the registry, when `maybe_not_migrated` is true the accounts may have never been
+/// inserted, then the root should not be changed.
+fn verify_accounts_removed(
+    parser: &WitnessesParser,
+    input_cells: &[usize],
+    output_cells: &[usize],
+    accounts: &[(Vec<u8>, Vec<u8>)],
+    maybe_not_migrated: bool,
+) -> Result<(), Box<dyn ScriptError>> {
+    assert!(
+        !accounts.is_empty(),
+        ErrorCode::InvalidTransactionStructure,
+        "There should be at least 1 account to be removed from the AccountRegistryCell."
+    );
+
+    let proof = match parser.params.get(0) {
+        Some(val) => val.as_reader().raw_data().to_vec(),
+        None => vec![],
+    };
+    let prev_root = account_registry::load_root(input_cells[0], Source::Input)?;
+    let current_root = account_registry::load_root(output_cells[0], Source::Output)?;
+
+    let ret = if maybe_not_migrated {
+        account_registry::verify_accounts_unregistered_if_migrated(prev_root, current_root, accounts, &proof)
+    } else {
+        account_registry::verify_accounts_unregistered(prev_root, current_root, accounts, &proof)
+    };
+
+    ret.map_err(|_| code_to_error!(PreAccountCellErrorCode::AccountRegistryCellError))
+}
@@ -10,6 +10,7 @@ use ckb_std::high_level;
 use das_core::constants::*;
 use das_core::error::*;
 use das_core::since_util::SinceFlag;
+use das_core::verifiers::account_registry::AccountRegistryMode;
 use das_core::witness_parser::WitnessesParser;
 use das_core::{assert, code_to_error, data_parser, debug, since_util, util, verifiers, warn};
 use das_sorted_list::util as sorted_list_util;
@@ -18,6 +19,8 @@ use das_types::mixer::PreAccountCellDataReaderMixer;
 use das_types::packed::*;
 use das_types::prelude::*;
 
+use crate::account_registry;
+
 pub fn main() -> Result<(), Box<dyn ScriptError>> {
     debug!("====== Running pre-account-cell-type ======");
 
@@ -28,13 +31,18 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     };
     let action = action_cp.as_slice();
 
-    util::is_system_off(&parser)?;
+    util::require_action_enabled(&parser, action)?;
 
     debug!(
         "Route to {:?} action ...",
         alloc::string::String::from_utf8(action.to_vec()).map_err(|_| ErrorCode::ActionNotSupported)?
     );
 
+    let this_type_script = high_level::load_script()?;
+    if this_type_script.as_reader().args().raw_data() == ACCOUNT_REGISTRY_CELL_ARGS {
+        return account_registry::main(&mut parser, action);
+    }
+
     match action {
         b"confirm_proposal" => {
             util::require_type_script(
@@ -58,7 +66,18 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 Source::CellDep,
             )?;
 
-            verifiers::common::verify_cell_dep_number("AccountCell", &dep_account_cells, 1)?;
+            // The uniqueness of the account is proved by the neighbouring AccountCell in cell_deps until the
+            // AccountRegistryCell is enabled, the mode is decided by ConfigCellMain only. Whenever the registry is switched
+            // on, the account must be inserted into it too.
+            let registry_mode = verifiers::account_registry::get_mode(config_main_reader)?;
+            let (input_registry_cells, output_registry_cells) =
+                verifiers::account_registry::verify_registry_cell_required(config_main_reader)?;
+
+            if registry_mode == AccountRegistryMode::Enabled {
+                verifiers::common::verify_cell_dep_number("AccountCell", &dep_account_cells, 0)?;
+            } else {
+                verifiers::common::verify_cell_dep_number("AccountCell", &dep_account_cells, 1)?;
+            }
 
             let (input_apply_register_cells, output_apply_register_cells) =
                 util::find_cells_by_type_id_in_inputs_and_outputs(
@@ -133,13 +152,29 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 &apply_register_hash,
             )?;
             verify_owner_lock_args(&pre_account_cell_witness_reader)?;
-            verify_quote(&pre_account_cell_witness_reader)?;
+            verify_quote(&parser, &pre_account_cell_witness_reader)?;
             verify_invited_discount(config_price, &pre_account_cell_witness_reader)?;
             verify_price_and_capacity(config_account, config_price, &pre_account_cell_witness_reader, capacity)?;
+            if util::get_payment_mode(&parser.params)? == PaymentMode::Stablecoin {
+                verify_paid_by_stablecoin(config_main_reader, &pre_account_cell_witness_reader, capacity)?;
+            }
             verify_account_id(&pre_account_cell_witness_reader, account_id)?;
             // TODO Remove the PreAccountCell.witness.created_at field, it is no longer needed.
             verify_created_at(timestamp, &pre_account_cell_witness_reader)?;
-            verify_account_not_exist(dep_account_cells[0], account_id)?;
+            if registry_mode != AccountRegistryMode::Enabled {
+                verify_account_not_exist(dep_account_cells[0], account_id)?;
+            }
+            if registry_mode != AccountRegistryMode::Off {
+                verify_account_not_registered(
+                    &parser,
+                    registry_mode,
+                    verifiers::account_registry::get_migration_started_at(config_main_reader)?,
+                    &input_registry_cells,
+                    &output_registry_cells,
+                    account_id,
+                    &pre_account_cell_witness_reader,
+                )?;
+            }
 
             debug!("Verify if account is available for registration for now ...");
 
@@ -194,6 +229,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 2 => {
                     if let Ok(reader) = pre_account_cell_witness_reader.try_into_v2() {
                         verifiers::account_cell::verify_records_keys(&parser, reader.initial_records())?;
+                        verifiers::record_value::verify_records_values(&parser, None, reader.initial_records())?;
                     } else {
                         warn!("The PreAccountCellDataReaderMixer.version returned a mismatched version number.");
                         return Err(code_to_error!(ErrorCode::HardCodedError));
@@ -202,6 +238,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 3 => {
                     if let Ok(reader) = pre_account_cell_witness_reader.try_into_latest() {
                         verifiers::account_cell::verify_records_keys(&parser, reader.initial_records())?;
+                        verifiers::record_value::verify_records_values(&parser, None, reader.initial_records())?;
                     } else {
                         warn!("The PreAccountCellDataReaderMixer.version returned a mismatched version number.");
                         return Err(code_to_error!(ErrorCode::HardCodedError));
@@ -223,6 +260,9 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
                 (Ordering::Equal, 0),
             )?;
 
+            // The removal of the accounts is verified by the script group of the AccountRegistryCell.
+            verifiers::account_registry::verify_registry_cell_required(config_main_reader)?;
+
             debug!("Find if any cell with refund_lock in inputs ...");
 
             let pre_account_cell_witness =
@@ -426,10 +466,13 @@ fn verify_owner_lock_args<'a>(
     Ok(())
 }
 
-fn verify_quote<'a>(reader: &Box<dyn PreAccountCellDataReaderMixer + 'a>) -> Result<(), Box<dyn ScriptError>> {
+fn verify_quote<'a>(
+    parser: &WitnessesParser,
+    reader: &Box<dyn PreAccountCellDataReaderMixer + 'a>,
+) -> Result<(), Box<dyn ScriptError>> {
     debug!("Check if PreAccountCell.witness.quote is the same as QuoteCell.");
 
-    let expected_quote = util::load_oracle_data(OracleCellType::Quote)?;
+    let expected_quote = util::load_quote(parser)?;
     let current = u64::from(reader.quote());
 
     assert!(
@@ -561,6 +604,52 @@ fn verify_price_and_capacity<'a>(
     Ok(())
 }
 
+/// Verify the PreAccountCell is paid by the stablecoin to the DAS wallet.
+///
+/// The PreAccountCell still needs its capacity, because the proposal derives the duration and the profit from it, so the
+/// stablecoin paid by the refund_lock should be worth the whole capacity at the quote of the PreAccountCell.
+fn verify_paid_by_stablecoin<'a>(
+    config_main: ConfigCellMainReader,
+    reader: &Box<dyn PreAccountCellDataReaderMixer + 'a>,
+    capacity: u64,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if the PreAccountCell is paid by the stablecoin properly.");
+
+    let settings = match util::get_stablecoin_settings(config_main)? {
+        Some(settings) => settings,
+        None => {
+            warn!("Paying by stablecoin is not supported, because there is no stablecoin settings in ConfigCellMain.");
+            return Err(code_to_error!(ErrorCode::StablecoinPaymentError));
+        }
+    };
+
+    let (input_income_cells, output_income_cells) =
+        util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, config_main.type_id_table().income_cell())?;
+    assert!(
+        input_income_cells.is_empty() && output_income_cells.is_empty(),
+        ErrorCode::StablecoinPaymentError,
+        "There should be no IncomeCell when paying by stablecoin."
+    );
+
+    let udt_type_hash = data_parser::stablecoin_settings::get_type_hash(settings);
+    let decimals = data_parser::stablecoin_settings::get_decimals(settings);
+    let das_wallet_lock = das_wallet_lock();
+
+    let (paid, _) =
+        verifiers::udt::verify_udt_payment(udt_type_hash, reader.refund_lock().into(), das_wallet_lock.as_reader())?;
+    let expected = util::calc_udt_amount_from_capacity(capacity, u64::from(reader.quote()), decimals);
+
+    assert!(
+        paid >= expected,
+        ErrorCode::StablecoinPaymentError,
+        "The stablecoin paid should be worth the capacity of the PreAccountCell. (expected: >= {}, current: {})",
+        expected,
+        paid
+    );
+
+    Ok(())
+}
+
 fn verify_account_length_and_years<'a>(
     reader: &Box<dyn PreAccountCellDataReaderMixer + 'a>,
     current_timestamp: u64,
@@ -679,3 +768,60 @@ fn verify_account_not_exist(pre_account_cell: usize, account_id: &[u8]) -> Resul
 
     Ok(())
 }
+
+fn verify_account_not_registered<'a>(
+    parser: &WitnessesParser,
+    registry_mode: AccountRegistryMode,
+    migration_started_at: u64,
+    input_registry_cells: &[usize],
+    output_registry_cells: &[usize],
+    account_id: &[u8],
+    reader: &Box<dyn PreAccountCellDataReaderMixer + 'a>,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the account is not registered in the AccountRegistryCell ...");
+
+    let (prev_root, migration_finished) =
+        verifiers::account_registry::load_registry(input_registry_cells[0], Source::Input)?;
+
+    // The registry can only be trusted alone after all the existing accounts have been migrated into it.
+    assert!(
+        registry_mode != AccountRegistryMode::Enabled || migration_finished,
+        PreAccountCellErrorCode::AccountRegistryIsNotEnabled,
+        "The migration of AccountRegistryCell is not finished yet."
+    );
+
+    // The refund tells whether the PreAccountCell has been inserted into the registry by its created_at.
+    let created_at = u64::from(reader.created_at());
+    assert!(
+        created_at >= migration_started_at,
+        PreAccountCellErrorCode::AccountRegistryIsNotEnabled,
+        "The migration of AccountRegistryCell has not started yet.(migration_started_at: {}, created_at: {})",
+        migration_started_at,
+        created_at
+    );
+
+    let proof = match parser.params.get(1) {
+        Some(val) => val.as_reader().raw_data().to_vec(),
+        None => {
+            warn!("The params of pre_register should contain the SMT proof after the payment mode.");
+            return Err(code_to_error!(
+                PreAccountCellErrorCode::AccountAlreadyExistOrProofInvalid
+            ));
+        }
+    };
+
+    let mut account = reader.account().as_readable();
+    account.extend(ACCOUNT_SUFFIX.as_bytes());
+
+    let current_root = verifiers::account_registry::load_root(output_registry_cells[0], Source::Output)?;
+
+    verifiers::account_registry::verify_accounts_registered(
+        prev_root,
+        current_root,
+        &[(account_id.to_vec(), account)],
+        &proof,
+    )
+    .map_err(|_| code_to_error!(PreAccountCellErrorCode::AccountAlreadyExistOrProofInvalid))?;
+
+    Ok(())
+}
@@ -30,7 +30,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     };
     let action = action_cp.as_slice();
 
-    util::is_system_off(&parser)?;
+    util::require_action_enabled(&parser, action)?;
 
     debug!("Find out ProposalCell ...");
 
@@ -48,40 +48,29 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         b"propose" | b"extend_proposal" => {
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
-            parser.parse_cell()?;
+            parser.parse_cell_lazily()?;
             let config_main = parser.configs.main()?;
             let config_proposal = parser.configs.proposal()?;
 
-            if action == b"propose" {
-                assert!(
-                    dep_cells.len() == 0,
-                    ErrorCode::InvalidTransactionStructure,
-                    "There should be 0 ProposalCell in the cell_deps."
-                );
-            } else {
-                assert!(
-                    dep_cells.len() == 1,
-                    ErrorCode::InvalidTransactionStructure,
-                    "There should be 1 ProposalCell found in the cell_deps"
-                );
-            }
-
             verifiers::common::verify_cell_number("ProposalCell", &input_cells, 0, &output_cells, 1)?;
             verifiers::misc::verify_always_success_lock(output_cells[0], Source::Output)?;
 
+            let output_cell_witness = util::parse_proposal_cell_witness(&parser, output_cells[0], Source::Output)?;
+            let output_cell_witness_reader = output_cell_witness.as_reader();
+
+            let required_cells_count = verify_slices(config_proposal, output_cell_witness_reader.slices())?;
+
+            let prev_cell_opt =
+                find_prev_proposal_cell(&parser, action, &dep_cells, output_cell_witness_reader.slices())?;
+
             let dep_cell_witness;
             let dep_cell_witness_reader;
             let mut prev_slices_reader_opt = None;
-            if action == b"extend_proposal" {
-                dep_cell_witness = util::parse_proposal_cell_witness(&parser, dep_cells[0], Source::CellDep)?;
+            if let Some(prev_cell) = prev_cell_opt {
+                dep_cell_witness = util::parse_proposal_cell_witness(&parser, prev_cell, Source::CellDep)?;
                 dep_cell_witness_reader = dep_cell_witness.as_reader();
                 prev_slices_reader_opt = Some(dep_cell_witness_reader.slices());
             }
-
-            let output_cell_witness = util::parse_proposal_cell_witness(&parser, output_cells[0], Source::Output)?;
-            let output_cell_witness_reader = output_cell_witness.as_reader();
-
-            let required_cells_count = verify_slices(config_proposal, output_cell_witness_reader.slices())?;
             let dep_related_cells = find_proposal_related_cells(config_main, Source::CellDep)?;
 
             #[cfg(debug_assertions)]
@@ -109,7 +98,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         b"confirm_proposal" => {
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
-            parser.parse_cell()?;
+            parser.parse_cell_lazily()?;
             let config_account = parser.configs.account()?;
             let config_main = parser.configs.main()?;
             let config_profit_rate = parser.configs.profit_rate()?;
@@ -135,6 +124,9 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
 
             debug!("Check all AccountCells are updated or created base on proposal.");
 
+            verify_slices_own_ranges(config_main, input_cell_witness_reader.slices())?;
+            verify_related_cells_in_ranges(config_main, input_cell_witness_reader.slices())?;
+
             verify_proposal_execution_result(
                 &parser,
                 config_account,
@@ -147,7 +139,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
             verify_refund_correct(input_cells[0], input_cell_witness_reader, 0)?;
         }
         b"recycle_proposal" => {
-            parser.parse_cell()?;
+            parser.parse_cell_lazily()?;
             let config_proposal_reader = parser.configs.proposal()?;
 
             verifiers::common::verify_cell_number("ProposalCell", &input_cells, 1, &output_cells, 0)?;
@@ -391,6 +383,211 @@ fn verify_slices(
     Ok(required_cells_count)
 }
 
+/// Get the ranges of account ID which are covered by the slices.
+///
+/// Every slice starts with an existing AccountCell and ends with its original next, so the range of a slice is
+/// [first_item.account_id, last_item.next) , no other proposal should insert any account into it before this one confirmed.
+fn get_slice_ranges(slices_reader: SliceListReader) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut ranges = Vec::new();
+    for sl_reader in slices_reader.iter() {
+        let first_item = sl_reader.get(0).unwrap();
+        let last_item = sl_reader.get(sl_reader.len() - 1).unwrap();
+        ranges.push((
+            first_item.account_id().raw_data().to_vec(),
+            last_item.next().raw_data().to_vec(),
+        ));
+    }
+
+    ranges
+}
+
+fn is_ranges_overlapped(a: &[(Vec<u8>, Vec<u8>)], b: &[(Vec<u8>, Vec<u8>)]) -> bool {
+    for (a_start, a_end) in a.iter() {
+        for (b_start, b_end) in b.iter() {
+            // The ranges are half-open, so two slices may share the same boundary.
+            if a_start < b_end && b_start < a_end {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Find the ProposalCell which is extended by the new proposal.
+///
+/// The ProposalCells in cell_deps are chosen by the proposer, so they are never trusted to prove that the new proposal is
+/// disjoint with the parallel ones, that is guaranteed by verify_slices_own_ranges when confirming.
+fn find_prev_proposal_cell(
+    parser: &WitnessesParser,
+    action: &[u8],
+    dep_cells: &[usize],
+    slices_reader: SliceListReader,
+) -> Result<Option<usize>, Box<dyn ScriptError>> {
+    if action == b"propose" {
+        assert!(
+            dep_cells.len() == 0,
+            ErrorCode::InvalidTransactionStructure,
+            "There should be 0 ProposalCell in the cell_deps."
+        );
+
+        return Ok(None);
+    }
+
+    assert!(
+        dep_cells.len() == 1,
+        ErrorCode::InvalidTransactionStructure,
+        "There should be 1 ProposalCell found in the cell_deps"
+    );
+
+    debug!("Check if the ProposalCell in cell_deps is really the previous proposal.");
+
+    let prev_cell = dep_cells[0];
+    let prev_cell_witness = util::parse_proposal_cell_witness(parser, prev_cell, Source::CellDep)?;
+    let prev_slices_reader = prev_cell_witness.as_reader().slices();
+
+    let mut extended_slices_count = 0;
+    for sl_reader in slices_reader.iter() {
+        let first_item = sl_reader.get(0).unwrap();
+        if u8::from(first_item.item_type()) == ProposalSliceItemType::Proposed as u8 {
+            // Every slice starting with a proposed item must continue a slice of the previous proposal.
+            find_item_contains_account_id(&prev_slices_reader, &first_item.account_id())?;
+            extended_slices_count += 1;
+        }
+    }
+
+    assert!(
+        extended_slices_count > 0,
+        ErrorCode::InvalidTransactionStructure,
+        "The extended proposal should contain at least one slice starting with an item of the previous proposal."
+    );
+
+    Ok(Some(prev_cell))
+}
+
+/// Verify every slice owns its range by consuming the AccountCell at the start of the range.
+///
+/// The range of a slice is the gap between an AccountCell and its next in the linked list, so the AccountCell must be
+/// consumed with the same next as the slice ends with. Once a proposal over the gap is confirmed, the next of the
+/// AccountCell changes, so no other proposal over the same gap can be confirmed.
+fn verify_slices_own_ranges(
+    config: ConfigCellMainReader,
+    slices_reader: SliceListReader,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if every slice of the proposal owns its range in the account chain.");
+
+    let ranges = get_slice_ranges(slices_reader);
+    for (sl_index, range) in ranges.iter().enumerate() {
+        assert!(
+            !is_ranges_overlapped(&ranges[sl_index + 1..], core::slice::from_ref(range)),
+            ErrorCode::ProposalRangeOverlapped,
+            "slices[{}] The range of the slice overlaps with the other slices.",
+            sl_index
+        );
+    }
+
+    let account_cell_type_id = config.type_id_table().account_cell();
+    let input_account_cells = util::find_cells_by_type_id(ScriptType::Type, account_cell_type_id, Source::Input)?;
+
+    for (sl_index, (start, end)) in ranges.iter().enumerate() {
+        let mut owner_opt = None;
+        for &index in input_account_cells.iter() {
+            let data = util::load_cell_data(index, Source::Input)?;
+            if data_parser::account_cell::get_id(&data) == start.as_slice() {
+                owner_opt = Some((index, data));
+                break;
+            }
+        }
+
+        match owner_opt {
+            Some((index, data)) => {
+                assert!(
+                    data_parser::account_cell::get_next(&data) == end.as_slice(),
+                    ErrorCode::ProposalCellNextError,
+                    "inputs[{}] The next of the AccountCell should be 0x{}, the range of slices[{}] may have been taken by another proposal.",
+                    index,
+                    util::hex_string(end),
+                    sl_index
+                );
+            }
+            None => {
+                warn!(
+                    "slices[{}] The AccountCell 0x{} at the start of the range should be consumed in inputs.",
+                    sl_index,
+                    util::hex_string(start)
+                );
+                return Err(code_to_error!(ErrorCode::ProposalRelatedCellOutOfRange));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_related_cells_in_ranges(
+    config: ConfigCellMainReader,
+    slices_reader: SliceListReader,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if all the AccountCells and PreAccountCells in the transaction are owned by the proposal.");
+
+    let ranges = get_slice_ranges(slices_reader);
+    let items_count = slices_reader.iter().map(|sl_reader| sl_reader.len()).sum::<usize>();
+
+    let account_cell_type_id = config.type_id_table().account_cell();
+    let pre_account_cell_type_id = config.type_id_table().pre_account_cell();
+    let input_account_cells = util::find_cells_by_type_id(ScriptType::Type, account_cell_type_id, Source::Input)?;
+    let input_pre_account_cells =
+        util::find_cells_by_type_id(ScriptType::Type, pre_account_cell_type_id, Source::Input)?;
+    let output_account_cells = util::find_cells_by_type_id(ScriptType::Type, account_cell_type_id, Source::Output)?;
+
+    // The proposal should be the only one which modify the account chain in this transaction, so that the confirmations
+    // of parallel proposals never touch the same range.
+    assert!(
+        input_account_cells.len() + input_pre_account_cells.len() == items_count
+            && output_account_cells.len() == items_count,
+        ErrorCode::ProposalRelatedCellOutOfRange,
+        "The number of AccountCells and PreAccountCells should be the same as the items in the proposal. (expected: {}, inputs: {}, outputs: {})",
+        items_count,
+        input_account_cells.len() + input_pre_account_cells.len(),
+        output_account_cells.len()
+    );
+
+    let mut cells = Vec::new();
+    for &index in input_account_cells.iter() {
+        let data = util::load_cell_data(index, Source::Input)?;
+        cells.push((index, Source::Input, data_parser::account_cell::get_id(&data).to_vec()));
+    }
+    for &index in input_pre_account_cells.iter() {
+        let data = util::load_cell_data(index, Source::Input)?;
+        cells.push((
+            index,
+            Source::Input,
+            data_parser::pre_account_cell::get_id(&data).to_vec(),
+        ));
+    }
+    for &index in output_account_cells.iter() {
+        let data = util::load_cell_data(index, Source::Output)?;
+        cells.push((index, Source::Output, data_parser::account_cell::get_id(&data).to_vec()));
+    }
+
+    for (index, source, account_id) in cells.iter() {
+        let in_range = ranges
+            .iter()
+            .any(|(start, end)| start <= account_id && account_id < end);
+
+        assert!(
+            in_range,
+            ErrorCode::ProposalRelatedCellOutOfRange,
+            "{:?}[{}] The account 0x{} is out of the ranges of the proposal.",
+            source,
+            index,
+            util::hex_string(account_id)
+        );
+    }
+
+    Ok(())
+}
+
 fn find_proposal_related_cells(
     config: ConfigCellMainReader,
     source: Source,
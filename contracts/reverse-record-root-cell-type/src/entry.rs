@@ -13,7 +13,7 @@ use das_core::{assert as das_assert, code_to_error, debug, util, verifiers, warn
 use das_dynamic_libs::constants::DynLibName;
 use das_dynamic_libs::error::Error as DasDynamicLibError;
 use das_dynamic_libs::sign_lib::SignLib;
-use das_dynamic_libs::{load_2_methods, load_lib, log_loading, new_context};
+use das_dynamic_libs::{load_lib, load_methods, log_loading, new_context};
 use das_types::constants::DasLockType;
 
 pub fn main() -> Result<(), Box<dyn ScriptError>> {
@@ -73,7 +73,7 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
             );
         }
         b"update_reverse_record_root" => {
-            util::is_system_off(&parser)?;
+            util::require_action_enabled(&parser, action)?;
 
             let config_main = parser.configs.main()?;
             let config_smt_white_list = parser.configs.smt_node_white_list()?;
@@ -101,17 +101,27 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
             let mut eth_context = new_context!();
             log_loading!(DynLibName::ETH, config_main.das_lock_type_id_table());
             let eth_lib = load_lib!(eth_context, DynLibName::ETH, config_main.das_lock_type_id_table());
-            sign_lib.eth = load_2_methods!(eth_lib);
+            sign_lib.set_methods(DynLibName::ETH, load_methods!(eth_lib, DynLibName::ETH));
 
             let mut tron_context = new_context!();
             log_loading!(DynLibName::TRON, config_main.das_lock_type_id_table());
             let tron_lib = load_lib!(tron_context, DynLibName::TRON, config_main.das_lock_type_id_table());
-            sign_lib.tron = load_2_methods!(tron_lib);
+            sign_lib.set_methods(DynLibName::TRON, load_methods!(tron_lib, DynLibName::TRON));
 
             let mut doge_context = new_context!();
             log_loading!(DynLibName::DOGE, config_main.das_lock_type_id_table());
             let doge_lib = load_lib!(doge_context, DynLibName::DOGE, config_main.das_lock_type_id_table());
-            sign_lib.doge = load_2_methods!(doge_lib);
+            sign_lib.set_methods(DynLibName::DOGE, load_methods!(doge_lib, DynLibName::DOGE));
+
+            // The MIXIN lock is verified by the Ed25519 library.
+            let mut ed25519_context = new_context!();
+            log_loading!(DynLibName::ED25519, config_main.das_lock_type_id_table());
+            let ed25519_lib = load_lib!(
+                ed25519_context,
+                DynLibName::ED25519,
+                config_main.das_lock_type_id_table()
+            );
+            sign_lib.set_methods(DynLibName::ED25519, load_methods!(ed25519_lib, DynLibName::ED25519));
 
             debug!("Start iterating ReverseRecord witnesses ...");
 
@@ -193,7 +203,9 @@ fn verify_sign(sign_lib: &SignLib, witness: &ReverseRecordWitness) -> Result<(),
     );
 
     let das_lock_type = match witness.sign_type {
-        DasLockType::ETH | DasLockType::ETHTypedData | DasLockType::TRON | DasLockType::Doge => witness.sign_type,
+        DasLockType::ETH | DasLockType::ETHTypedData | DasLockType::TRON | DasLockType::MIXIN | DasLockType::Doge => {
+            witness.sign_type
+        }
         _ => {
             warn!(
                 "  witnesses[{:>2}] Parsing das-lock(witness.reverse_record.lock.args) algorithm failed (maybe not supported for now), but it is required in this transaction.",
@@ -222,7 +234,10 @@ fn verify_sign(sign_lib: &SignLib, witness: &ReverseRecordWitness) -> Result<(),
     })?;
     let ret = sign_lib.validate_str(das_lock_type, 0i32, message.clone(), message.len(), signature, args);
     match ret {
-        Err(_error_code) if _error_code == DasDynamicLibError::UndefinedDasLockType as i32 => {
+        Err(_error_code)
+            if _error_code == DasDynamicLibError::UndefinedDasLockType as i32
+                || _error_code == DasDynamicLibError::DynLibNotLoaded as i32 =>
+        {
             warn!(
                 "  witnesses[{:>2}] The signature algorithm has not been supported",
                 witness.index
@@ -18,7 +18,7 @@ use das_core::witness_parser::WitnessesParser;
 use das_core::{assert as das_assert, code_to_error, data_parser, debug, verifiers, warn};
 use das_dynamic_libs::constants::DynLibName;
 use das_dynamic_libs::sign_lib::SignLib;
-use das_dynamic_libs::{load_2_methods, load_lib, log_loading, new_context};
+use das_dynamic_libs::{load_lib, load_methods, log_loading, new_context};
 use das_types::constants::{AccountStatus, DataType, LockRole, SubAccountConfigFlag, SubAccountCustomRuleFlag};
 use das_types::packed::*;
 use das_types::prelude::{Builder, Entity};
@@ -78,8 +78,9 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
     Ok(())
 }
 
-fn action_config_sub_account(_action: &[u8], parser: &mut WitnessesParser) -> Result<(), Box<dyn ScriptError>> {
+fn action_config_sub_account(action: &[u8], parser: &mut WitnessesParser) -> Result<(), Box<dyn ScriptError>> {
     parser.parse_cell()?;
+    util::require_action_enabled(parser, action)?;
     let config_main = parser.configs.main()?;
     let config_account = parser.configs.account()?;
     let config_sub_account = parser.configs.sub_account()?;
@@ -253,10 +254,11 @@ fn action_config_sub_account(_action: &[u8], parser: &mut WitnessesParser) -> Re
 }
 
 fn action_config_sub_account_custom_script(
-    _action: &[u8],
+    action: &[u8],
     parser: &mut WitnessesParser,
 ) -> Result<(), Box<dyn ScriptError>> {
     parser.parse_cell()?;
+    util::require_action_enabled(parser, action)?;
     let config_main = parser.configs.main()?;
     let config_account = parser.configs.account()?;
     let config_sub_account = parser.configs.sub_account()?;
@@ -364,12 +366,13 @@ fn action_config_sub_account_custom_script(
 
 fn action_update_sub_account(action: &[u8], parser: &mut WitnessesParser) -> Result<(), Box<dyn ScriptError>> {
     parser.parse_cell()?;
+    util::require_action_enabled(parser, action)?;
     let config_main = parser.configs.main()?;
     let config_account = parser.configs.account()?;
     let config_sub_account = parser.configs.sub_account()?;
 
     let timestamp = util::load_oracle_data(OracleCellType::Time)?;
-    let quote = util::load_oracle_data(OracleCellType::Quote)?;
+    let quote = util::load_quote(parser)?;
 
     debug!("Preparing to parse sub-account witnesses by loading the SubAccountCell ...");
 
@@ -466,22 +469,49 @@ fn action_update_sub_account(action: &[u8], parser: &mut WitnessesParser) -> Res
         // let mut ckb_context = new_context!();
         // log_loading!(DynLibName::CKBSignhash, config_main.das_lock_type_id_table());
         // let ckb_lib = load_lib!(ckb_context, DynLibName::CKBSignhash, config_main.das_lock_type_id_table());
-        // sign_lib.ckb_signhash = load_2_methods!(ckb_lib);
+        // sign_lib.set_methods(DynLibName::CKBSignhash, load_methods!(ckb_lib, DynLibName::CKBSignhash));
 
         let mut eth_context = new_context!();
         log_loading!(DynLibName::ETH, config_main.das_lock_type_id_table());
         let eth_lib = load_lib!(eth_context, DynLibName::ETH, config_main.das_lock_type_id_table());
-        sign_lib.eth = load_2_methods!(eth_lib);
+        sign_lib.set_methods(DynLibName::ETH, load_methods!(eth_lib, DynLibName::ETH));
 
         let mut tron_context = new_context!();
         log_loading!(DynLibName::TRON, config_main.das_lock_type_id_table());
         let tron_lib = load_lib!(tron_context, DynLibName::TRON, config_main.das_lock_type_id_table());
-        sign_lib.tron = load_2_methods!(tron_lib);
+        sign_lib.set_methods(DynLibName::TRON, load_methods!(tron_lib, DynLibName::TRON));
 
         let mut doge_context = new_context!();
         log_loading!(DynLibName::DOGE, config_main.das_lock_type_id_table());
         let doge_lib = load_lib!(doge_context, DynLibName::DOGE, config_main.das_lock_type_id_table());
-        sign_lib.doge = load_2_methods!(doge_lib);
+        sign_lib.set_methods(DynLibName::DOGE, load_methods!(doge_lib, DynLibName::DOGE));
+
+        // The MIXIN lock is verified by the Ed25519 library.
+        let mut ed25519_context = new_context!();
+        log_loading!(DynLibName::ED25519, config_main.das_lock_type_id_table());
+        let ed25519_lib = load_lib!(
+            ed25519_context,
+            DynLibName::ED25519,
+            config_main.das_lock_type_id_table()
+        );
+        sign_lib.set_methods(DynLibName::ED25519, load_methods!(ed25519_lib, DynLibName::ED25519));
+
+        // The WebAuthn and BTC libraries have no slot in the DasLockTypeIdTable, they are loaded only when they are configured in
+        // ConfigCellMain.
+        let dyn_lib_type_id_table = util::get_dyn_lib_type_id_table(config_main)?;
+        let mut webauthn_context = new_context!();
+        if DynLibName::WebAuthn.get_code_hash(dyn_lib_type_id_table).is_some() {
+            log_loading!(DynLibName::WebAuthn, dyn_lib_type_id_table);
+            let webauthn_lib = load_lib!(webauthn_context, DynLibName::WebAuthn, dyn_lib_type_id_table);
+            sign_lib.set_methods(DynLibName::WebAuthn, load_methods!(webauthn_lib, DynLibName::WebAuthn));
+        }
+
+        let mut btc_context = new_context!();
+        if DynLibName::BTC.get_code_hash(dyn_lib_type_id_table).is_some() {
+            log_loading!(DynLibName::BTC, dyn_lib_type_id_table);
+            let btc_lib = load_lib!(btc_context, DynLibName::BTC, dyn_lib_type_id_table);
+            sign_lib.set_methods(DynLibName::BTC, load_methods!(btc_lib, DynLibName::BTC));
+        }
     }
 
     debug!("Initialize some vars base on the sub-actions contains in the transaction ...");
@@ -600,9 +630,8 @@ fn action_update_sub_account(action: &[u8], parser: &mut WitnessesParser) -> Res
                 let action_str = String::from_utf8(action.to_vec()).unwrap();
                 custom_script_params.push(action_str);
 
-                debug!("Try to find the QuoteCell from cell_deps and push quote into custom_script_params.");
+                debug!("Push quote into custom_script_params.");
 
-                let quote = util::load_oracle_data(OracleCellType::Quote)?;
                 custom_script_params.push(util::hex_string(&quote.to_le_bytes()));
 
                 let input_das_profit = data_parser::sub_account_cell::get_das_profit(&input_sub_account_data).unwrap();
@@ -922,6 +951,7 @@ fn action_update_sub_account(action: &[u8], parser: &mut WitnessesParser) -> Res
 
 fn action_collect_sub_account_profit(action: &[u8], parser: &mut WitnessesParser) -> Result<(), Box<dyn ScriptError>> {
     parser.parse_cell()?;
+    util::require_action_enabled(parser, action)?;
     let config_main = parser.configs.main()?;
     let config_sub_account = parser.configs.sub_account()?;
 
@@ -524,7 +524,7 @@ impl<'a> SubAction<'a> {
             self.parent_expired_at,
             self.sub_account_last_updated_at,
         )?;
-        verifiers::sub_account_cell::verify_sub_account_edit_sign(&witness, &self.sign_lib)?;
+        verifiers::sub_account_cell::verify_sub_account_edit_sign(self.parser, &witness, &self.sign_lib)?;
         verifiers::sub_account_cell::verify_expiration(
             self.config_account,
             witness.index,
@@ -579,6 +579,11 @@ impl<'a> SubAction<'a> {
             }
             SubAccountEditValue::Records(records) => {
                 verifiers::account_cell::verify_records_keys(self.parser, records.as_reader())?;
+                verifiers::record_value::verify_records_values(
+                    self.parser,
+                    Some(sub_account_reader.records()),
+                    records.as_reader(),
+                )?;
             }
             // manual::verify_edit_value_not_empty
             SubAccountEditValue::None | _ => {
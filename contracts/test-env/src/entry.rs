@@ -36,12 +36,16 @@ pub fn main() -> Result<(), Box<dyn ScriptError>> {
         b"test_parse_witness_raw_config" => {
             parser.configs.record_key_namespace()?;
         }
-        b"test_parse_witness_cells" => {
+        b"test_parse_witness_cells" | b"test_parse_witness_cells_lazily" => {
             let config_main = parser.configs.main()?;
             let account_cell_type_id = config_main.type_id_table().account_cell();
             let account_cells = util::find_cells_by_type_id(ScriptType::Type, account_cell_type_id, Source::CellDep)?;
 
-            parser.parse_cell()?;
+            if action == b"test_parse_witness_cells_lazily" {
+                parser.parse_cell_lazily()?;
+            } else {
+                parser.parse_cell()?;
+            }
 
             let (version, _, mol_bytes) =
                 parser.verify_and_get(DataType::AccountCellData, account_cells[0], Source::CellDep)?;
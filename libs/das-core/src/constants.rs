@@ -47,6 +47,14 @@ pub enum OracleCellType {
     Height = 2,
 }
 
+/// The payment mode of renew_account, it is passed as the only param of the action and no param means Capacity.
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(u8)]
+pub enum PaymentMode {
+    Capacity = 0,
+    Stablecoin = 1,
+}
+
 #[derive(Eq, PartialEq, Debug, Clone, Copy)]
 #[repr(u8)]
 pub enum CellField {
@@ -62,17 +70,52 @@ pub const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash";
 pub const CELL_BASIC_CAPACITY: u64 = 6_100_000_000;
 
 pub const RATE_BASE: u64 = 10_000;
+// All the prices in USD have 6 decimals, for example 5_000_000 means 5 USD.
+pub const USD_DECIMALS: u32 = 6;
+
+// The minimum delay in seconds between staging a ConfigCell update and the update taking effect.
+pub const CONFIG_CELL_ACTIVATION_DELAY: u64 = 3 * 86400;
 
 pub const ACCOUNT_ID_LENGTH: usize = 20;
 pub const ACCOUNT_SUFFIX: &str = ".bit";
 pub const ACCOUNT_MAX_PRICED_LENGTH: u8 = 8;
+// The AccountStatus in das-types has no variant for the two-step transfer, so the status is defined here following the
+// existing values. When the AccountCell is in this status, the pending owner is kept in the manager part of its lock.
+pub const ACCOUNT_STATUS_PENDING_TRANSFER: u8 = 4;
+// The window in seconds for the pending owner to accept a two-step transfer, it starts from last_transfer_account_at.
+pub const ACCOUNT_TRANSFER_ACCEPTANCE_WINDOW: u64 = 7 * 86400;
 
 pub const CUSTOM_KEYS_NAMESPACE: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz_";
+// The records of this type limit which records a manager can edit, the key is an allowed prefix of `type.key` and the value
+// is the manager in 0x-prefixed hex of its lock type and lock args. They can only be edited by the owner.
+pub const MANAGER_PERMISSION_RECORD_TYPE: &[u8] = b"manager_permission";
 pub const COIN_TYPE_DIGITS: &[u8] = b"0123456789";
 
 pub const SECP_SIGNATURE_SIZE: usize = 65;
 // This is smaller than the real data type in solidity, but it is enough for now.
 pub const EIP712_CHAINID_SIZE: usize = 8;
+// The optional byte after the chain ID in signatures, it indicates which schema of typed data is signed.
+pub const EIP712_SCHEMA_VERSION_SIZE: usize = 1;
+// The flag byte after the signature of DasLockType::ETH, it indicates the signature is an EIP-191 personal_sign of DAS_MESSAGE.
+pub const EIP191_PERSONAL_SIGN_FLAG: u8 = 1;
+// The flag byte at the end of witness_args.lock of DasLockType::ETH, it indicates the lock is held by a contract wallet and an
+// attestation of its approval is provided instead of a signature. It must differ from EIP191_PERSONAL_SIGN_FLAG and all the
+// EIP712 schema versions, because they are all trailing bytes of witness_args.lock.
+pub const CONTRACT_WALLET_ATTESTATION_FLAG: u8 = 255;
+// The binary format of the attestation in witness_args.lock is:
+//
+// - quorum: CONTRACT_WALLET_ATTESTATION_KIND_QUORUM(1) + count(1) + [attester_index(1) + signature(65)] * count + flag(1)
+// - storage proof: CONTRACT_WALLET_ATTESTATION_KIND_STORAGE_PROOF(1) + proof(32) + flag(1)
+pub const CONTRACT_WALLET_ATTESTATION_KIND_QUORUM: u8 = 0;
+pub const CONTRACT_WALLET_ATTESTATION_KIND_STORAGE_PROOF: u8 = 1;
+pub const CONTRACT_WALLET_ATTESTER_SIGNATURE_SIZE: usize = 1 + SECP_SIGNATURE_SIZE;
+pub const CONTRACT_WALLET_STORAGE_PROOF_SIZE: usize = 32;
+// The signature of DasLockType::WebAuthn is a secp256r1 signature in r + s format, and the public key is in x + y format.
+pub const WEBAUTHN_SIGNATURE_SIZE: usize = 64;
+pub const WEBAUTHN_PUBKEY_SIZE: usize = 64;
+// The first byte of the args of DasLockType::BTC, the pubkey_hash is the same HASH160 for both, only the address format differs.
+pub const BTC_ADDR_TYPE_P2PKH: u8 = 0;
+pub const BTC_ADDR_TYPE_P2WPKH: u8 = 1;
 
 pub const DAY_SEC: u64 = 86400;
 pub const DAYS_OF_YEAR: u64 = 365;
@@ -82,6 +125,11 @@ pub const PRE_ACCOUNT_CELL_TIMEOUT: u64 = DAY_SEC;
 pub const PRE_ACCOUNT_CELL_SHORT_TIMEOUT: u64 = 3600;
 
 pub const CROSS_CHAIN_BLACK_ARGS: [u8; 20] = [0; 20];
+pub const CROSS_CHAIN_HEADER_BYTES: usize = 72;
+pub const CROSS_CHAIN_MIN_CONFIRMATIONS: usize = 12;
+
+// The AccountRegistryCell is typed by pre-account-cell-type with these args.
+pub const ACCOUNT_REGISTRY_CELL_ARGS: &[u8] = b"account-registry";
 
 pub const TYPE_ID_CODE_HASH: [u8; 32] = [
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 84, 89, 80, 69, 95, 73, 68,
@@ -411,6 +459,23 @@ pub fn quote_cell_type() -> Script {
     util::script_literal_to_script(quote_cell_type)
 }
 
+pub fn cross_chain_light_client_cell_type() -> Option<Script> {
+    #[cfg(any(feature = "dev", feature = "local"))]
+    let light_client_cell_type = Some(ScriptLiteral {
+        code_hash: [
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ],
+        hash_type: ScriptHashType::Type,
+        args: vec![16],
+    });
+
+    // The light client of the other chains has not been deployed on these networks yet.
+    #[cfg(any(feature = "testnet", feature = "mainnet"))]
+    let light_client_cell_type = None;
+
+    light_client_cell_type.map(util::script_literal_to_script)
+}
+
 #[cfg(feature = "dev")]
 pub const CONFIG_CELL_TYPE: ScriptLiteral = ScriptLiteral {
     code_hash: [
@@ -0,0 +1,20 @@
+use core::convert::TryInto;
+
+// The account registry settings in ConfigCellMain is structured as:
+// [ mode(1 byte) | migration_started_at(8 bytes) ]
+// The mode is 0x00 for Off, 0x01 for Migrating and 0x02 for Enabled. The migration_started_at is a little-endian timestamp in
+// seconds, the PreAccountCells created before it have never been inserted into the registry, so it must not be earlier than
+// the time the Migrating mode takes effect and must never be changed after that.
+pub const DATA_LENGTH: usize = 9;
+
+pub fn is_valid_length(data: &[u8]) -> bool {
+    data.len() == DATA_LENGTH
+}
+
+pub fn get_mode(data: &[u8]) -> u8 {
+    data[0]
+}
+
+pub fn get_migration_started_at(data: &[u8]) -> u64 {
+    u64::from_le_bytes(data[1..9].try_into().unwrap())
+}
@@ -0,0 +1,32 @@
+use core::convert::TryInto;
+
+// The outputs_data of a ConfigCell is structured as:
+// [ active_hash(32 bytes) ]
+// or, when an update is staged:
+// [ active_hash(32 bytes) | pending_hash(32 bytes) | effective_at(8 bytes) ]
+// Both hashes are blake2b hashes of the witness entities, effective_at is a little-endian timestamp in seconds since
+// which the pending entity can be promoted to the active one by config-cell-type. Other scripts only read the active hash.
+pub const HASH_LENGTH: usize = 32;
+pub const STAGED_DATA_LENGTH: usize = HASH_LENGTH * 2 + 8;
+
+pub fn is_valid_length(data: &[u8]) -> bool {
+    data.len() == HASH_LENGTH || data.len() == STAGED_DATA_LENGTH
+}
+
+pub fn get_active_hash(data: &[u8]) -> [u8; 32] {
+    data.get(..HASH_LENGTH)
+        .expect("ConfigCell should have at least 32 bytes of data.")
+        .try_into()
+        .unwrap()
+}
+
+pub fn get_pending_opt(data: &[u8]) -> Option<([u8; 32], u64)> {
+    if data.len() != STAGED_DATA_LENGTH {
+        return None;
+    }
+
+    let hash = data[HASH_LENGTH..(HASH_LENGTH * 2)].try_into().unwrap();
+    let effective_at = u64::from_le_bytes(data[(HASH_LENGTH * 2)..].try_into().unwrap());
+
+    Some((hash, effective_at))
+}
@@ -0,0 +1,17 @@
+// The contract wallet settings in ConfigCellMain is structured as:
+// [ threshold(1 byte) | attester(20 bytes) * n ]
+// The attesters are ETH addresses, an attestation by quorum is accepted only when it is signed by at least threshold of them.
+pub const THRESHOLD_SIZE: usize = 1;
+pub const ATTESTER_SIZE: usize = 20;
+
+pub fn is_valid_length(data: &[u8]) -> bool {
+    data.len() > THRESHOLD_SIZE && (data.len() - THRESHOLD_SIZE) % ATTESTER_SIZE == 0
+}
+
+pub fn get_threshold(data: &[u8]) -> usize {
+    data[0] as usize
+}
+
+pub fn get_attesters(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    data[THRESHOLD_SIZE..].chunks(ATTESTER_SIZE)
+}
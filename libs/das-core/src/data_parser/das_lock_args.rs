@@ -15,6 +15,10 @@ pub fn get_owner_lock_args_opt(data: &[u8]) -> Option<&[u8]> {
     let ret = match data[0] {
         1 => data.get(1..29),
         6 => data.get(1..33),
+        // The args of WebAuthn is sub_alg_id(1) + pubkey_hash(10) + cid(10).
+        8 => data.get(1..22),
+        // The args of BTC is addr_type(1) + pubkey_hash(20), the addr_type decides if it is a P2PKH or P2WPKH address.
+        9 => data.get(1..22),
         _ => data.get(1..21),
     };
 
@@ -29,6 +33,7 @@ pub fn get_manager_type_opt(data: &[u8]) -> Option<u8> {
     let ret = match data[0] {
         1 => data.get(29),
         6 => data.get(33),
+        8 | 9 => data.get(22),
         _ => data.get(21),
     };
 
@@ -43,6 +48,7 @@ pub fn get_manager_lock_args_opt(data: &[u8]) -> Option<&[u8]> {
     let ret = match data[0] {
         1 => data.get(30..),
         6 => data.get(34..),
+        8 | 9 => data.get(23..),
         _ => data.get(22..),
     };
 
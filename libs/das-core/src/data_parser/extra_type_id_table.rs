@@ -0,0 +1,30 @@
+// The extra type ID table in ConfigCellMain is structured as:
+// [ webauthn_lib(32 bytes) | device_key_list_cell_type(32 bytes) | btc_lib(32 bytes) ]
+// The type IDs here have no slot in the DasLockTypeIdTable or TypeIdTable, new type IDs will be appended to the end, so a
+// shorter table is still valid and the missing or zero type IDs mean the scripts are not deployed.
+pub const TYPE_ID_SIZE: usize = 32;
+
+const WEBAUTHN_LIB_INDEX: usize = 0;
+const DEVICE_KEY_LIST_CELL_TYPE_INDEX: usize = 1;
+const BTC_LIB_INDEX: usize = 2;
+
+pub fn is_valid_length(data: &[u8]) -> bool {
+    data.len() % TYPE_ID_SIZE == 0
+}
+
+fn get_type_id(data: &[u8], index: usize) -> Option<&[u8]> {
+    data.get(index * TYPE_ID_SIZE..(index + 1) * TYPE_ID_SIZE)
+        .filter(|type_id| type_id.iter().any(|byte| *byte != 0))
+}
+
+pub fn get_webauthn_lib(data: &[u8]) -> Option<&[u8]> {
+    get_type_id(data, WEBAUTHN_LIB_INDEX)
+}
+
+pub fn get_device_key_list_cell_type(data: &[u8]) -> Option<&[u8]> {
+    get_type_id(data, DEVICE_KEY_LIST_CELL_TYPE_INDEX)
+}
+
+pub fn get_btc_lib(data: &[u8]) -> Option<&[u8]> {
+    get_type_id(data, BTC_LIB_INDEX)
+}
@@ -0,0 +1,34 @@
+use core::convert::TryInto;
+
+// The args of the belong_to of a vesting record in IncomeCellData is structured as:
+// [ start(8 bytes) | cliff(8 bytes) | duration(8 bytes) | total(8 bytes) | beneficiary(molecule encoded Script) ]
+// All the integers are little-endian, start is a timestamp in seconds, cliff and duration are seconds counted from start.
+pub const HEADER_LENGTH: usize = 32;
+
+fn get_u64_opt(data: &[u8], start: usize) -> Option<u64> {
+    data.get(start..start + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+pub fn get_start_opt(data: &[u8]) -> Option<u64> {
+    get_u64_opt(data, 0)
+}
+
+pub fn get_cliff_opt(data: &[u8]) -> Option<u64> {
+    get_u64_opt(data, 8)
+}
+
+pub fn get_duration_opt(data: &[u8]) -> Option<u64> {
+    get_u64_opt(data, 16)
+}
+
+pub fn get_total_opt(data: &[u8]) -> Option<u64> {
+    get_u64_opt(data, 24)
+}
+
+pub fn get_beneficiary_opt(data: &[u8]) -> Option<&[u8]> {
+    match data.get(HEADER_LENGTH..) {
+        Some(bytes) if !bytes.is_empty() => Some(bytes),
+        _ => None,
+    }
+}
@@ -1,5 +1,12 @@
 pub mod account_cell;
+pub mod account_registry_settings;
 pub mod apply_register_cell;
+pub mod config_cell;
+pub mod contract_wallet_settings;
 pub mod das_lock_args;
+pub mod extra_type_id_table;
+pub mod income_vesting_args;
+pub mod oracle_settings;
 pub mod pre_account_cell;
+pub mod stablecoin_settings;
 pub mod sub_account_cell;
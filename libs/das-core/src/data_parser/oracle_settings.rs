@@ -0,0 +1,29 @@
+use core::convert::TryInto;
+
+// The oracle settings in ConfigCellMain is structured as:
+// [ max_age(8 bytes) | min_quote_sources(1 byte) | max_quote_deviation(4 bytes) | quote_cell_type_hash(32 bytes) * n ]
+// All the integers are little-endian, max_age is in seconds and 0 means no limit, max_quote_deviation is based on RATE_BASE.
+// The quote_cell_type_hashes are the hashes of the type scripts of QuoteCells which come from different oracles, when there is
+// none only the default QuoteCell is accepted.
+pub const DATA_LENGTH: usize = 13;
+pub const TYPE_HASH_SIZE: usize = 32;
+
+pub fn is_valid_length(data: &[u8]) -> bool {
+    data.len() >= DATA_LENGTH && (data.len() - DATA_LENGTH) % TYPE_HASH_SIZE == 0
+}
+
+pub fn get_max_age(data: &[u8]) -> u64 {
+    u64::from_le_bytes(data[0..8].try_into().unwrap())
+}
+
+pub fn get_min_quote_sources(data: &[u8]) -> u8 {
+    data[8]
+}
+
+pub fn get_max_quote_deviation(data: &[u8]) -> u32 {
+    u32::from_le_bytes(data[9..13].try_into().unwrap())
+}
+
+pub fn get_quote_cell_type_hashes(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    data[DATA_LENGTH..].chunks_exact(TYPE_HASH_SIZE)
+}
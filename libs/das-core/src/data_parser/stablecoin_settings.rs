@@ -0,0 +1,16 @@
+// The stablecoin settings in ConfigCellMain is structured as:
+// [ type_hash(32 bytes) | decimals(1 byte) ]
+// The type_hash is the hash of the type script of the xUDT/sUDT, the decimals is the decimals of the amount of the UDT.
+pub const DATA_LENGTH: usize = 33;
+
+pub fn is_valid_length(data: &[u8]) -> bool {
+    data.len() == DATA_LENGTH
+}
+
+pub fn get_type_hash(data: &[u8]) -> &[u8] {
+    &data[0..32]
+}
+
+pub fn get_decimals(data: &[u8]) -> u8 {
+    data[32]
+}
@@ -105,16 +105,22 @@ pub enum ErrorCode {
     ProposalRecycleNeedWaitLonger,
     ProposalRecycleRefundAmountError,
     PrevProposalItemNotFound,
+    ProposalRangeOverlapped, // 120
+    ProposalRelatedCellOutOfRange,
     IncomeCellConsolidateConditionNotSatisfied = -126,
     IncomeCellConsolidateError,
     IncomeCellConsolidateWaste,
     IncomeCellTransferError,
     IncomeCellCapacityError,
     IncomeCellProfitMismatch,
+    IncomeCellWithdrawError,
+    IncomeCellVestingRecordInvalid,
     EIP712SerializationError = -90,
     EIP712SematicError,
     EIP712DecodingWitnessArgsError,
     EIP712SignatureError,
+    ContractWalletAttestationError,
+    WebAuthnSignatureError,
     BalanceCellFoundSomeOutputsLackOfType = -80,
     BalanceCellCanNotBeSpent,
     AccountSaleCellCapacityError,
@@ -138,6 +144,13 @@ pub enum ErrorCode {
     UpgradeDefaultValueOfNewFieldIsError,
     CrossChainLockError,
     CrossChainUnlockError,
+    CrossChainProofInvalid,
+    ActionPaused,
+    ConfigCellIsInvalid,
+    ConfigCellStagingError,
+    OracleCellIsStale,
+    OracleQuoteDeviationTooLarge,
+    StablecoinPaymentError,
     UnittestError = -2,
     SystemOff = -1,
 }
@@ -206,6 +219,10 @@ pub enum AccountCellErrorCode {
     // 70
     AccountCellHasExpired,
     AccountCellStillCanNotRecycle,
+    AccountCellPendingOwnerInvalid,
+    AccountCellTransferAcceptanceExpired,
+    AccountCellRecordNotPermitted,
+    AccountCellRecordValueInvalid,
 }
 
 impl From<SysError> for AccountCellErrorCode {
@@ -259,6 +276,8 @@ pub enum PreAccountCellErrorCode {
     InviterIdIsInvalid,
     InviteeDiscountShouldBeEmpty,
     InviteeDiscountIsInvalid,
+    AccountRegistryIsNotEnabled,
+    AccountRegistryCellError,
 }
 
 impl From<SysError> for PreAccountCellErrorCode {
@@ -11,7 +11,11 @@ use das_types::constants::DasLockType;
 
 use super::error::*;
 use super::{code_to_error, util};
-use crate::constants::{ScriptType, SECP_SIGNATURE_SIZE};
+use crate::constants::{
+    ScriptType, CONTRACT_WALLET_ATTESTATION_FLAG, CONTRACT_WALLET_ATTESTATION_KIND_QUORUM,
+    CONTRACT_WALLET_ATTESTATION_KIND_STORAGE_PROOF, CONTRACT_WALLET_ATTESTER_SIGNATURE_SIZE,
+    CONTRACT_WALLET_STORAGE_PROOF_SIZE, EIP191_PERSONAL_SIGN_FLAG, SECP_SIGNATURE_SIZE,
+};
 
 fn find_input_size() -> Result<usize, Box<dyn ScriptError>> {
     let mut i = 1;
@@ -38,6 +42,31 @@ fn find_input_size() -> Result<usize, Box<dyn ScriptError>> {
     Ok(i)
 }
 
+/// Check if the witness_args.lock of DasLockType::ETH contains an EIP-191 personal_sign signature.
+pub fn is_personal_sign_lock(lock_bytes: &[u8]) -> bool {
+    lock_bytes.len() == SECP_SIGNATURE_SIZE + 1 && lock_bytes[SECP_SIGNATURE_SIZE] == EIP191_PERSONAL_SIGN_FLAG
+}
+
+/// Check if the witness_args.lock of DasLockType::ETH contains an attestation of a contract wallet.
+///
+/// Only the exact lengths of the known attestation kinds are accepted, see constants::CONTRACT_WALLET_ATTESTATION_KIND_* for
+/// the binary format.
+pub fn is_contract_wallet_lock(lock_bytes: &[u8]) -> bool {
+    let len = lock_bytes.len();
+    if len < 3 || lock_bytes[len - 1] != CONTRACT_WALLET_ATTESTATION_FLAG {
+        return false;
+    }
+
+    match lock_bytes[0] {
+        CONTRACT_WALLET_ATTESTATION_KIND_QUORUM => {
+            let count = lock_bytes[1] as usize;
+            count > 0 && len == 1 + 1 + count * CONTRACT_WALLET_ATTESTER_SIGNATURE_SIZE + 1
+        }
+        CONTRACT_WALLET_ATTESTATION_KIND_STORAGE_PROOF => len == 1 + CONTRACT_WALLET_STORAGE_PROOF_SIZE + 1,
+        _ => false,
+    }
+}
+
 pub fn calc_digest_by_lock(
     sign_type: DasLockType,
     script: ScriptReader,
@@ -103,6 +132,19 @@ pub fn calc_digest_by_input_group(
 
                     data
                 }
+                // Keep the flag byte, so the signing mode is also covered by the digest.
+                DasLockType::ETH if is_personal_sign_lock(witness_args_lock.raw_data()) => {
+                    let mut data = vec![0u8; SECP_SIGNATURE_SIZE];
+                    data.push(EIP191_PERSONAL_SIGN_FLAG);
+
+                    data
+                }
+                DasLockType::ETH if is_contract_wallet_lock(witness_args_lock.raw_data()) => {
+                    let mut data = vec![0u8; witness_args_lock.len() - 1];
+                    data.push(CONTRACT_WALLET_ATTESTATION_FLAG);
+
+                    data
+                }
                 _ => {
                     vec![0u8; witness_args_lock.len()]
                 }
@@ -16,6 +16,7 @@ use ckb_std::ckb_types::packed::*;
 use ckb_std::ckb_types::prelude::*;
 use ckb_std::error::SysError;
 use ckb_std::{high_level, syscalls};
+use das_dynamic_libs::constants::ExtendedDynLibTypeIdTable;
 use das_types::constants::{DasLockType, DataType, LockRole, WITNESS_HEADER};
 use das_types::mixer::*;
 use das_types::packed::{self as das_packed};
@@ -436,10 +437,14 @@ pub fn load_oracle_data(type_: OracleCellType) -> Result<u64, Box<dyn ScriptErro
         ret.len()
     );
 
-    debug!("cell_deps[{}] Parsing outputs_data of {:?}Cell ...", ret[0], type_);
+    load_oracle_cell_data(ret[0], type_)
+}
+
+fn load_oracle_cell_data(index: usize, type_: OracleCellType) -> Result<u64, Box<dyn ScriptError>> {
+    debug!("cell_deps[{}] Parsing outputs_data of {:?}Cell ...", index, type_);
 
     // Read the passed timestamp from outputs_data of TimeCell
-    let data = load_cell_data(ret[0], Source::CellDep)?;
+    let data = load_cell_data(index, Source::CellDep)?;
     let data_in_uint = match data.get(2..) {
         Some(bytes) => {
             das_assert!(
@@ -459,6 +464,155 @@ pub fn load_oracle_data(type_: OracleCellType) -> Result<u64, Box<dyn ScriptErro
     Ok(data_in_uint as u64)
 }
 
+/// Load the quote of CKB/USD from the QuoteCells in cell_deps.
+///
+/// Without the oracle settings in ConfigCellMain, there should be only one QuoteCell just like load_oracle_data. Otherwise
+/// there should be one QuoteCell from each of at least min_quote_sources different oracles, the median of them is used, and
+/// every QuoteCell should be fresh enough and close enough to the median.
+pub fn load_quote(parser: &WitnessesParser) -> Result<u64, Box<dyn ScriptError>> {
+    let config_main = parser.configs.main()?;
+    let settings = match get_oracle_settings(config_main)? {
+        Some(settings) => settings,
+        None => return load_oracle_data(OracleCellType::Quote),
+    };
+
+    das_assert!(
+        data_parser::oracle_settings::is_valid_length(settings),
+        ErrorCode::ConfigCellWitnessDecodingError,
+        "The oracle settings in ConfigCellMain should be {} bytes followed by {} bytes of each QuoteCell type hash, but {} bytes found.",
+        data_parser::oracle_settings::DATA_LENGTH,
+        data_parser::oracle_settings::TYPE_HASH_SIZE,
+        settings.len()
+    );
+
+    let max_age = data_parser::oracle_settings::get_max_age(settings);
+    let min_quote_sources = data_parser::oracle_settings::get_min_quote_sources(settings);
+    let max_quote_deviation = data_parser::oracle_settings::get_max_quote_deviation(settings);
+
+    let default_source = blake2b_256(quote_cell_type().as_slice());
+    let mut sources: Vec<&[u8]> = data_parser::oracle_settings::get_quote_cell_type_hashes(settings).collect();
+    if sources.is_empty() {
+        sources.push(&default_source);
+    }
+
+    debug!("Finding QuoteCells of {} oracles in cell_deps ...", sources.len());
+
+    // Multiple QuoteCells from the same oracle are not independent, so only one QuoteCell of each oracle is allowed.
+    let mut quote_cells: Vec<usize> = Vec::new();
+    let mut found_sources: Vec<&[u8]> = Vec::new();
+    let mut i = 0;
+    loop {
+        match high_level::load_cell_type_hash(i, Source::CellDep) {
+            Ok(Some(hash)) => {
+                if let Some(source) = sources.iter().find(|source| **source == hash) {
+                    das_assert!(
+                        !found_sources.contains(source),
+                        ErrorCode::InvalidTransactionStructure,
+                        "cell_deps[{}] There should be only one QuoteCell of the oracle 0x{}.",
+                        i,
+                        hex_string(source)
+                    );
+
+                    found_sources.push(*source);
+                    quote_cells.push(i);
+                }
+            }
+            Ok(None) => {}
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err.into()),
+        }
+
+        i += 1;
+    }
+
+    das_assert!(
+        !quote_cells.is_empty() && quote_cells.len() >= min_quote_sources as usize,
+        ErrorCode::OracleCellIsRequired,
+        "There should be QuoteCells of at least {} different oracles in cell_deps, but {} found.",
+        min_quote_sources,
+        quote_cells.len()
+    );
+
+    // The max_age is disabled when it is 0, then the TimeCell and the header_deps are not required.
+    if max_age > 0 {
+        verify_oracle_cells_freshness(&quote_cells, max_age)?;
+    }
+
+    let mut quotes = Vec::new();
+    for index in quote_cells {
+        quotes.push(load_oracle_cell_data(index, OracleCellType::Quote)?);
+    }
+
+    quotes.sort_unstable();
+    let middle = quotes.len() / 2;
+    let median = if quotes.len() % 2 == 0 {
+        (quotes[middle - 1] + quotes[middle]) / 2
+    } else {
+        quotes[middle]
+    };
+
+    for quote in quotes.iter() {
+        let deviation = quote.abs_diff(median) * RATE_BASE / median.max(1);
+        das_assert!(
+            deviation <= max_quote_deviation as u64,
+            ErrorCode::OracleQuoteDeviationTooLarge,
+            "The quote {} deviates too much from the median {}.(deviation: {}/{}, max_deviation: {}/{})",
+            quote,
+            median,
+            deviation,
+            RATE_BASE,
+            max_quote_deviation,
+            RATE_BASE
+        );
+    }
+
+    debug!("The median quote of {} QuoteCells is {}.", quotes.len(), median);
+
+    Ok(median)
+}
+
+/// The TimeCell can not be the only reference of the current time, because a TimeCell which is no longer updated carries an
+/// old timestamp too. So the newest timestamp among the TimeCell and the block headers of all the OracleCells is used as the
+/// current time, and every OracleCell including the TimeCell should be updated within max_age before it.
+fn verify_oracle_cells_freshness(quote_cells: &[usize], max_age: u64) -> Result<(), Box<dyn ScriptError>> {
+    let time_cells = find_cells_by_script(ScriptType::Type, time_cell_type().as_reader(), Source::CellDep)?;
+    das_assert!(
+        time_cells.len() == 1,
+        ErrorCode::OracleCellIsRequired,
+        "There should be one cell of {:?} in cell_deps, no more and no less, but {} found.",
+        OracleCellType::Time,
+        time_cells.len()
+    );
+
+    let mut current = load_oracle_cell_data(time_cells[0], OracleCellType::Time)?;
+    let mut oracle_cells = Vec::new();
+    for (index, type_) in time_cells
+        .iter()
+        .map(|index| (*index, OracleCellType::Time))
+        .chain(quote_cells.iter().map(|index| (*index, OracleCellType::Quote)))
+    {
+        let header = load_header(index, Source::CellDep)?;
+        let updated_at = get_timestamp_from_header(header.as_reader());
+        current = current.max(updated_at);
+        oracle_cells.push((index, type_, updated_at));
+    }
+
+    for (index, type_, updated_at) in oracle_cells {
+        das_assert!(
+            current - updated_at <= max_age,
+            ErrorCode::OracleCellIsStale,
+            "cell_deps[{}] The {:?}Cell is stale.(updated_at: {}, current: {}, max_age: {})",
+            index,
+            type_,
+            updated_at,
+            current,
+            max_age
+        );
+    }
+
+    Ok(())
+}
+
 pub fn load_cells_capacity(cells: &[usize], source: Source) -> Result<u64, Box<dyn ScriptError>> {
     let mut total_input_capacity = 0;
     for i in cells.iter() {
@@ -614,6 +768,221 @@ pub fn is_system_off(parser: &WitnessesParser) -> Result<(), Box<dyn ScriptError
     Ok(())
 }
 
+/// Verify if the action is neither stopped by the global status nor paused individually in ConfigCellMain.
+///
+/// ⚠️ The config-cell-type must not call this, otherwise it will be impossible to turn the system back on. ⚠️
+pub fn require_action_enabled(parser: &WitnessesParser, action: &[u8]) -> Result<(), Box<dyn ScriptError>> {
+    is_system_off(parser)?;
+
+    let config_main = parser.configs.main()?;
+    let paused_actions = get_paused_actions(config_main)?;
+    if let Some(paused_actions) = paused_actions {
+        das_assert!(
+            paused_actions.iter().all(|paused| paused.raw_data() != action),
+            ErrorCode::ActionPaused,
+            "The action {} is paused currently.",
+            String::from_utf8(action.to_vec()).unwrap_or_default()
+        );
+    }
+
+    Ok(())
+}
+
+/// Get the raw extra field of ConfigCellMain by its index counted from the end of the known fields.
+///
+/// The extra fields are appended to ConfigCellMain in the order of paused_actions, oracle_settings, stablecoin_settings,
+/// record_value_validation_keys, contract_wallet_settings, extra_type_id_table.
+/// Because ConfigCellMain is decoded by from_compatible_slice, the contracts which do not know these fields can still work
+/// as before.
+fn get_config_main_extra_field<'r>(
+    config_main: das_packed::ConfigCellMainReader<'r>,
+    extra_index: usize,
+) -> Result<Option<&'r [u8]>, Box<dyn ScriptError>> {
+    const OFFSET_SIZE: usize = 4;
+
+    let field_index = das_packed::ConfigCellMainReader::FIELD_COUNT + extra_index;
+    if config_main.field_count() <= field_index {
+        return Ok(None);
+    }
+
+    let slice = config_main.as_slice();
+    let read_offset = |i: usize| -> Option<usize> {
+        let bytes = slice.get(OFFSET_SIZE * (i + 1)..OFFSET_SIZE * (i + 2))?;
+        Some(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+
+    let start = read_offset(field_index);
+    let end = if config_main.field_count() > field_index + 1 {
+        read_offset(field_index + 1)
+    } else {
+        Some(slice.len())
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end && end <= slice.len() => Ok(Some(&slice[start..end])),
+        _ => {
+            warn!("The extra fields of ConfigCellMain are invalid.");
+            Err(code_to_error!(ErrorCode::ConfigCellWitnessDecodingError))
+        }
+    }
+}
+
+/// Get the paused actions from ConfigCellMain, they are stored as a BytesVec.
+fn get_paused_actions(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<Option<das_packed::BytesVecReader>, Box<dyn ScriptError>> {
+    match get_config_main_extra_field(config_main, 0)? {
+        Some(raw) => {
+            let paused_actions = das_packed::BytesVecReader::from_slice(raw).map_err(|_| {
+                warn!("Decoding the paused actions in ConfigCellMain failed.");
+                ErrorCode::ConfigCellWitnessDecodingError
+            })?;
+
+            Ok(Some(paused_actions))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Get the settings stored as a Bytes in the extra field of ConfigCellMain, empty Bytes means the settings are not set.
+fn get_config_main_extra_settings(
+    config_main: das_packed::ConfigCellMainReader,
+    extra_index: usize,
+) -> Result<Option<&[u8]>, Box<dyn ScriptError>> {
+    match get_config_main_extra_field(config_main, extra_index)? {
+        Some(raw) => {
+            let settings = das_packed::BytesReader::from_slice(raw).map_err(|_| {
+                warn!("Decoding the extra field {} of ConfigCellMain failed.", extra_index);
+                ErrorCode::ConfigCellWitnessDecodingError
+            })?;
+
+            if settings.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(settings.raw_data()))
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Get the oracle settings from ConfigCellMain, see data_parser::oracle_settings for details.
+fn get_oracle_settings(config_main: das_packed::ConfigCellMainReader) -> Result<Option<&[u8]>, Box<dyn ScriptError>> {
+    get_config_main_extra_settings(config_main, 1)
+}
+
+/// Get the stablecoin settings from ConfigCellMain, see data_parser::stablecoin_settings for details.
+pub fn get_stablecoin_settings(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<Option<&[u8]>, Box<dyn ScriptError>> {
+    let settings = get_config_main_extra_settings(config_main, 2)?;
+    if let Some(settings) = settings {
+        das_assert!(
+            data_parser::stablecoin_settings::is_valid_length(settings),
+            ErrorCode::ConfigCellWitnessDecodingError,
+            "The stablecoin settings in ConfigCellMain should be {} bytes, but {} found.",
+            data_parser::stablecoin_settings::DATA_LENGTH,
+            settings.len()
+        );
+    }
+
+    Ok(settings)
+}
+
+/// Get the payment mode from the params of the action, the mode is paying by CKB when the params is empty.
+pub fn get_payment_mode(params: &[das_packed::Bytes]) -> Result<PaymentMode, Box<dyn ScriptError>> {
+    let param = match params.get(0) {
+        Some(val) => val.as_reader().raw_data(),
+        None => return Ok(PaymentMode::Capacity),
+    };
+
+    das_assert!(
+        param.len() == 1,
+        ErrorCode::ParamsDecodingError,
+        "The param of payment mode should be 1 byte, but {} bytes found.",
+        param.len()
+    );
+
+    match param[0] {
+        0 => Ok(PaymentMode::Capacity),
+        1 => Ok(PaymentMode::Stablecoin),
+        _ => {
+            warn!("The param of payment mode is invalid. (current: 0x{:02x})", param[0]);
+            Err(code_to_error!(ErrorCode::ParamsDecodingError))
+        }
+    }
+}
+
+/// Get the record keys whose values should be validated from ConfigCellMain, the keys are joined with 0x00 byte just like the
+/// ConfigCellRecordKeyNamespace.
+pub fn get_record_value_validation_keys(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<Option<&[u8]>, Box<dyn ScriptError>> {
+    get_config_main_extra_settings(config_main, 3)
+}
+
+/// Get the contract wallet settings from ConfigCellMain, see data_parser::contract_wallet_settings for details.
+pub fn get_contract_wallet_settings(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<Option<&[u8]>, Box<dyn ScriptError>> {
+    let settings = get_config_main_extra_settings(config_main, 4)?;
+    if let Some(settings) = settings {
+        das_assert!(
+            data_parser::contract_wallet_settings::is_valid_length(settings),
+            ErrorCode::ConfigCellWitnessDecodingError,
+            "The contract wallet settings in ConfigCellMain should be 1 byte of threshold followed by 20 bytes of each attester, but {} bytes found.",
+            settings.len()
+        );
+    }
+
+    Ok(settings)
+}
+
+/// Get the extra type ID table from ConfigCellMain, see data_parser::extra_type_id_table for details.
+pub fn get_extra_type_id_table(config_main: das_packed::ConfigCellMainReader) -> Result<&[u8], Box<dyn ScriptError>> {
+    let table = get_config_main_extra_settings(config_main, 5)?.unwrap_or_default();
+    das_assert!(
+        data_parser::extra_type_id_table::is_valid_length(table),
+        ErrorCode::ConfigCellWitnessDecodingError,
+        "The extra type ID table in ConfigCellMain should be a multiple of {} bytes, but {} bytes found.",
+        data_parser::extra_type_id_table::TYPE_ID_SIZE,
+        table.len()
+    );
+
+    Ok(table)
+}
+
+/// Get the account registry settings from ConfigCellMain, see data_parser::account_registry_settings for details.
+pub fn get_account_registry_settings(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<Option<&[u8]>, Box<dyn ScriptError>> {
+    let settings = get_config_main_extra_settings(config_main, 6)?;
+    if let Some(settings) = settings {
+        das_assert!(
+            data_parser::account_registry_settings::is_valid_length(settings),
+            ErrorCode::ConfigCellWitnessDecodingError,
+            "The account registry settings in ConfigCellMain should be {} bytes, but {} found.",
+            data_parser::account_registry_settings::DATA_LENGTH,
+            settings.len()
+        );
+    }
+
+    Ok(settings)
+}
+
+/// Get the type IDs of all the dynamic libraries, including the ones which have no slot in the DasLockTypeIdTable.
+pub fn get_dyn_lib_type_id_table(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<ExtendedDynLibTypeIdTable, Box<dyn ScriptError>> {
+    let extra_type_id_table = get_extra_type_id_table(config_main)?;
+
+    Ok(ExtendedDynLibTypeIdTable {
+        das_lock_type_id_table: config_main.das_lock_type_id_table(),
+        webauthn: data_parser::extra_type_id_table::get_webauthn_lib(extra_type_id_table),
+        btc: data_parser::extra_type_id_table::get_btc_lib(extra_type_id_table),
+    })
+}
+
 pub fn get_length_in_price(account_length: u64) -> u8 {
     if account_length > ACCOUNT_MAX_PRICED_LENGTH.into() {
         ACCOUNT_MAX_PRICED_LENGTH
@@ -733,6 +1102,24 @@ pub fn calc_duration_from_paid(paid: u64, yearly_price: u64, quote: u64, discoun
     paid * 365 / yearly_capacity * 86400
 }
 
+pub fn calc_yearly_udt_amount(yearly_price: u64, decimals: u8) -> u128 {
+    yearly_price as u128 * 10u128.pow(decimals as u32) / 10u128.pow(USD_DECIMALS)
+}
+
+/// Convert the capacity to the amount of the stablecoin, the capacity is converted to USD by the quote first.
+pub fn calc_udt_amount_from_capacity(capacity: u64, quote: u64, decimals: u8) -> u128 {
+    let price_in_usd = capacity as u128 * quote as u128 / 100_000_000;
+
+    price_in_usd * 10u128.pow(decimals as u32) / 10u128.pow(USD_DECIMALS)
+}
+
+pub fn calc_duration_from_udt_paid(paid: u128, yearly_price: u64, decimals: u8) -> u64 {
+    let yearly_amount = calc_yearly_udt_amount(yearly_price, decimals);
+
+    // The same as calc_duration_from_paid, the stablecoin is treated as USD directly, so there is no quote.
+    (paid * 365 / yearly_amount) as u64 * 86400
+}
+
 fn get_type_id(
     parser: &WitnessesParser,
     type_script: TypeScript,
@@ -809,6 +1196,8 @@ pub fn get_action_required_role(action: &[u8]) -> Option<LockRole> {
     match action {
         // account-cell-type
         b"edit_records" => Some(LockRole::Manager),
+        // The pending owner of a two-step transfer is kept in the manager part of the lock.
+        b"accept_transfer_account" => Some(LockRole::Manager),
         _ => Some(LockRole::Owner),
     }
 }
@@ -1,7 +1,7 @@
 use alloc::boxed::Box;
 use alloc::string::String;
-use alloc::vec;
 use alloc::vec::Vec;
+use alloc::{format, vec};
 use core::convert::TryFrom;
 
 use ckb_std::ckb_constants::Source;
@@ -9,6 +9,7 @@ use ckb_std::high_level;
 use das_types::constants::*;
 use das_types::mixer::AccountCellDataReaderMixer;
 use das_types::packed::*;
+use das_types::prelude::Reader;
 use das_types::util as das_types_util;
 
 use crate::constants::*;
@@ -388,6 +389,22 @@ pub fn verify_status_conversion<'a>(
     output_account_cell_witness_reader: &Box<dyn AccountCellDataReaderMixer + 'a>,
     expected_input_status: AccountStatus,
     expected_output_status: AccountStatus,
+) -> Result<(), Box<dyn ScriptError>> {
+    verify_raw_status_conversion(
+        input_account_cell_witness_reader,
+        output_account_cell_witness_reader,
+        expected_input_status as u8,
+        expected_output_status as u8,
+    )
+}
+
+/// The same as verify_status_conversion, but the status is passed as u8, so the statuses which are not defined in
+/// AccountStatus like ACCOUNT_STATUS_PENDING_TRANSFER can be verified too.
+pub fn verify_raw_status_conversion<'a>(
+    input_account_cell_witness_reader: &Box<dyn AccountCellDataReaderMixer + 'a>,
+    output_account_cell_witness_reader: &Box<dyn AccountCellDataReaderMixer + 'a>,
+    expected_input_status: u8,
+    expected_output_status: u8,
 ) -> Result<(), Box<dyn ScriptError>> {
     if input_account_cell_witness_reader.version() <= 1 {
         // CAREFUL! The early versions will no longer be supported.
@@ -397,14 +414,14 @@ pub fn verify_status_conversion<'a>(
         let output_status = u8::from(output_account_cell_witness_reader.status());
 
         das_assert!(
-            input_status == expected_input_status as u8,
+            input_status == expected_input_status,
             AccountCellErrorCode::AccountCellStatusLocked,
             "The AccountCell.witness.status should be {:?} in inputs, received {}",
             expected_input_status,
             input_status
         );
         das_assert!(
-            output_status == expected_output_status as u8,
+            output_status == expected_output_status,
             AccountCellErrorCode::AccountCellStatusLocked,
             "The AccountCell.witness.status should be {:?} in outputs, received {}",
             expected_output_status,
@@ -707,6 +724,16 @@ pub fn verify_records_keys(parser: &WitnessesParser, records: RecordsReader) ->
         let record_type = Vec::from(record.record_type().raw_data());
         let record_key = Vec::from(record.record_key().raw_data());
         match record_type.as_slice() {
+            MANAGER_PERMISSION_RECORD_TYPE => {
+                das_assert!(
+                    !record_key.is_empty()
+                        && record_key
+                            .iter()
+                            .all(|char| CUSTOM_KEYS_NAMESPACE.contains(char) || *char == b'.'),
+                    AccountCellErrorCode::AccountCellRecordKeyInvalid,
+                    "The keys in manager_permission should only contain digits, lowercase alphabet, underline and dot."
+                );
+            }
             b"custom_key" => {
                 // CAREFUL Triple check
                 for char in record_key.iter() {
@@ -763,3 +790,141 @@ pub fn verify_records_keys(parser: &WitnessesParser, records: RecordsReader) ->
 
     Ok(())
 }
+
+/// Get the manager of the AccountCell in the same format as the value of the manager_permission records.
+pub fn to_manager_permission_value(lock_args: &[u8]) -> Vec<u8> {
+    let manager_type = data_parser::das_lock_args::get_manager_type(lock_args);
+    let manager_args = data_parser::das_lock_args::get_manager_lock_args(lock_args);
+
+    format!(
+        "0x{}{}",
+        util::hex_string(&[manager_type]),
+        util::hex_string(manager_args)
+    )
+    .into_bytes()
+}
+
+/// Get the record key prefixes which the manager of the AccountCell is allowed to edit, empty means no limit.
+pub fn get_manager_permissions(records: RecordsReader, lock_args: &[u8]) -> Vec<Vec<u8>> {
+    let manager = to_manager_permission_value(lock_args);
+
+    records
+        .iter()
+        .filter(|record| {
+            record.record_type().raw_data() == MANAGER_PERMISSION_RECORD_TYPE
+                && record.record_value().raw_data() == manager.as_slice()
+        })
+        .map(|record| record.record_key().raw_data().to_vec())
+        .collect()
+}
+
+/// Find the records which exist only in one side of the inputs and outputs.
+fn diff_records<'a>(input_records: RecordsReader<'a>, output_records: RecordsReader<'a>) -> Vec<RecordReader<'a>> {
+    let mut changed_records = Vec::new();
+    for record in input_records.iter() {
+        if !output_records.iter().any(|item| item.as_slice() == record.as_slice()) {
+            changed_records.push(record);
+        }
+    }
+    for record in output_records.iter() {
+        if !input_records.iter().any(|item| item.as_slice() == record.as_slice()) {
+            changed_records.push(record);
+        }
+    }
+
+    changed_records
+}
+
+/// Verify the records edited by the manager are all permitted by the manager_permission records.
+pub fn verify_records_changes_permitted(
+    input_account_index: usize,
+    input_records: RecordsReader,
+    output_records: RecordsReader,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if the records changed are permitted for the manager.");
+
+    let lock = high_level::load_cell_lock(input_account_index, Source::Input)?;
+    let lock_args = lock.as_reader().args().raw_data();
+    let permissions = get_manager_permissions(input_records, lock_args);
+
+    for record in diff_records(input_records, output_records) {
+        let record_type = record.record_type().raw_data();
+        let record_type_and_key = [record_type, b".", record.record_key().raw_data()].concat();
+
+        das_assert!(
+            record_type != MANAGER_PERMISSION_RECORD_TYPE,
+            AccountCellErrorCode::AccountCellRecordNotPermitted,
+            "inputs[{}] The manager_permission records can only be edited by the owner.",
+            input_account_index
+        );
+
+        das_assert!(
+            permissions.is_empty()
+                || permissions
+                    .iter()
+                    .any(|prefix| is_permitted_by(&record_type_and_key, prefix)),
+            AccountCellErrorCode::AccountCellRecordNotPermitted,
+            "inputs[{}] The manager is not permitted to edit the record {}.",
+            input_account_index,
+            String::from_utf8(record_type_and_key).unwrap_or_default()
+        );
+    }
+
+    Ok(())
+}
+
+/// Check if the record is permitted by the prefix, the prefix must match the whole record or end at a `.` boundary of it.
+fn is_permitted_by(record_type_and_key: &[u8], prefix: &[u8]) -> bool {
+    if !record_type_and_key.starts_with(prefix) {
+        return false;
+    }
+
+    record_type_and_key.len() == prefix.len() || prefix.ends_with(b".") || record_type_and_key[prefix.len()] == b'.'
+}
+
+/// Verify the manager_permission records all belong to the new manager, so the permissions of the old manager can not be
+/// left behind when the manager is changed.
+pub fn verify_manager_permissions_belong_to_new_manager(
+    output_account_index: usize,
+    output_records: RecordsReader,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if the manager_permission records all belong to the new manager.");
+
+    let lock = high_level::load_cell_lock(output_account_index, Source::Output)?;
+    let manager = to_manager_permission_value(lock.as_reader().args().raw_data());
+
+    for record in output_records.iter() {
+        if record.record_type().raw_data() != MANAGER_PERMISSION_RECORD_TYPE {
+            continue;
+        }
+
+        das_assert!(
+            record.record_value().raw_data() == manager.as_slice(),
+            AccountCellErrorCode::AccountCellRecordNotPermitted,
+            "outputs[{}] The manager_permission records of the old manager should be removed or migrated to the new manager.",
+            output_account_index
+        );
+    }
+
+    Ok(())
+}
+
+/// Verify only the manager_permission records are changed, this is for the owner to edit the permissions of the manager.
+pub fn verify_only_manager_permissions_changed(
+    output_account_index: usize,
+    input_records: RecordsReader,
+    output_records: RecordsReader,
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Check if only the manager_permission records are changed.");
+
+    for record in diff_records(input_records, output_records) {
+        das_assert!(
+            record.record_type().raw_data() == MANAGER_PERMISSION_RECORD_TYPE,
+            AccountCellErrorCode::AccountCellProtectFieldIsModified,
+            "outputs[{}] Only the manager_permission records can be edited with the manager.",
+            output_account_index
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use ckb_std::ckb_constants::Source;
+use das_types::packed as das_packed;
+use das_types::prelude::{Builder, Entity, Reader};
+
+use crate::constants::*;
+use crate::error::*;
+use crate::util::{self, blake2b_256};
+use crate::{assert, code_to_error, data_parser, verifiers};
+
+/// The account registry is an AccountRegistryCell which keeps the SMT root of all registered account IDs, it is switched
+/// on by the account registry settings in ConfigCellMain only, no matter which cells are carried by the transaction:
+///
+/// - Off: only the linked list of AccountCells is used to keep accounts unique, the registry is not touched;
+/// - Migrating: existing accounts can be migrated into the registry, new accounts are registered with the linked list and
+///   inserted into the registry at the same time;
+/// - Enabled: new accounts are registered with a non-inclusion proof of the registry only, and the migration must have
+///   been marked as finished;
+///
+/// In both Migrating and Enabled modes, the accounts are removed from the registry when their PreAccountCells are
+/// refunded or their AccountCells are recycled. The PreAccountCells created before the migration started have never been
+/// inserted, so they are refunded without touching the registry.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+pub enum AccountRegistryMode {
+    Off = 0,
+    Migrating = 1,
+    Enabled = 2,
+}
+
+pub fn get_mode(config_main: das_packed::ConfigCellMainReader) -> Result<AccountRegistryMode, Box<dyn ScriptError>> {
+    let settings = match util::get_account_registry_settings(config_main)? {
+        Some(val) => val,
+        None => return Ok(AccountRegistryMode::Off),
+    };
+
+    match data_parser::account_registry_settings::get_mode(settings) {
+        0 => Ok(AccountRegistryMode::Off),
+        1 => Ok(AccountRegistryMode::Migrating),
+        2 => Ok(AccountRegistryMode::Enabled),
+        mode => {
+            warn!(
+                "The mode of the account registry in ConfigCellMain is invalid. (current: {})",
+                mode
+            );
+            Err(code_to_error!(ErrorCode::ConfigCellWitnessDecodingError))
+        }
+    }
+}
+
+/// Get the time when the migration started, it is u64::MAX when the registry has never been switched on.
+pub fn get_migration_started_at(config_main: das_packed::ConfigCellMainReader) -> Result<u64, Box<dyn ScriptError>> {
+    let started_at = util::get_account_registry_settings(config_main)?
+        .map(data_parser::account_registry_settings::get_migration_started_at)
+        .unwrap_or(u64::MAX);
+
+    Ok(started_at)
+}
+
+pub fn account_registry_cell_type(config_main: das_packed::ConfigCellMainReader) -> das_packed::Script {
+    das_packed::Script::new_builder()
+        .code_hash(config_main.type_id_table().pre_account_cell().to_entity())
+        .hash_type(das_packed::Byte::new(ScriptHashType::Type as u8))
+        .args(das_packed::Bytes::from(ACCOUNT_REGISTRY_CELL_ARGS.to_vec()))
+        .build()
+}
+
+pub fn find_account_registry_cells(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<(Vec<usize>, Vec<usize>), Box<dyn ScriptError>> {
+    let type_script = account_registry_cell_type(config_main);
+    util::find_cells_by_script_in_inputs_and_outputs(ScriptType::Type, type_script.as_reader().into())
+}
+
+/// Load the outputs_data of the AccountRegistryCell, it is structured as `[ root(32 bytes) | migration_finished(1 byte) ]`,
+/// the last byte is optional and 0x01 means the migration of existing accounts has been finished.
+pub fn load_registry(index: usize, source: Source) -> Result<([u8; 32], bool), Box<dyn ScriptError>> {
+    let data = util::load_cell_data(index, source)?;
+
+    assert!(
+        data.len() == 32 || (data.len() == 33 && data[32] <= 1),
+        ErrorCode::InvalidCellData,
+        "{:?}[{}] The outputs_data of AccountRegistryCell should be a 32 bytes SMT root with an optional migration flag.",
+        source,
+        index
+    );
+
+    let root = data[..32].try_into().unwrap();
+    let migration_finished = data.len() == 33 && data[32] == 1;
+
+    Ok((root, migration_finished))
+}
+
+pub fn load_root(index: usize, source: Source) -> Result<[u8; 32], Box<dyn ScriptError>> {
+    let (root, _) = load_registry(index, source)?;
+    Ok(root)
+}
+
+pub fn gen_smt_key(account_id: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    key[..ACCOUNT_ID_LENGTH].copy_from_slice(&account_id[..ACCOUNT_ID_LENGTH]);
+    key
+}
+
+/// The value of an account is the hash of the account with suffix, so anyone can recover the account from the registry.
+pub fn gen_smt_value(account: &[u8]) -> [u8; 32] {
+    blake2b_256(account)
+}
+
+/// Verify that the accounts are not in the SMT of prev_root and are in the SMT of current_root.
+///
+/// The accounts are pairs of account ID and account with suffix. The proof must be compiled with exactly these keys.
+pub fn verify_accounts_registered(
+    prev_root: [u8; 32],
+    current_root: [u8; 32],
+    accounts: &[(Vec<u8>, Vec<u8>)],
+    proof: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the accounts are not registered before and are registered now.");
+
+    let mut absent_leaves = Vec::new();
+    let mut present_leaves = Vec::new();
+    for (account_id, account) in accounts.iter() {
+        let key = gen_smt_key(account_id);
+        debug!(
+            "  The account {} is required to be inserted into the registry.(key: 0x{})",
+            alloc::string::String::from_utf8_lossy(account),
+            util::hex_string(&key)
+        );
+
+        absent_leaves.push((key, [0u8; 32]));
+        present_leaves.push((key, gen_smt_value(account)));
+    }
+
+    verifiers::common::verify_smt_multi_proof(&absent_leaves, prev_root, proof)?;
+    verifiers::common::verify_smt_multi_proof(&present_leaves, current_root, proof)?;
+
+    Ok(())
+}
+
+/// Verify that the accounts are in the SMT of prev_root and are not in the SMT of current_root.
+///
+/// The accounts are pairs of account ID and account with suffix. The proof must be compiled with exactly these keys.
+pub fn verify_accounts_unregistered(
+    prev_root: [u8; 32],
+    current_root: [u8; 32],
+    accounts: &[(Vec<u8>, Vec<u8>)],
+    proof: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the accounts are removed from the registry.");
+
+    let (absent_leaves, present_leaves) = gen_removal_leaves(accounts);
+
+    verifiers::common::verify_smt_multi_proof(&present_leaves, prev_root, proof)?;
+    verifiers::common::verify_smt_multi_proof(&absent_leaves, current_root, proof)?;
+
+    Ok(())
+}
+
+/// Verify that the accounts are not in the SMT of current_root, and they are either all in or all not in the SMT of
+/// prev_root, because the accounts registered before the registry is switched on may not have been migrated yet.
+///
+/// The accounts are pairs of account ID and account with suffix. The proof must be compiled with exactly these keys.
+pub fn verify_accounts_unregistered_if_migrated(
+    prev_root: [u8; 32],
+    current_root: [u8; 32],
+    accounts: &[(Vec<u8>, Vec<u8>)],
+    proof: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the accounts are removed from the registry if they have been migrated.");
+
+    let (absent_leaves, present_leaves) = gen_removal_leaves(accounts);
+
+    verifiers::common::verify_smt_multi_proof(&absent_leaves, current_root, proof)?;
+    if verifiers::common::verify_smt_multi_proof(&present_leaves, prev_root, proof).is_err() {
+        assert!(
+            prev_root == current_root,
+            ErrorCode::SMTProofVerifyFailed,
+            "The accounts are not in the registry, so the root should not be changed."
+        );
+    }
+
+    Ok(())
+}
+
+fn gen_removal_leaves(accounts: &[(Vec<u8>, Vec<u8>)]) -> (Vec<([u8; 32], [u8; 32])>, Vec<([u8; 32], [u8; 32])>) {
+    let mut absent_leaves = Vec::new();
+    let mut present_leaves = Vec::new();
+    for (account_id, account) in accounts.iter() {
+        let key = gen_smt_key(account_id);
+        debug!(
+            "  The account {} is required to be removed from the registry.(key: 0x{})",
+            alloc::string::String::from_utf8_lossy(account),
+            util::hex_string(&key)
+        );
+
+        absent_leaves.push((key, [0u8; 32]));
+        present_leaves.push((key, gen_smt_value(account)));
+    }
+
+    (absent_leaves, present_leaves)
+}
+
+/// Verify the AccountRegistryCell is carried by the transaction when the registry is switched on, so the accounts
+/// created or removed by the transaction are always synchronized with the registry.
+pub fn verify_registry_cell_required(
+    config_main: das_packed::ConfigCellMainReader,
+) -> Result<(Vec<usize>, Vec<usize>), Box<dyn ScriptError>> {
+    let (input_registry_cells, output_registry_cells) = find_account_registry_cells(config_main)?;
+    if get_mode(config_main)? == AccountRegistryMode::Off {
+        verifiers::common::verify_cell_number(
+            "AccountRegistryCell",
+            &input_registry_cells,
+            0,
+            &output_registry_cells,
+            0,
+        )?;
+    } else {
+        verifiers::common::verify_cell_number(
+            "AccountRegistryCell",
+            &input_registry_cells,
+            1,
+            &output_registry_cells,
+            1,
+        )?;
+    }
+
+    Ok((input_registry_cells, output_registry_cells))
+}
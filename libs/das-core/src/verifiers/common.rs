@@ -343,3 +343,24 @@ pub fn verify_smt_proof(
     }
     Ok(())
 }
+
+pub fn verify_smt_multi_proof(
+    leaves: &[([u8; 32], [u8; 32])],
+    root: [u8; 32],
+    proof: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    let mut builder = SMTBuilder::new();
+    for (key, val) in leaves.iter() {
+        builder = builder.insert(&H256::from(*key), &H256::from(*val)).unwrap();
+    }
+
+    let smt = builder.build().unwrap();
+    let ret = smt.verify(&H256::from(root), &proof);
+    if let Err(_e) = ret {
+        debug!("  verify_smt_multi_proof verification failed. Err: {:?}", _e);
+        return Err(code_to_error!(ErrorCode::SMTProofVerifyFailed));
+    } else {
+        debug!("  verify_smt_multi_proof verification passed.");
+    }
+    Ok(())
+}
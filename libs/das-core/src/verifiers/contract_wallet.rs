@@ -0,0 +1,161 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use das_dynamic_libs::sign_lib::SignLib;
+use das_types::constants::DasLockType;
+use das_types::packed as das_packed;
+
+use crate::constants::*;
+use crate::error::*;
+use crate::{assert as das_assert, code_to_error, data_parser, debug, sign_util, util, warn};
+
+/// Verify if the contract wallet has approved the digest of the transaction.
+///
+/// The wallet_address is the ETH address in the owner or manager part of das-lock args, so which role unlocked the cell is still
+/// decided by das-lock args like any other signatures. The attesters and the threshold are read from ConfigCellMain, so
+/// attestations by quorum are rejected until they are configured.
+pub fn verify_attestation(
+    sign_lib: &SignLib,
+    config_main: das_packed::ConfigCellMainReader,
+    input_index: usize,
+    wallet_address: &[u8],
+    digest: &[u8],
+    lock_bytes: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!(
+        "Inputs[{}] Verify if the contract wallet 0x{} has approved the digest 0x{} ...",
+        input_index,
+        util::hex_string(wallet_address),
+        util::hex_string(digest)
+    );
+
+    das_assert!(
+        sign_util::is_contract_wallet_lock(lock_bytes),
+        ErrorCode::ContractWalletAttestationError,
+        "Inputs[{}] The attestation of contract wallet is malformed.",
+        input_index
+    );
+
+    // The message which attesters sign or the storage proof proves, it binds the digest to the contract wallet.
+    let message = util::blake2b_256([wallet_address, digest].concat());
+    let payload = &lock_bytes[1..lock_bytes.len() - 1];
+    match lock_bytes[0] {
+        CONTRACT_WALLET_ATTESTATION_KIND_QUORUM => verify_quorum(sign_lib, config_main, input_index, &message, payload),
+        CONTRACT_WALLET_ATTESTATION_KIND_STORAGE_PROOF => verify_storage_proof(input_index, &message, payload),
+        _kind => {
+            warn!(
+                "Inputs[{}] The kind of contract wallet attestation is unknown.(kind: {})",
+                input_index, _kind
+            );
+            Err(code_to_error!(ErrorCode::ContractWalletAttestationError))
+        }
+    }
+}
+
+fn verify_quorum(
+    sign_lib: &SignLib,
+    config_main: das_packed::ConfigCellMainReader,
+    input_index: usize,
+    message: &[u8],
+    payload: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    let settings = match util::get_contract_wallet_settings(config_main)? {
+        Some(settings) => settings,
+        None => {
+            warn!(
+                "Inputs[{}] The attesters of contract wallet are not configured in ConfigCellMain.",
+                input_index
+            );
+            return Err(code_to_error!(ErrorCode::ContractWalletAttestationError));
+        }
+    };
+    let threshold = data_parser::contract_wallet_settings::get_threshold(settings);
+    let attesters: Vec<&[u8]> = data_parser::contract_wallet_settings::get_attesters(settings).collect();
+    let count = payload[0] as usize;
+
+    das_assert!(
+        threshold > 0 && threshold <= attesters.len(),
+        ErrorCode::ContractWalletAttestationError,
+        "Inputs[{}] The threshold of contract wallet attesters in ConfigCellMain is invalid.(threshold: {}, attesters: {})",
+        input_index,
+        threshold,
+        attesters.len()
+    );
+
+    das_assert!(
+        count >= threshold,
+        ErrorCode::ContractWalletAttestationError,
+        "Inputs[{}] The quorum attestation should be signed by at least {} of {} attesters.(current: {})",
+        input_index,
+        threshold,
+        attesters.len(),
+        count
+    );
+
+    let mut prev_attester_index: Option<usize> = None;
+    for signature_bytes in payload[1..].chunks(CONTRACT_WALLET_ATTESTER_SIGNATURE_SIZE) {
+        let attester_index = signature_bytes[0] as usize;
+
+        // The attester indexes must be in ascending order, so one attester can not be counted twice.
+        das_assert!(
+            attester_index < attesters.len() && prev_attester_index.map(|i| i < attester_index).unwrap_or(true),
+            ErrorCode::ContractWalletAttestationError,
+            "Inputs[{}] The attester index is invalid or duplicated.(attester_index: {})",
+            input_index,
+            attester_index
+        );
+        prev_attester_index = Some(attester_index);
+
+        // CAREFUL Signature verification has been skipped in development mode.
+        if cfg!(not(feature = "dev")) {
+            let ret = sign_lib.validate(
+                DasLockType::ETH,
+                0i32,
+                message.to_vec(),
+                signature_bytes[1..].to_vec(),
+                attesters[attester_index].to_vec(),
+            );
+            das_assert!(
+                ret.is_ok(),
+                ErrorCode::ContractWalletAttestationError,
+                "Inputs[{}] The signature of attester 0x{} is invalid.",
+                input_index,
+                util::hex_string(attesters[attester_index])
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_storage_proof(input_index: usize, message: &[u8], payload: &[u8]) -> Result<(), Box<dyn ScriptError>> {
+    das_assert!(
+        payload.len() == CONTRACT_WALLET_STORAGE_PROOF_SIZE,
+        ErrorCode::ContractWalletAttestationError,
+        "Inputs[{}] The length of storage proof is invalid.(expected: {}, current: {})",
+        input_index,
+        CONTRACT_WALLET_STORAGE_PROOF_SIZE,
+        payload.len()
+    );
+
+    // The storage proof need to be verified with the state root provided by the light client of Ethereum, before it is deployed,
+    // a stand-in proof which is the message itself is accepted on the networks for development and testing.
+    if cfg!(any(feature = "dev", feature = "local")) {
+        das_assert!(
+            payload == message,
+            ErrorCode::ContractWalletAttestationError,
+            "Inputs[{}] The stand-in storage proof is mismatched.(expected: 0x{}, current: 0x{})",
+            input_index,
+            util::hex_string(message),
+            util::hex_string(payload)
+        );
+
+        Ok(())
+    } else {
+        warn!(
+            "Inputs[{}] The storage proof of contract wallet is not supported on this network yet.",
+            input_index
+        );
+        Err(code_to_error!(ErrorCode::ContractWalletAttestationError))
+    }
+}
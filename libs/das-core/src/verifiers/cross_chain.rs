@@ -0,0 +1,180 @@
+use alloc::boxed::Box;
+use core::convert::TryInto;
+
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::prelude::*;
+use ckb_std::high_level;
+
+use crate::constants::*;
+use crate::error::*;
+use crate::util;
+use crate::witness_parser::cross_chain_proof::CrossChainProof;
+use crate::{assert, code_to_error, debug, warn};
+
+/// The hash functions used by the light client of the other chain.
+pub trait LightClient {
+    fn header_hash(raw_header: &[u8]) -> [u8; 32];
+    fn leaf_hash(raw_receipt: &[u8]) -> [u8; 32];
+    fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32];
+}
+
+// The leaves and the nodes of the receipts tree are hashed with different tags, so a 64 bytes receipt can not pass
+// as a node.
+pub const MERKLE_LEAF_TAG: u8 = 0;
+pub const MERKLE_NODE_TAG: u8 = 1;
+
+/// A light client which hash everything with blake2b, it is only a stand-in of the real ones for dev and unit tests.
+#[cfg(any(feature = "dev", feature = "local"))]
+pub struct StandInLightClient;
+
+#[cfg(any(feature = "dev", feature = "local"))]
+impl LightClient for StandInLightClient {
+    fn header_hash(raw_header: &[u8]) -> [u8; 32] {
+        util::blake2b_256(raw_header)
+    }
+
+    fn leaf_hash(raw_receipt: &[u8]) -> [u8; 32] {
+        util::blake2b_256([[MERKLE_LEAF_TAG].as_slice(), raw_receipt].concat())
+    }
+
+    fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        util::blake2b_256([[MERKLE_NODE_TAG].as_slice(), left.as_slice(), right.as_slice()].concat())
+    }
+}
+
+/// Load the latest checkpoint of the light client from cell_deps, it is the block_hash and number in the outputs_data.
+pub fn load_light_client_checkpoint() -> Result<([u8; 32], u64), Box<dyn ScriptError>> {
+    let type_script = match cross_chain_light_client_cell_type() {
+        Some(val) => val,
+        None => {
+            warn!("The light client of the other chain is not available on this network.");
+            return Err(code_to_error!(ErrorCode::CrossChainProofInvalid));
+        }
+    };
+
+    let cells = util::find_cells_by_script(ScriptType::Type, type_script.as_reader(), Source::CellDep)?;
+    assert!(
+        cells.len() == 1,
+        ErrorCode::CrossChainProofInvalid,
+        "There should be one LightClientCell in cell_deps, no more and no less, but {} found.",
+        cells.len()
+    );
+
+    let data = util::load_cell_data(cells[0], Source::CellDep)?;
+    assert!(
+        data.len() == 32 + 8,
+        ErrorCode::CrossChainProofInvalid,
+        "cell_deps[{}] The data of LightClientCell should be block_hash(32) + number(8).",
+        cells[0]
+    );
+
+    let block_hash: [u8; 32] = data[0..32].try_into().unwrap();
+    let number = u64::from_le_bytes(data[32..40].try_into().unwrap());
+
+    Ok((block_hash, number))
+}
+
+/// Verify the proof of the release event on the other chain, and make sure it is releasing the AccountCell in inputs to
+/// the lock in outputs.
+///
+/// The proof contains a segment of headers which must end at the checkpoint of the light client, and a merkle proof
+/// which proves that the receipt is included in the first header of the segment.
+pub fn verify_unlock_proof<T: LightClient>(
+    proof: &CrossChainProof,
+    input_account_index: usize,
+    account_id: &[u8],
+    output_lock_args: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!("Verify if the proof of the release event is valid.");
+
+    let (checkpoint_hash, checkpoint_number) = load_light_client_checkpoint()?;
+
+    assert!(
+        proof.headers.len() >= CROSS_CHAIN_MIN_CONFIRMATIONS,
+        ErrorCode::CrossChainProofInvalid,
+        "The release event should have at least {} confirmations.(current: {})",
+        CROSS_CHAIN_MIN_CONFIRMATIONS,
+        proof.headers.len()
+    );
+
+    let mut prev_hash = T::header_hash(&proof.headers[0].raw);
+    let mut prev_number = proof.headers[0].number;
+    for (i, header) in proof.headers.iter().enumerate().skip(1) {
+        assert!(
+            header.parent_hash == prev_hash && header.number == prev_number + 1,
+            ErrorCode::CrossChainProofInvalid,
+            "The headers[{}] is not the child of the headers[{}].",
+            i,
+            i - 1
+        );
+
+        prev_hash = T::header_hash(&header.raw);
+        prev_number = header.number;
+    }
+
+    assert!(
+        prev_hash == checkpoint_hash && prev_number == checkpoint_number,
+        ErrorCode::CrossChainProofInvalid,
+        "The last header should be the checkpoint of the light client.(expected: 0x{}({}), current: 0x{}({}))",
+        util::hex_string(&checkpoint_hash),
+        checkpoint_number,
+        util::hex_string(&prev_hash),
+        prev_number
+    );
+
+    let mut node = T::leaf_hash(&proof.receipt.raw);
+    let mut position = proof.receipt_index;
+    for sibling in proof.merkle_proof.iter() {
+        node = if position & 1 == 0 {
+            T::node_hash(&node, sibling)
+        } else {
+            T::node_hash(sibling, &node)
+        };
+        position >>= 1;
+    }
+
+    assert!(
+        position == 0 && node == proof.headers[0].receipts_root,
+        ErrorCode::CrossChainProofInvalid,
+        "The receipt is not included in the headers[0].(expected_root: 0x{}, current_root: 0x{})",
+        util::hex_string(&proof.headers[0].receipts_root),
+        util::hex_string(&node)
+    );
+
+    debug!("Verify if the release event is releasing the AccountCell in inputs.");
+
+    assert!(
+        proof.receipt.account_id == account_id,
+        ErrorCode::CrossChainProofInvalid,
+        "The receipt should release the account 0x{}, but 0x{} found.",
+        util::hex_string(account_id),
+        util::hex_string(&proof.receipt.account_id)
+    );
+
+    // CAREFUL! The out point changes every time the AccountCell is locked, so the release event of a previous lock can not
+    // be replayed. If the AccountCell has been touched by other transactions after locking, the keepers need to unlock it
+    // with multisig instead.
+    let out_point = high_level::load_input_out_point(input_account_index, Source::Input)?;
+    let tx_hash = out_point.as_reader().tx_hash().raw_data();
+    let index: u32 = out_point.index().unpack();
+    assert!(
+        proof.receipt.tx_hash == tx_hash && proof.receipt.index == index,
+        ErrorCode::CrossChainProofInvalid,
+        "inputs[{}] The receipt should release the out point 0x{}-{}, but 0x{}-{} found.",
+        input_account_index,
+        util::hex_string(tx_hash),
+        index,
+        util::hex_string(&proof.receipt.tx_hash),
+        proof.receipt.index
+    );
+
+    assert!(
+        proof.receipt.lock_args == output_lock_args,
+        ErrorCode::CrossChainProofInvalid,
+        "The lock.args of the AccountCell in outputs should be 0x{}, but 0x{} found.",
+        util::hex_string(&proof.receipt.lock_args),
+        util::hex_string(output_lock_args)
+    );
+
+    Ok(())
+}
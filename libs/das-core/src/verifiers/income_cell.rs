@@ -8,11 +8,95 @@ use das_map::util as map_util;
 use das_types::packed::*;
 use das_types::prelude::*;
 
-use crate::constants::ScriptType;
+use crate::constants::{ScriptHashType, ScriptType};
+use crate::data_parser::income_vesting_args;
 use crate::error::*;
 use crate::witness_parser::WitnessesParser;
 use crate::{assert, code_to_error, debug, util, warn};
 
+/// A vesting record is an income record whose belong_to takes the type ID of income-cell-type as code_hash, so it can never
+/// be unlocked as a lock. The schedule and the lock of the beneficiary are stored in its args.
+pub struct VestingRecord {
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub total: u64,
+    pub beneficiary: Script,
+}
+
+pub fn is_vesting_record(belong_to: ScriptReader, income_cell_type_id: HashReader) -> bool {
+    util::is_reader_eq(belong_to.code_hash(), income_cell_type_id)
+        && belong_to.hash_type().as_slice()[0] == ScriptHashType::Type as u8
+}
+
+pub fn parse_vesting_record(belong_to: ScriptReader) -> Result<VestingRecord, Box<dyn ScriptError>> {
+    let args = belong_to.args().raw_data();
+    let (start, cliff, duration, total, beneficiary) = match (
+        income_vesting_args::get_start_opt(args),
+        income_vesting_args::get_cliff_opt(args),
+        income_vesting_args::get_duration_opt(args),
+        income_vesting_args::get_total_opt(args),
+        income_vesting_args::get_beneficiary_opt(args),
+    ) {
+        (Some(start), Some(cliff), Some(duration), Some(total), Some(beneficiary)) => {
+            (start, cliff, duration, total, beneficiary)
+        }
+        _ => {
+            warn!("The args of the vesting record is invalid. (belong_to: {})", belong_to);
+            return Err(code_to_error!(ErrorCode::IncomeCellVestingRecordInvalid));
+        }
+    };
+    let beneficiary = Script::from_slice(beneficiary).map_err(|_| {
+        warn!(
+            "The beneficiary of the vesting record is not a valid Script. (belong_to: {})",
+            belong_to
+        );
+        ErrorCode::IncomeCellVestingRecordInvalid
+    })?;
+
+    assert!(
+        duration > 0 && cliff <= duration,
+        ErrorCode::IncomeCellVestingRecordInvalid,
+        "The schedule of the vesting record is invalid, the duration should not be 0 and the cliff should not be longer than the duration. (cliff: {}, duration: {})",
+        cliff,
+        duration
+    );
+
+    Ok(VestingRecord {
+        start,
+        cliff,
+        duration,
+        total,
+        beneficiary,
+    })
+}
+
+/// Calculate how much capacity of a vesting record can be transferred to its beneficiary at the timestamp.
+///
+/// The capacity released before is the part of the total which is no longer in the record, so only the difference between
+/// the vested capacity and the released capacity is transferable.
+pub fn calc_transferable_capacity(vesting: &VestingRecord, capacity: u64, timestamp: u64) -> u64 {
+    let vested = if timestamp < vesting.start.saturating_add(vesting.cliff) {
+        0
+    } else if timestamp >= vesting.start.saturating_add(vesting.duration) {
+        vesting.total
+    } else {
+        (vesting.total as u128 * (timestamp - vesting.start) as u128 / vesting.duration as u128) as u64
+    };
+    let released = vesting.total.saturating_sub(capacity);
+
+    debug!(
+        "  {{ beneficiary.args: {}, total: {}, vested: {}, released: {}, remain: {} }}",
+        vesting.beneficiary.args(),
+        vesting.total,
+        vested,
+        released,
+        capacity
+    );
+
+    core::cmp::min(vested.saturating_sub(released), capacity)
+}
+
 pub fn verify_newly_created(
     income_cell_witness_reader: IncomeCellDataReader,
     index: usize,
@@ -172,6 +256,34 @@ pub fn verify_income_cells(
         map_util::add(&mut output_records, key, value);
     }
 
+    debug!("  Verify if the vesting records in the IncomeCell in outputs keep their unvested capacity.");
+
+    let income_cell_type_id = config_main.type_id_table().income_cell();
+    for (key, current_capacity) in output_records.items.iter() {
+        let belong_to = Script::from_slice(key.as_slice()).unwrap();
+        if !is_vesting_record(belong_to.as_reader(), income_cell_type_id) {
+            continue;
+        }
+
+        let vesting = parse_vesting_record(belong_to.as_reader())?;
+        let expected_capacity = match exist_records_opt.as_ref().and_then(|records| records.get(key)) {
+            // The vesting record can only be released by consolidate_income, so it should be kept as it is.
+            Some(&exist_capacity) => exist_capacity,
+            // The newly created vesting record should carry the whole total of its schedule.
+            None => vesting.total,
+        };
+
+        assert!(
+            *current_capacity == expected_capacity,
+            ErrorCode::IncomeCellVestingRecordInvalid,
+            "outputs[{}] The capacity of the vesting record is incorrect. (belong_to: {}, expected: {}, current: {})",
+            output_income_cells[0],
+            belong_to,
+            expected_capacity,
+            current_capacity
+        );
+    }
+
     if let Some(exist_records) = exist_records_opt.as_ref() {
         debug!("  Verify if the records in the IncomeCell in inputs is reserved correctly in outputs");
 
@@ -1,6 +1,12 @@
 pub mod account_cell;
+pub mod account_registry;
 pub mod balance_cell;
 pub mod common;
+pub mod contract_wallet;
+pub mod cross_chain;
 pub mod income_cell;
 pub mod misc;
+pub mod record_value;
 pub mod sub_account_cell;
+pub mod udt;
+pub mod webauthn;
@@ -0,0 +1,502 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bech32::FromBase32;
+use das_types::packed::*;
+use das_types::prelude::Reader;
+use eip712::util::{
+    keccak256, to_btc_p2pkh_address, to_btc_p2sh_address, to_btc_segwit_address, to_doge_address, to_ed25519_address,
+    to_full_address, to_short_address, to_tron_address,
+};
+
+use crate::error::*;
+use crate::util;
+use crate::witness_parser::WitnessesParser;
+
+type ValueValidator = fn(&str) -> bool;
+
+/// The registry of the record value validators keyed by `type.key`, the records without a validator are not validated.
+fn get_value_validator(record_type_and_key: &[u8]) -> Option<ValueValidator> {
+    match record_type_and_key {
+        b"address.eth" | b"address.60" | b"address.etc" | b"address.61" | b"address.bsc" | b"address.9006"
+        | b"address.polygon" | b"address.966" | b"address.heco" | b"address.celo" | b"address.52752" => {
+            Some(is_evm_address)
+        }
+        b"address.btc" | b"address.0" => Some(is_btc_address),
+        b"address.doge" | b"address.3" => Some(is_doge_address),
+        b"address.trx" | b"address.195" => Some(is_tron_address),
+        b"address.sol" | b"address.501" => Some(is_ed25519_address),
+        b"address.ckb" | b"address.309" => Some(is_ckb_address),
+        b"dns.a" => Some(is_ipv4_address),
+        b"dns.aaaa" => Some(is_ipv6_address),
+        b"dns.cname" => Some(is_domain_name),
+        b"dweb.ipfs" => Some(is_ipfs_cid),
+        b"dweb.ipns" => Some(is_ipns_name),
+        _ => None,
+    }
+}
+
+/// Verify the values of the records which are listed in the record value validation keys of ConfigCellMain.
+///
+/// The records which already exist in the prev_records are skipped, so the records saved before the validation is enabled
+/// will not block the other edits.
+pub fn verify_records_values(
+    parser: &WitnessesParser,
+    prev_records: Option<RecordsReader>,
+    records: RecordsReader,
+) -> Result<(), Box<dyn ScriptError>> {
+    let config_main = parser.configs.main()?;
+    let validation_keys = match util::get_record_value_validation_keys(config_main)? {
+        Some(keys) => keys,
+        None => {
+            debug!("Skip checking records values, because there is no record key requires validation.");
+            return Ok(());
+        }
+    };
+
+    debug!("Check if records values are valid.");
+
+    for record in records.iter() {
+        if let Some(prev_records) = prev_records {
+            if prev_records.iter().any(|item| item.as_slice() == record.as_slice()) {
+                continue;
+            }
+        }
+
+        let record_type_and_key = [record.record_type().raw_data(), b".", record.record_key().raw_data()].concat();
+        if !validation_keys
+            .split(|byte| *byte == 0)
+            .any(|key| key == record_type_and_key.as_slice())
+        {
+            continue;
+        }
+
+        let validator = match get_value_validator(&record_type_and_key) {
+            Some(validator) => validator,
+            None => {
+                debug!(
+                    "Skip checking the value of record {}, because there is no validator for it.",
+                    String::from_utf8_lossy(&record_type_and_key)
+                );
+                continue;
+            }
+        };
+
+        let value = record.record_value().raw_data();
+        let is_valid = core::str::from_utf8(value).map(validator).unwrap_or(false);
+
+        das_assert!(
+            is_valid,
+            AccountCellErrorCode::AccountCellRecordValueInvalid,
+            "The value of record {} is invalid: {}",
+            String::from_utf8_lossy(&record_type_and_key),
+            String::from_utf8_lossy(value)
+        );
+    }
+
+    Ok(())
+}
+
+/// The EVM address should be 0x-prefixed 20 bytes hex, and it should match the EIP-55 checksum if it is in mixed case.
+fn is_evm_address(value: &str) -> bool {
+    let hex_part = match value.strip_prefix("0x") {
+        Some(hex_part) if hex_part.len() == 40 && hex_part.bytes().all(|c| c.is_ascii_hexdigit()) => hex_part,
+        _ => return false,
+    };
+
+    let has_lowercase = hex_part.bytes().any(|c| c.is_ascii_lowercase());
+    let has_uppercase = hex_part.bytes().any(|c| c.is_ascii_uppercase());
+    if !has_lowercase || !has_uppercase {
+        return true;
+    }
+
+    let hash = keccak256(hex_part.to_ascii_lowercase().as_bytes());
+    hex_part.bytes().enumerate().all(|(i, c)| {
+        let nibble = if i % 2 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0x0f
+        };
+        if c.is_ascii_digit() {
+            true
+        } else if nibble >= 8 {
+            c.is_ascii_uppercase()
+        } else {
+            c.is_ascii_lowercase()
+        }
+    })
+}
+
+fn decode_base58(value: &str) -> Option<Vec<u8>> {
+    bs58::decode(value).into_vec().ok()
+}
+
+/// Decode the base58check payload of 25 bytes, the version byte and the 20 bytes hash are returned.
+fn decode_base58check_hash160(value: &str) -> Option<(u8, Vec<u8>)> {
+    match decode_base58(value) {
+        Some(payload) if payload.len() == 25 => Some((payload[0], payload[1..21].to_vec())),
+        _ => None,
+    }
+}
+
+fn is_btc_address(value: &str) -> bool {
+    if value.starts_with("bc1") {
+        return is_btc_segwit_address(value);
+    }
+
+    // Encode the decoded hash again, so the version and checksum are both verified.
+    match decode_base58check_hash160(value) {
+        Some((0x00, hash)) => to_btc_p2pkh_address(hash) == value,
+        Some((0x05, hash)) => to_btc_p2sh_address(hash) == value,
+        _ => false,
+    }
+}
+
+fn is_btc_segwit_address(value: &str) -> bool {
+    let (hrp, data, _) = match bech32::decode(value) {
+        Ok(ret) => ret,
+        Err(_) => return false,
+    };
+    if hrp != "bc" || data.is_empty() {
+        return false;
+    }
+
+    let version = data[0].to_u8();
+    let program = match Vec::<u8>::from_base32(&data[1..]) {
+        Ok(program) => program,
+        Err(_) => return false,
+    };
+
+    // The length of witness program is limited by BIP141.
+    let is_valid_length = if version == 0 {
+        program.len() == 20 || program.len() == 32
+    } else {
+        (2..=40).contains(&program.len())
+    };
+
+    version <= 16
+        && is_valid_length
+        && to_btc_segwit_address(version, program)
+            .map(|address| address == value)
+            .unwrap_or(false)
+}
+
+fn is_doge_address(value: &str) -> bool {
+    match decode_base58check_hash160(value) {
+        Some((0x1e, hash)) => to_doge_address(hash) == value,
+        _ => false,
+    }
+}
+
+fn is_tron_address(value: &str) -> bool {
+    match decode_base58check_hash160(value) {
+        Some((0x41, hash)) => to_tron_address(hash) == value,
+        _ => false,
+    }
+}
+
+fn is_ed25519_address(value: &str) -> bool {
+    match decode_base58(value) {
+        Some(pubkey) if pubkey.len() == 32 => to_ed25519_address(pubkey) == value,
+        _ => false,
+    }
+}
+
+/// Only the full address and the short address of CKB are supported, the deprecated formats are treated as invalid.
+fn is_ckb_address(value: &str) -> bool {
+    let payload = match bech32::decode(value).map(|(_, data, _)| Vec::<u8>::from_base32(&data)) {
+        Ok(Ok(payload)) => payload,
+        _ => return false,
+    };
+
+    let address = match payload.first() {
+        Some(&0x00) if payload.len() >= 34 => to_full_address(
+            payload[1..33].to_vec(),
+            payload[33..34].to_vec(),
+            payload[34..].to_vec(),
+        ),
+        Some(&0x01) if payload.len() == 22 => to_short_address(payload[1..2].to_vec(), payload[2..].to_vec()),
+        _ => return false,
+    };
+
+    address.map(|address| address == value).unwrap_or(false)
+}
+
+fn is_ipv4_address(value: &str) -> bool {
+    let mut count = 0;
+    for part in value.split('.') {
+        count += 1;
+        if part.is_empty()
+            || part.len() > 3
+            || !part.bytes().all(|c| c.is_ascii_digit())
+            || (part.len() > 1 && part.starts_with('0'))
+            || part.parse::<u16>().map(|num| num > 255).unwrap_or(true)
+        {
+            return false;
+        }
+    }
+
+    count == 4
+}
+
+fn is_ipv6_address(value: &str) -> bool {
+    // Count the 16 bits groups, the embedded IPv4 address at the end is counted as two groups.
+    fn count_groups(part: &str) -> Option<usize> {
+        if part.is_empty() {
+            return Some(0);
+        }
+
+        let groups: Vec<&str> = part.split(':').collect();
+        let mut count = 0;
+        for (i, group) in groups.iter().enumerate() {
+            if i == groups.len() - 1 && group.contains('.') {
+                if !is_ipv4_address(group) {
+                    return None;
+                }
+                count += 2;
+            } else if !group.is_empty() && group.len() <= 4 && group.bytes().all(|c| c.is_ascii_hexdigit()) {
+                count += 1;
+            } else {
+                return None;
+            }
+        }
+
+        Some(count)
+    }
+
+    match value.find("::") {
+        Some(pos) => {
+            let (head, tail) = (&value[..pos], &value[pos + 2..]);
+            if head.contains('.') || tail.contains("::") {
+                return false;
+            }
+
+            match (count_groups(head), count_groups(tail)) {
+                (Some(head_count), Some(tail_count)) => head_count + tail_count < 8,
+                _ => false,
+            }
+        }
+        None => count_groups(value) == Some(8),
+    }
+}
+
+fn is_domain_name(value: &str) -> bool {
+    let name = value.strip_suffix('.').unwrap_or(value);
+
+    !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-')
+        })
+}
+
+fn decode_base32(value: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+    let mut output = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in value.bytes() {
+        let index = ALPHABET.iter().position(|item| *item == c)? as u32;
+        buffer = (buffer << 5) | index;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // The unpadded encoding should end with less than 5 bits and they should all be 0.
+    if bits >= 5 || buffer != 0 {
+        return None;
+    }
+
+    Some(output)
+}
+
+fn decode_base36(value: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    // The bytes are stored in little-endian during the calculation.
+    let mut output: Vec<u8> = Vec::new();
+    for c in value.bytes() {
+        let mut carry = ALPHABET.iter().position(|item| *item == c)? as u32;
+        for byte in output.iter_mut() {
+            carry += (*byte as u32) * 36;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            output.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let leading_zeros = value.bytes().take_while(|c| *c == b'0').count();
+    output.extend(core::iter::repeat(0).take(leading_zeros));
+    output.reverse();
+
+    Some(output)
+}
+
+fn read_varint(data: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in data.iter().enumerate().take(9) {
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &data[i + 1..]));
+        }
+    }
+
+    None
+}
+
+/// The multihash is structured as `varint(hash_code) | varint(digest_length) | digest`.
+fn is_multihash(data: &[u8]) -> bool {
+    match read_varint(data).and_then(|(_, rest)| read_varint(rest)) {
+        Some((digest_length, digest)) => digest.len() as u64 == digest_length,
+        None => false,
+    }
+}
+
+/// The CIDv1 is structured as `varint(version) | varint(codec) | multihash`.
+fn is_cid_v1(data: &[u8]) -> bool {
+    match read_varint(data) {
+        Some((1, rest)) => match read_varint(rest) {
+            Some((_, multihash)) => is_multihash(multihash),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn is_ipfs_cid(value: &str) -> bool {
+    if value.starts_with("Qm") {
+        // The CIDv0 is always a base58btc encoded sha2-256 multihash.
+        return match decode_base58(value) {
+            Some(multihash) => multihash.len() == 34 && multihash[0] == 0x12 && is_multihash(&multihash),
+            None => false,
+        };
+    }
+
+    // The CIDv1 is prefixed with the multibase code.
+    let data = if let Some(encoded) = value.strip_prefix('b') {
+        decode_base32(encoded)
+    } else if let Some(encoded) = value.strip_prefix('k') {
+        decode_base36(encoded)
+    } else if let Some(encoded) = value.strip_prefix('z') {
+        decode_base58(encoded)
+    } else {
+        None
+    };
+
+    match data {
+        Some(data) => is_cid_v1(&data),
+        None => false,
+    }
+}
+
+/// The IPNS name is either a CID of the key or a domain name with DNSLink.
+fn is_ipns_name(value: &str) -> bool {
+    is_ipfs_cid(value) || (value.contains('.') && is_domain_name(value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_value_evm_address() {
+        assert!(is_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+        assert!(is_evm_address("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"));
+        // Copy from https://eips.ethereum.org/EIPS/eip-55
+        assert!(is_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(is_evm_address("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+
+        assert!(!is_evm_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(!is_evm_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"));
+        assert!(!is_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea"));
+        assert!(!is_evm_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaeg"));
+    }
+
+    #[test]
+    fn test_record_value_btc_address() {
+        assert!(is_btc_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"));
+        assert!(is_btc_address("3CNHUhP3uyB9EUtRLsmvFUmvGdjGdkTxJw"));
+        assert!(is_btc_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+        assert!(is_btc_address(
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"
+        ));
+        assert!(is_btc_address(
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+        ));
+
+        // The checksum is broken.
+        assert!(!is_btc_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ"));
+        assert!(!is_btc_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"));
+        // The address of other chains.
+        assert!(!is_btc_address("DU1qTa77uRizv4JGR8Ydj6Yrs73GVT2pFR"));
+    }
+
+    #[test]
+    fn test_record_value_other_addresses() {
+        assert!(is_doge_address("DU1qTa77uRizv4JGR8Ydj6Yrs73GVT2pFR"));
+        assert!(!is_doge_address("TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL"));
+        assert!(is_tron_address("TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL"));
+        assert!(!is_tron_address("TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeM"));
+        assert!(is_ed25519_address("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"));
+        assert!(!is_ed25519_address("FVen3X669xLzsi6N2V91Doiyz"));
+        assert!(!is_ed25519_address("0Ven3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"));
+        assert!(is_ckb_address("ckt1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jq5t63cs"));
+        assert!(is_ckb_address(
+            "ckt1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqdnnw7qkdnnclfkg59uzn8umtfd2kwxceqgutnjd"
+        ));
+        assert!(!is_ckb_address("ckt1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jq5t63ct"));
+    }
+
+    #[test]
+    fn test_record_value_dns() {
+        assert!(is_ipv4_address("192.168.0.1"));
+        assert!(!is_ipv4_address("192.168.0"));
+        assert!(!is_ipv4_address("192.168.0.256"));
+        assert!(!is_ipv4_address("192.168.00.1"));
+
+        assert!(is_ipv6_address("2001:db8:85a3:0:0:8a2e:370:7334"));
+        assert!(is_ipv6_address("2001:db8::1"));
+        assert!(is_ipv6_address("::"));
+        assert!(is_ipv6_address("::ffff:192.168.0.1"));
+        assert!(!is_ipv6_address("2001:db8::1::2"));
+        assert!(!is_ipv6_address("2001:db8:85a3:0:0:8a2e:370"));
+        assert!(!is_ipv6_address("2001:db8:85a3:0:0:8a2e:370:73345"));
+
+        assert!(is_domain_name("example.com"));
+        assert!(is_domain_name("www.example.com."));
+        assert!(!is_domain_name("-example.com"));
+        assert!(!is_domain_name("example..com"));
+        assert!(!is_domain_name("exa_mple.com"));
+    }
+
+    #[test]
+    fn test_record_value_content_hash() {
+        assert!(is_ipfs_cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"));
+        assert!(is_ipfs_cid(
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        ));
+        assert!(!is_ipfs_cid(
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd"
+        ));
+        assert!(!is_ipfs_cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd"));
+
+        assert!(is_ipns_name(
+            "k51qzi5uqu5dlvj2baxnqndepeb86cbk3ng7n3i46uzyxzyqj2xjonzllnv0v8"
+        ));
+        assert!(is_ipns_name("docs.ipfs.tech"));
+        assert!(!is_ipns_name("xxxx"));
+    }
+}
@@ -1,4 +1,5 @@
 use alloc::boxed::Box;
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -10,6 +11,9 @@ use das_types::constants::*;
 use das_types::packed::*;
 use das_types::prelude::Entity;
 use das_types::prettier::Prettier;
+use eip712::semantic::{self, SemanticRecord};
+use eip712::sub_account::{sub_account_edit_to_typed_data, sub_account_mint_to_typed_data};
+use eip712::util::to_semantic_date;
 
 use crate::constants::*;
 use crate::error::*;
@@ -244,6 +248,7 @@ pub fn verify_initial_properties(
 
         let records_reader = sub_account_reader.records();
         verifiers::account_cell::verify_records_keys(parser, records_reader)?;
+        verifiers::record_value::verify_records_values(parser, None, records_reader)?;
     } else {
         warn!(
             "  witnesses[{:>2}] The witness.sub_account.records of {} should be empty or only one default record.",
@@ -305,7 +310,7 @@ pub fn verify_sub_account_mint_sign(
     let das_lock_type = match witness.sign_type {
         Some(val) => {
             assert!(
-                [DasLockType::CKBSingle, DasLockType::ETH, DasLockType::ETHTypedData, DasLockType::TRON, DasLockType::Doge].contains(&val),
+                [DasLockType::CKBSingle, DasLockType::ETH, DasLockType::ETHTypedData, DasLockType::TRON, DasLockType::MIXIN, DasLockType::Doge, DasLockType::BTC].contains(&val),
                 ErrorCode::InvalidTransactionStructure,
                 "  witnesses[{:>2}] Parsing das-lock(witness.sub_account.lock.args) algorithm failed (maybe not supported for now), but it is required in this transaction.",
                 witness.index
@@ -322,12 +327,33 @@ pub fn verify_sub_account_mint_sign(
         }
     };
 
-    let expired_at = witness.expired_at.to_le_bytes().to_vec();
-    let account_list_smt_root = witness.account_list_smt_root.clone();
-    let signature = witness.signature.clone();
-    let args = witness.sign_args.clone();
+    let ret = match get_typed_data_chain_id(das_lock_type, &witness.signature) {
+        Some(chain_id) => {
+            debug!(
+                "  witnesses[{:>2}] The SubAccountMintSignWitness.signature is signed as EIP712 typed data.",
+                witness.index
+            );
+
+            let typed_data =
+                sub_account_mint_to_typed_data(chain_id, &witness.account_list_smt_root, witness.expired_at);
+            let typed_data_hash = eip712::hash_data(&typed_data).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+            sign_lib.verify_typed_data_sig(
+                das_lock_type,
+                typed_data_hash,
+                witness.signature[..SECP_SIGNATURE_SIZE].to_vec(),
+                witness.sign_args.clone(),
+            )
+        }
+        None => {
+            let expired_at = witness.expired_at.to_le_bytes().to_vec();
+            let account_list_smt_root = witness.account_list_smt_root.clone();
+            let signature = witness.signature.clone();
+            let args = witness.sign_args.clone();
 
-    let ret = sign_lib.verify_sub_account_mint_sig(das_lock_type, expired_at, account_list_smt_root, signature, args);
+            sign_lib.verify_sub_account_mint_sig(das_lock_type, expired_at, account_list_smt_root, signature, args)
+        }
+    };
     match ret {
         Err(_error_code) if _error_code == DasDynamicLibError::UndefinedDasLockType as i32 => {
             warn!(
@@ -413,9 +439,16 @@ pub fn verify_sub_account_mint_sign_not_expired(
 }
 
 pub fn verify_sub_account_edit_sign(
+    parser: &WitnessesParser,
     witness: &SubAccountWitness,
     sign_lib: &SignLib,
 ) -> Result<(), Box<dyn ScriptError>> {
+    // The WebAuthn signature also proves the signer is a device of the owner, so only the final signature verification
+    // is skipped in development mode.
+    if witness.sign_type == Some(DasLockType::WebAuthn) {
+        return verify_sub_account_edit_webauthn_sign(parser, witness, sign_lib);
+    }
+
     if cfg!(feature = "dev") {
         // CAREFUL Proof verification has been skipped in development mode.
         debug!(
@@ -433,7 +466,7 @@ pub fn verify_sub_account_edit_sign(
     let das_lock_type = match witness.sign_type {
         Some(val) => {
             assert!(
-                [DasLockType::CKBSingle, DasLockType::ETH, DasLockType::ETHTypedData, DasLockType::TRON, DasLockType::Doge].contains(&val),
+                [DasLockType::CKBSingle, DasLockType::ETH, DasLockType::ETHTypedData, DasLockType::TRON, DasLockType::MIXIN, DasLockType::Doge, DasLockType::BTC, DasLockType::WebAuthn].contains(&val),
                 ErrorCode::InvalidTransactionStructure,
                 "  witnesses[{:>2}] Parsing das-lock(witness.sub_account.lock.args) algorithm failed (maybe not supported for now), but it is required in this transaction.",
                 witness.index
@@ -450,24 +483,58 @@ pub fn verify_sub_account_edit_sign(
         }
     };
 
-    let account_id = witness.sub_account.id().as_slice().to_vec();
-    let edit_key = witness.edit_key.as_slice();
-    let edit_value = witness.edit_value_bytes.as_slice();
-    let nonce = witness.sub_account.nonce().as_slice().to_vec();
-    let signature = witness.signature.as_slice();
-    let args = witness.sign_args.as_slice();
-    let sign_expired_at = witness.sign_expired_at.to_le_bytes().to_vec();
-
-    let ret = sign_lib.verify_sub_account_sig(
-        das_lock_type,
-        account_id,
-        edit_key.to_vec(),
-        edit_value.to_vec(),
-        nonce,
-        signature.to_vec(),
-        args.to_vec(),
-        sign_expired_at,
-    );
+    let ret = match get_typed_data_chain_id(das_lock_type, &witness.signature) {
+        Some(chain_id) => {
+            debug!(
+                "  witnesses[{:>2}] The witness.sub_account.signature is signed as EIP712 typed data.",
+                witness.index
+            );
+
+            let sub_account_reader = witness.sub_account.as_reader();
+            let account = util::get_sub_account_name_from_reader(sub_account_reader);
+            let edit_key =
+                String::from_utf8(witness.edit_key.clone()).map_err(|_| ErrorCode::EIP712SerializationError)?;
+            let edit_value = edit_value_to_semantic(&witness.edit_value)?;
+            let nonce = u64::from(sub_account_reader.nonce());
+
+            let typed_data = sub_account_edit_to_typed_data(
+                chain_id,
+                &account,
+                &edit_key,
+                &edit_value,
+                nonce,
+                witness.sign_expired_at,
+            );
+            let typed_data_hash = eip712::hash_data(&typed_data).map_err(|_| ErrorCode::EIP712SerializationError)?;
+
+            sign_lib.verify_typed_data_sig(
+                das_lock_type,
+                typed_data_hash,
+                witness.signature[..SECP_SIGNATURE_SIZE].to_vec(),
+                witness.sign_args.clone(),
+            )
+        }
+        None => {
+            let account_id = witness.sub_account.id().as_slice().to_vec();
+            let edit_key = witness.edit_key.as_slice();
+            let edit_value = witness.edit_value_bytes.as_slice();
+            let nonce = witness.sub_account.nonce().as_slice().to_vec();
+            let signature = witness.signature.as_slice();
+            let args = witness.sign_args.as_slice();
+            let sign_expired_at = witness.sign_expired_at.to_le_bytes().to_vec();
+
+            sign_lib.verify_sub_account_sig(
+                das_lock_type,
+                account_id,
+                edit_key.to_vec(),
+                edit_value.to_vec(),
+                nonce,
+                signature.to_vec(),
+                args.to_vec(),
+                sign_expired_at,
+            )
+        }
+    };
 
     match ret {
         Err(_error_code) if _error_code == DasDynamicLibError::UndefinedDasLockType as i32 => {
@@ -491,6 +558,76 @@ pub fn verify_sub_account_edit_sign(
     }
 }
 
+fn verify_sub_account_edit_webauthn_sign(
+    parser: &WitnessesParser,
+    witness: &SubAccountWitness,
+    sign_lib: &SignLib,
+) -> Result<(), Box<dyn ScriptError>> {
+    let data = [
+        witness.sub_account.id().as_slice(),
+        witness.edit_key.as_slice(),
+        witness.edit_value_bytes.as_slice(),
+        witness.sub_account.nonce().as_slice(),
+        &witness.sign_expired_at.to_le_bytes(),
+    ]
+    .concat();
+    let digest = sign_lib.gen_digest(DasLockType::WebAuthn, data).map_err(|_error_code| {
+        warn!(
+            "  witnesses[{:>2}] Generating the digest of witness.sub_account failed, the error_code returned by dynamic library is: {}",
+            witness.index, _error_code
+        );
+        code_to_error!(SubAccountCellErrorCode::SubAccountSigVerifyError)
+    })?;
+
+    verifiers::webauthn::verify_signature(
+        parser,
+        sign_lib,
+        witness.index,
+        &digest,
+        &witness.signature,
+        &witness.sign_args,
+    )
+}
+
+/// The signatures of ETHTypedData may be signed as EIP712 typed data, then the chain ID is appended to the signature.
+/// Otherwise the signature is signed with the digest from SignLib::gen_digest like other algorithms.
+fn get_typed_data_chain_id(das_lock_type: DasLockType, signature: &[u8]) -> Option<u64> {
+    if das_lock_type == DasLockType::ETHTypedData && signature.len() == SECP_SIGNATURE_SIZE + EIP712_CHAINID_SIZE {
+        let mut chain_id = [0u8; EIP712_CHAINID_SIZE];
+        chain_id.copy_from_slice(&signature[SECP_SIGNATURE_SIZE..]);
+        Some(u64::from_be_bytes(chain_id))
+    } else {
+        None
+    }
+}
+
+fn edit_value_to_semantic(edit_value: &SubAccountEditValue) -> Result<String, Box<dyn ScriptError>> {
+    let ret = match edit_value {
+        SubAccountEditValue::Owner(args) | SubAccountEditValue::Manager(args) => {
+            format!("0x{}", util::hex_string(args))
+        }
+        SubAccountEditValue::Records(records) => {
+            let mut semantic_records = Vec::new();
+            for record in records.as_reader().iter() {
+                semantic_records.push(SemanticRecord::from_raw(
+                    record.record_type().raw_data(),
+                    record.record_key().raw_data(),
+                    record.record_label().raw_data(),
+                    record.record_value().raw_data(),
+                ));
+            }
+            semantic::records_to_semantic(&semantic_records)
+        }
+        SubAccountEditValue::Channel(account_id, price) => {
+            format!("0x{},{}", util::hex_string(account_id), price)
+        }
+        SubAccountEditValue::ExpiredAt(expired_at) => to_semantic_date(*expired_at),
+        SubAccountEditValue::None | SubAccountEditValue::Proof => String::new(),
+    };
+
+    Ok(ret)
+}
+
 pub fn verify_sub_account_edit_sign_not_expired(
     witness: &SubAccountWitness,
     parent_expired_at: u64,
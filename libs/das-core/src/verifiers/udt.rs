@@ -0,0 +1,101 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::packed as ckb_packed;
+use ckb_std::high_level;
+
+use crate::constants::*;
+use crate::error::*;
+use crate::util::{self, find_cells_by_script};
+use crate::{assert, code_to_error, debug, warn};
+
+/// Load the amount of the UDT cell, it is the first 16 bytes of outputs_data in little-endian.
+pub fn load_udt_amount(index: usize, source: Source) -> Result<u128, Box<dyn ScriptError>> {
+    let data = util::load_cell_data(index, source)?;
+    match data.get(..16) {
+        Some(bytes) => Ok(u128::from_le_bytes(bytes.try_into().unwrap())),
+        None => {
+            warn!(
+                "{:?}[{}] The data of the UDT cell should be at least 16 bytes.",
+                source, index
+            );
+            Err(code_to_error!(ErrorCode::InvalidCellData))
+        }
+    }
+}
+
+pub fn load_udt_amounts(cells: &[usize], source: Source) -> Result<u128, Box<dyn ScriptError>> {
+    let mut total = 0u128;
+    for i in cells.iter() {
+        total += load_udt_amount(*i, source)?;
+    }
+
+    Ok(total)
+}
+
+/// Find the UDT cells with the given type script hash and lock script.
+pub fn find_udt_cells(
+    udt_type_hash: &[u8],
+    lock: ckb_packed::ScriptReader,
+    source: Source,
+) -> Result<Vec<usize>, Box<dyn ScriptError>> {
+    let mut cells = Vec::new();
+    for i in find_cells_by_script(ScriptType::Lock, lock, source)? {
+        if let Some(type_hash) = high_level::load_cell_type_hash(i, source)? {
+            if type_hash == udt_type_hash {
+                cells.push(i);
+            }
+        }
+    }
+
+    Ok(cells)
+}
+
+/// Verify the UDT is transferred from the payer to the receiver, return the paid amount and the capacity occupied by the
+/// newly created UDT cells.
+pub fn verify_udt_payment(
+    udt_type_hash: &[u8],
+    payer_lock: ckb_packed::ScriptReader,
+    receiver_lock: ckb_packed::ScriptReader,
+) -> Result<(u128, u64), Box<dyn ScriptError>> {
+    debug!("Check if the UDT is transferred from the payer to the receiver properly ...");
+
+    let input_payer_cells = find_udt_cells(udt_type_hash, payer_lock, Source::Input)?;
+    let output_payer_cells = find_udt_cells(udt_type_hash, payer_lock, Source::Output)?;
+    let input_receiver_cells = find_udt_cells(udt_type_hash, receiver_lock, Source::Input)?;
+    let output_receiver_cells = find_udt_cells(udt_type_hash, receiver_lock, Source::Output)?;
+
+    let input_payer_amount = load_udt_amounts(&input_payer_cells, Source::Input)?;
+    let output_payer_amount = load_udt_amounts(&output_payer_cells, Source::Output)?;
+    let input_receiver_amount = load_udt_amounts(&input_receiver_cells, Source::Input)?;
+    let output_receiver_amount = load_udt_amounts(&output_receiver_cells, Source::Output)?;
+
+    assert!(
+        input_payer_amount >= output_payer_amount && output_receiver_amount >= input_receiver_amount,
+        ErrorCode::StablecoinPaymentError,
+        "The UDT of the payer should decrease and the UDT of the receiver should increase.(payer: {} -> {}, receiver: {} -> {})",
+        input_payer_amount,
+        output_payer_amount,
+        input_receiver_amount,
+        output_receiver_amount
+    );
+
+    let paid = input_payer_amount - output_payer_amount;
+    let received = output_receiver_amount - input_receiver_amount;
+
+    assert!(
+        paid == received,
+        ErrorCode::StablecoinPaymentError,
+        "All the UDT paid by the payer should be received by the receiver.(paid: {}, received: {})",
+        paid,
+        received
+    );
+
+    let input_capacity = util::load_cells_capacity(&[input_payer_cells, input_receiver_cells].concat(), Source::Input)?;
+    let output_capacity =
+        util::load_cells_capacity(&[output_payer_cells, output_receiver_cells].concat(), Source::Output)?;
+
+    Ok((paid, output_capacity.saturating_sub(input_capacity)))
+}
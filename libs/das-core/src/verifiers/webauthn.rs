@@ -0,0 +1,310 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_types::prelude::*;
+use ckb_std::high_level::{self, QueryIter};
+use das_dynamic_libs::sign_lib::SignLib;
+use das_types::constants::{DasLockType, DataType};
+use das_types::packed::DeviceKeyListCellData;
+use das_types::prelude::Entity;
+use sha2::{Digest, Sha256};
+
+use crate::constants::*;
+use crate::error::*;
+use crate::witness_parser::WitnessesParser;
+use crate::{assert as das_assert, code_to_error, data_parser, debug, util, warn};
+
+// The binary format of the signature of DasLockType::WebAuthn is:
+//
+// signature(64) + pubkey(64) + authenticator_data_len(2, little-endian) + authenticator_data + client_data_json
+const AUTHENTICATOR_DATA_LEN_SIZE: usize = 2;
+// The pubkey_hash in das-lock args and DeviceKey is the first 10 bytes of the pubkey hashed by blake2b for 5 times.
+const PUBKEY_HASH_SIZE: usize = 10;
+const PUBKEY_HASH_ROUNDS: usize = 5;
+const BASE64URL_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+#[derive(Debug)]
+pub struct WebAuthnAssertion<'a> {
+    pub signature: &'a [u8],
+    pub pubkey: &'a [u8],
+    pub authenticator_data: &'a [u8],
+    pub client_data_json: &'a [u8],
+}
+
+impl<'a> WebAuthnAssertion<'a> {
+    pub fn from_slice(bytes: &'a [u8]) -> Option<Self> {
+        let pubkey_end = WEBAUTHN_SIGNATURE_SIZE + WEBAUTHN_PUBKEY_SIZE;
+        let header_size = pubkey_end + AUTHENTICATOR_DATA_LEN_SIZE;
+        let len_bytes = bytes.get(pubkey_end..header_size)?;
+        let authenticator_data_len = u16::from_le_bytes([len_bytes[0], len_bytes[1]]) as usize;
+        let authenticator_data = bytes.get(header_size..header_size + authenticator_data_len)?;
+
+        Some(WebAuthnAssertion {
+            signature: &bytes[..WEBAUTHN_SIGNATURE_SIZE],
+            pubkey: &bytes[WEBAUTHN_SIGNATURE_SIZE..pubkey_end],
+            authenticator_data,
+            client_data_json: &bytes[header_size + authenticator_data_len..],
+        })
+    }
+
+    /// The challenge in clientDataJSON should be the digest encoded in base64url without padding.
+    pub fn has_challenge(&self, digest: &[u8]) -> bool {
+        let expected = [b"\"challenge\":\"", base64url_encode(digest).as_bytes(), b"\""].concat();
+        self.client_data_json
+            .windows(expected.len())
+            .any(|window| window == expected.as_slice())
+    }
+
+    /// The authenticator signs sha256(authenticator_data + sha256(client_data_json)) with secp256r1.
+    pub fn signed_message(&self) -> [u8; 32] {
+        let client_data_hash = Sha256::digest(self.client_data_json);
+        let mut hasher = Sha256::new();
+        hasher.update(self.authenticator_data);
+        hasher.update(&client_data_hash);
+
+        let mut ret = [0u8; 32];
+        ret.copy_from_slice(&hasher.finalize());
+        ret
+    }
+}
+
+pub fn to_pubkey_hash(pubkey: &[u8]) -> [u8; PUBKEY_HASH_SIZE] {
+    let mut hash = util::blake2b_256(pubkey);
+    for _ in 1..PUBKEY_HASH_ROUNDS {
+        hash = util::blake2b_256(hash);
+    }
+
+    let mut ret = [0u8; PUBKEY_HASH_SIZE];
+    ret.copy_from_slice(&hash[..PUBKEY_HASH_SIZE]);
+    ret
+}
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut ret = String::with_capacity((bytes.len() * 4 + 2) / 3);
+    for chunk in bytes.chunks(3) {
+        let n =
+            (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..=chunk.len() {
+            ret.push(BASE64URL_CHARS[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    ret
+}
+
+/// Verify if the WebAuthn assertion in the signature is signed by a device of the das-lock args.
+///
+/// The sign_args is the owner or manager part of das-lock args, it is sub_alg_id(1) + pubkey_hash(10) + cid(10). The signer can
+/// be the device of sign_args itself, or any device in the DeviceKeyListCell of sign_args which should be put in cell_deps.
+pub fn verify_signature(
+    parser: &WitnessesParser,
+    sign_lib: &SignLib,
+    witness_index: usize,
+    digest: &[u8],
+    signature: &[u8],
+    sign_args: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    debug!(
+        "  witnesses[{:>2}] Verify if the WebAuthn signature is valid ...",
+        witness_index
+    );
+
+    let assertion = match WebAuthnAssertion::from_slice(signature) {
+        Some(val) => val,
+        None => {
+            warn!(
+                "  witnesses[{:>2}] The WebAuthn signature is not in the expected format.",
+                witness_index
+            );
+            return Err(code_to_error!(ErrorCode::WebAuthnSignatureError));
+        }
+    };
+
+    das_assert!(
+        assertion.has_challenge(digest),
+        ErrorCode::WebAuthnSignatureError,
+        "  witnesses[{:>2}] The challenge in clientDataJSON should be the digest 0x{}.",
+        witness_index,
+        util::hex_string(digest)
+    );
+
+    das_assert!(
+        sign_args.len() == 1 + PUBKEY_HASH_SIZE * 2,
+        ErrorCode::WebAuthnSignatureError,
+        "  witnesses[{:>2}] The args of WebAuthn should be {} bytes. (current: {})",
+        witness_index,
+        1 + PUBKEY_HASH_SIZE * 2,
+        sign_args.len()
+    );
+
+    let pubkey_hash = to_pubkey_hash(assertion.pubkey);
+    if sign_args[1..1 + PUBKEY_HASH_SIZE] != pubkey_hash {
+        verify_device_key_in_cell_deps(parser, witness_index, sign_args, &pubkey_hash)?;
+    }
+
+    // CAREFUL Signature verification has been skipped in development mode.
+    if cfg!(not(feature = "dev")) {
+        let ret = sign_lib.validate(
+            DasLockType::WebAuthn,
+            0i32,
+            assertion.signed_message().to_vec(),
+            [assertion.signature, assertion.pubkey].concat(),
+            sign_args.to_vec(),
+        );
+
+        das_assert!(
+            ret.is_ok(),
+            ErrorCode::WebAuthnSignatureError,
+            "  witnesses[{:>2}] The WebAuthn signature is invalid.",
+            witness_index
+        );
+    }
+
+    debug!("  witnesses[{:>2}] The WebAuthn signature is valid.", witness_index);
+
+    Ok(())
+}
+
+fn verify_device_key_in_cell_deps(
+    parser: &WitnessesParser,
+    witness_index: usize,
+    sign_args: &[u8],
+    pubkey_hash: &[u8],
+) -> Result<(), Box<dyn ScriptError>> {
+    let config_main = parser.configs.main()?;
+    let extra_type_id_table = util::get_extra_type_id_table(config_main)?;
+    let device_key_list_type_id = match data_parser::extra_type_id_table::get_device_key_list_cell_type(extra_type_id_table) {
+        Some(val) => val,
+        None => {
+            warn!(
+                "  witnesses[{:>2}] The DeviceKeyListCell is not configured in ConfigCellMain, so only the device in das-lock args can sign.",
+                witness_index
+            );
+            return Err(code_to_error!(ErrorCode::WebAuthnSignatureError));
+        }
+    };
+    let das_lock = das_lock();
+
+    for (i, cell) in QueryIter::new(high_level::load_cell, Source::CellDep).enumerate() {
+        let is_device_key_list = match cell.type_().to_opt() {
+            Some(script) => {
+                script.code_hash().as_slice() == device_key_list_type_id
+                    && script.hash_type().as_slice() == [ScriptHashType::Type as u8]
+            }
+            None => false,
+        };
+        let lock = cell.lock();
+        if !is_device_key_list || lock.code_hash().as_slice() != das_lock.code_hash().as_slice() {
+            continue;
+        }
+
+        let lock_args = lock.args().raw_data();
+        if lock_args.first() != Some(&(DasLockType::WebAuthn as u8))
+            || data_parser::das_lock_args::get_owner_lock_args_opt(&lock_args) != Some(sign_args)
+        {
+            continue;
+        }
+
+        let (_, _, entity) = parser.verify_and_get(DataType::DeviceKeyList, i, Source::CellDep)?;
+        let key_list = DeviceKeyListCellData::from_compatible_slice(&entity.raw_data()).map_err(|_| {
+            warn!("  cell_deps[{}] Decoding DeviceKeyListCellData failed", i);
+            ErrorCode::WitnessEntityDecodingError
+        })?;
+
+        let found = key_list
+            .keys()
+            .into_iter()
+            .any(|key| key.pubkey().raw_data().as_ref() == pubkey_hash);
+        das_assert!(
+            found,
+            ErrorCode::WebAuthnSignatureError,
+            "  witnesses[{:>2}] The pubkey_hash 0x{} is not in the DeviceKeyListCell of cell_deps[{}].",
+            witness_index,
+            util::hex_string(pubkey_hash),
+            i
+        );
+
+        return Ok(());
+    }
+
+    warn!(
+        "  witnesses[{:>2}] The signer is neither the device in das-lock args nor in any DeviceKeyListCell of cell_deps.",
+        witness_index
+    );
+
+    Err(code_to_error!(ErrorCode::WebAuthnSignatureError))
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    // Generated by a local software authenticator of secp256r1 with the digest as challenge.
+    const DIGEST: &str = "65ab40b7c89e8f6a42a563a0f6e6242db3a89719f41613b8e3ac5b323fca8cc9";
+    const SIGNATURE: &str = "be4894b9c8762985be3fd2ea25613f5d242273aa60b10d57741fbf68b6bce4a653b63fc73bd46151da7fcf569d15f0d476294f57874f3f81e61af33031d2c2fd";
+    const PUBKEY: &str = "4dbfb3cfd65ad8501e2e84accd50bcd0d3065da1167b8aaff4d420bff506c9053c9defc5d88fa20f6e04ea80ad11fb5f126019a102383d4dff153df50749be21";
+    const AUTHENTICATOR_DATA: &str = "49960de5880e8c687434170f6476605b8fe4aeb9a28632c7995cf3ba831d97630500000001";
+    const CLIENT_DATA_JSON: &str = r#"{"type":"webauthn.get","challenge":"ZatAt8iej2pCpWOg9uYkLbOolxn0FhO446xbMj_KjMk","origin":"http://localhost:8080","crossOrigin":false}"#;
+
+    fn gen_signature() -> Vec<u8> {
+        let authenticator_data = hex::decode(AUTHENTICATOR_DATA).unwrap();
+        [
+            hex::decode(SIGNATURE).unwrap(),
+            hex::decode(PUBKEY).unwrap(),
+            (authenticator_data.len() as u16).to_le_bytes().to_vec(),
+            authenticator_data,
+            CLIENT_DATA_JSON.as_bytes().to_vec(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_webauthn_base64url_encode() {
+        assert_eq!(base64url_encode(b""), "");
+        assert_eq!(base64url_encode(b"f"), "Zg");
+        assert_eq!(base64url_encode(b"fo"), "Zm8");
+        assert_eq!(base64url_encode(b"foo"), "Zm9v");
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), "-_8");
+        assert_eq!(
+            base64url_encode(&hex::decode(DIGEST).unwrap()),
+            "ZatAt8iej2pCpWOg9uYkLbOolxn0FhO446xbMj_KjMk"
+        );
+    }
+
+    #[test]
+    fn test_webauthn_parse_assertion() {
+        let signature = gen_signature();
+        assert_eq!(signature.len(), 301);
+
+        let assertion = WebAuthnAssertion::from_slice(&signature).unwrap();
+        assert_eq!(hex::encode(assertion.signature), SIGNATURE);
+        assert_eq!(hex::encode(assertion.pubkey), PUBKEY);
+        assert_eq!(hex::encode(assertion.authenticator_data), AUTHENTICATOR_DATA);
+        assert_eq!(assertion.client_data_json, CLIENT_DATA_JSON.as_bytes());
+
+        assert!(WebAuthnAssertion::from_slice(&signature[..WEBAUTHN_SIGNATURE_SIZE + WEBAUTHN_PUBKEY_SIZE]).is_none());
+        assert!(WebAuthnAssertion::from_slice(&signature[..140]).is_none());
+    }
+
+    #[test]
+    fn test_webauthn_challenge_and_signed_message() {
+        let signature = gen_signature();
+        let assertion = WebAuthnAssertion::from_slice(&signature).unwrap();
+
+        assert!(assertion.has_challenge(&hex::decode(DIGEST).unwrap()));
+        assert!(!assertion.has_challenge(&[0u8; 32]));
+        assert_eq!(
+            hex::encode(assertion.signed_message()),
+            "2445422a3b381c5618c39f3ccc60d278f7727fbaf8e4bfd56c7ac35c287c4ff0"
+        );
+    }
+
+    #[test]
+    fn test_webauthn_pubkey_hash() {
+        let pubkey = hex::decode(PUBKEY).unwrap();
+        assert_eq!(hex::encode(to_pubkey_hash(&pubkey)), "23de6d7505a79a759f6b");
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use super::super::constants::*;
+use super::super::error::*;
+use super::super::util;
+use super::lv_parser::*;
+
+// Binary format: lv(headers) + lv(receipt) + lv(receipt_index) + lv(merkle_proof)
+//
+// - headers: headers of the other chain, from the block containing the receipt to the checkpoint block, every header is
+//   parent_hash(32) + number(8) + receipts_root(32);
+// - receipt: account_id(20) + tx_hash(32) + index(4) + the das-lock args of the AccountCell after unlocking;
+// - receipt_index: the uint32 position of the receipt in the receipts tree;
+// - merkle_proof: the sibling hashes from the receipt to the receipts_root, every hash is 32 bytes, the leaves are
+//   hash(0x00 + receipt) and the nodes are hash(0x01 + left + right);
+
+#[derive(Debug)]
+pub struct CrossChainHeader {
+    pub parent_hash: [u8; 32],
+    pub number: u64,
+    pub receipts_root: [u8; 32],
+    pub raw: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct CrossChainReceipt {
+    pub account_id: [u8; ACCOUNT_ID_LENGTH],
+    pub tx_hash: [u8; 32],
+    pub index: u32,
+    pub lock_args: Vec<u8>,
+    pub raw: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct CrossChainProof {
+    pub headers: Vec<CrossChainHeader>,
+    pub receipt: CrossChainReceipt,
+    pub receipt_index: u32,
+    pub merkle_proof: Vec<[u8; 32]>,
+}
+
+impl CrossChainProof {
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Box<dyn ScriptError>> {
+        let (start, headers) = parse_field("headers", bytes, 0)?;
+        let (start, receipt) = parse_field("receipt", bytes, start)?;
+        let (start, receipt_index) = parse_field("receipt_index", bytes, start)?;
+        let (start, merkle_proof) = parse_field("merkle_proof", bytes, start)?;
+
+        assert!(
+            start == bytes.len(),
+            ErrorCode::CrossChainProofInvalid,
+            "  CrossChainProof has {} redundant bytes at the end.",
+            bytes.len() - start
+        );
+
+        assert!(
+            !headers.is_empty() && headers.len() % CROSS_CHAIN_HEADER_BYTES == 0,
+            ErrorCode::CrossChainProofInvalid,
+            "  CrossChainProof.headers should be a non-empty list of {} bytes headers.",
+            CROSS_CHAIN_HEADER_BYTES
+        );
+
+        let headers = headers
+            .chunks(CROSS_CHAIN_HEADER_BYTES)
+            .map(|raw| CrossChainHeader {
+                parent_hash: raw[0..32].try_into().unwrap(),
+                number: u64::from_le_bytes(raw[32..40].try_into().unwrap()),
+                receipts_root: raw[40..72].try_into().unwrap(),
+                raw: raw.to_vec(),
+            })
+            .collect();
+
+        let receipt = CrossChainReceipt::from_slice(receipt)?;
+
+        let receipt_index = u32::from_le_bytes(receipt_index.try_into().map_err(|_| {
+            warn!("  CrossChainProof.receipt_index should be 4 bytes.");
+            ErrorCode::CrossChainProofInvalid
+        })?);
+
+        assert!(
+            merkle_proof.len() % 32 == 0,
+            ErrorCode::CrossChainProofInvalid,
+            "  CrossChainProof.merkle_proof should be a list of 32 bytes hashes."
+        );
+
+        let merkle_proof = merkle_proof.chunks(32).map(|raw| raw.try_into().unwrap()).collect();
+
+        let ret = CrossChainProof {
+            headers,
+            receipt,
+            receipt_index,
+            merkle_proof,
+        };
+
+        debug!(
+            "  CrossChainProof: {{ headers: {}, receipt: {{ account_id: 0x{}, tx_hash: 0x{}, index: {}, lock_args: 0x{} }}, receipt_index: {}, merkle_proof: {} }}",
+            ret.headers.len(),
+            util::hex_string(&ret.receipt.account_id),
+            util::hex_string(&ret.receipt.tx_hash),
+            ret.receipt.index,
+            util::hex_string(&ret.receipt.lock_args),
+            ret.receipt_index,
+            ret.merkle_proof.len()
+        );
+
+        Ok(ret)
+    }
+}
+
+impl CrossChainReceipt {
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Box<dyn ScriptError>> {
+        let lock_args_start = ACCOUNT_ID_LENGTH + 32 + 4;
+
+        assert!(
+            bytes.len() > lock_args_start,
+            ErrorCode::CrossChainProofInvalid,
+            "  CrossChainProof.receipt should be longer than {} bytes.",
+            lock_args_start
+        );
+
+        Ok(CrossChainReceipt {
+            account_id: bytes[0..ACCOUNT_ID_LENGTH].try_into().unwrap(),
+            tx_hash: bytes[ACCOUNT_ID_LENGTH..(ACCOUNT_ID_LENGTH + 32)].try_into().unwrap(),
+            index: u32::from_le_bytes(bytes[(ACCOUNT_ID_LENGTH + 32)..lock_args_start].try_into().unwrap()),
+            lock_args: bytes[lock_args_start..].to_vec(),
+            raw: bytes.to_vec(),
+        })
+    }
+}
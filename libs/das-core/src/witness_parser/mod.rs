@@ -1,5 +1,6 @@
 mod lv_parser;
 
+pub mod cross_chain_proof;
 pub mod reverse_record;
 pub mod sub_account;
 pub mod witness_parser;
@@ -1,9 +1,10 @@
 use alloc::boxed::Box;
 use alloc::collections::btree_map::BTreeMap;
 use alloc::string::ToString;
+use core::cell::OnceCell;
 use core::convert::{TryFrom, TryInto};
 
-use ckb_std::ckb_constants::Source;
+use ckb_std::ckb_constants::{CellField, Source};
 use ckb_std::error::SysError;
 use ckb_std::syscalls;
 use das_types::constants::{DataType, WITNESS_HEADER, WITNESS_HEADER_BYTES, WITNESS_LENGTH_BYTES, WITNESS_TYPE_BYTES};
@@ -11,11 +12,20 @@ use das_types::packed::*;
 use das_types::prelude::*;
 
 use super::super::constants::*;
+use super::super::data_parser::config_cell;
 use super::super::error::*;
 use super::super::types::{Configs, LockScriptTypeIdTable};
 use super::super::util;
 use crate::util::load_data;
 
+#[derive(Debug)]
+struct WitnessEntity {
+    witness_index: usize,
+    data_type: DataType,
+    // The version, hash and entity of DataEntity, the Bytes is wrapped DataEntity.entity.
+    entity: OnceCell<(u32, Vec<u8>, Bytes)>,
+}
+
 #[derive(Debug)]
 pub struct WitnessesParser {
     pub witnesses: Vec<(usize, DataType)>,
@@ -24,10 +34,10 @@ pub struct WitnessesParser {
     pub params: Vec<Bytes>,
     pub lock_type_id_table: LockScriptTypeIdTable,
     pub config_cell_type_id: Hash,
-    // The Bytes is wrapped DataEntity.entity.
-    dep: Vec<(u32, u32, DataType, Vec<u8>, Bytes)>,
-    old: Vec<(u32, u32, DataType, Vec<u8>, Bytes)>,
-    new: Vec<(u32, u32, DataType, Vec<u8>, Bytes)>,
+    // The position in these Vecs is the index of the cell, so the witness of a cell can be found in constant time.
+    dep: Vec<Option<WitnessEntity>>,
+    old: Vec<Option<WitnessEntity>>,
+    new: Vec<Option<WitnessEntity>>,
 }
 
 impl WitnessesParser {
@@ -127,15 +137,16 @@ impl WitnessesParser {
 
                                     let data = util::load_cell_data(config_cells[0], Source::CellDep)?;
                                     assert!(
-                                        data.len() >= 32,
+                                        data.len() >= config_cell::HASH_LENGTH,
                                         ErrorCode::WitnessStructureError,
                                         "witnesses[{:>2}] The witness of {:?} should have at least 32 bytes.",
                                         i,
                                         data_type
                                     );
 
-                                    let mut expected_entity_hash = [0u8; 32];
-                                    expected_entity_hash.copy_from_slice(&data);
+                                    // Only the active entity takes effect, a pending update replaces it only after config-cell-type
+                                    // promotes it, so no TimeCell is required here.
+                                    let expected_entity_hash = config_cell::get_active_hash(&data);
 
                                     config_witnesses.insert(data_type as u32, (i, expected_entity_hash));
                                 }
@@ -256,6 +267,35 @@ impl WitnessesParser {
                     Bytes::from(vec![role].as_slice()),
                 ]
             }
+            b"pre_register" => {
+                let bytes = action_data.as_reader().params().raw_data();
+
+                // The first byte is the payment mode, the remaining bytes are an optional SMT proof of the AccountRegistryCell.
+                match bytes.split_first() {
+                    Some((mode, proof)) if !proof.is_empty() => {
+                        vec![Bytes::from(vec![*mode].as_slice()), Bytes::from(proof)]
+                    }
+                    Some((mode, _)) => vec![Bytes::from(vec![*mode].as_slice())],
+                    None => Vec::new(),
+                }
+            }
+            b"unlock_account_for_cross_chain" => {
+                let bytes = action_data.as_reader().params().raw_data();
+
+                // The first byte is the role, the remaining bytes are an optional proof of the release event on the other chain.
+                match bytes.split_first() {
+                    Some((role, proof)) if !proof.is_empty() => {
+                        vec![Bytes::from(vec![*role].as_slice()), Bytes::from(proof)]
+                    }
+                    _ => {
+                        if action_data.params().is_empty() {
+                            Vec::new()
+                        } else {
+                            vec![action_data.params()]
+                        }
+                    }
+                }
+            }
             _ => {
                 if action_data.params().is_empty() {
                     Vec::new()
@@ -344,54 +384,94 @@ impl WitnessesParser {
 
     pub fn parse_cell(&mut self) -> Result<(), Box<dyn ScriptError>> {
         debug!("Parsing witnesses of all other cells ...");
+        self.index_cells(false)
+    }
+
+    /// Only record which witness belongs to which cell, the entities are decoded and hashed when they are required by
+    /// `verify_and_get` and the other methods, so transactions which only read a few of their cells will be much cheaper.
+    pub fn parse_cell_lazily(&mut self) -> Result<(), Box<dyn ScriptError>> {
+        debug!("Indexing witnesses of all other cells ...");
+        self.index_cells(true)
+    }
+
+    fn index_cells(&mut self, lazy: bool) -> Result<(), Box<dyn ScriptError>> {
         // witness format 1: 'das'(3) + DATA_TYPE(4) + molecule
 
-        for (_i, witness) in self.witnesses.iter().enumerate() {
-            let (index, data_type) = witness.to_owned();
+        for i in 0..self.witnesses.len() {
+            let (index, data_type) = self.witnesses[i].to_owned();
             // Skip ActionData witness and ConfigCells' witnesses.
             if data_type == DataType::ActionData || Self::is_config_data_type(&data_type) {
                 continue;
             }
 
             let raw = util::load_das_witnesses(index)?;
-
             let data = Self::parse_data(raw.as_slice())?;
-            let mut _cell_index = 0;
-            if let Some(entity) = data.dep().to_opt() {
-                let entity_info = Self::parse_entity(entity, data_type)?;
-                _cell_index = entity_info.0;
-                self.dep.push(entity_info)
-            }
-            if let Some(entity) = data.old().to_opt() {
-                let entity_info = Self::parse_entity(entity, data_type)?;
-                _cell_index = entity_info.0;
-                self.old.push(entity_info)
+            let entities = [
+                (Source::CellDep, data.dep().to_opt()),
+                (Source::Input, data.old().to_opt()),
+                (Source::Output, data.new().to_opt()),
+            ];
+
+            for (source, data_entity) in entities {
+                if let Some(data_entity) = data_entity {
+                    let cell_index = u32::from(data_entity.index()) as usize;
+                    let entity = OnceCell::new();
+                    if !lazy {
+                        let _ = entity.set(Self::parse_entity(data_entity));
+                    }
+
+                    debug!(
+                        "  witnesses[{:>2}] {{ data_type: {:?}, source: {:?}, index: {} }}",
+                        index, data_type, source, cell_index
+                    );
+
+                    self.insert(
+                        source,
+                        cell_index,
+                        WitnessEntity {
+                            witness_index: index,
+                            data_type,
+                            entity,
+                        },
+                    )?;
+                }
             }
-            if let Some(entity) = data.new().to_opt() {
-                let entity_info = Self::parse_entity(entity, data_type)?;
-                _cell_index = entity_info.0;
-                self.new.push(entity_info)
+        }
+
+        Ok(())
+    }
+
+    fn insert(&mut self, source: Source, cell_index: usize, entity: WitnessEntity) -> Result<(), Box<dyn ScriptError>> {
+        let group = match source {
+            Source::Input => &mut self.old,
+            Source::Output => &mut self.new,
+            Source::CellDep => &mut self.dep,
+            _ => {
+                return Err(code_to_error!(ErrorCode::HardCodedError));
             }
+        };
 
-            #[cfg(all(debug_assertions))]
-            {
-                let mut source = None;
-                if let Some(_) = data.dep().to_opt() {
-                    source = Some(Source::CellDep);
-                }
-                if let Some(_) = data.old().to_opt() {
-                    source = Some(Source::Input);
-                }
-                if let Some(_) = data.new().to_opt() {
-                    source = Some(Source::Output);
+        if cell_index >= group.len() {
+            // Only grow the index for cells which really exist, so a witness with a huge index can not exhaust the memory.
+            let mut buf = [0u8; 8];
+            match syscalls::load_cell_by_field(&mut buf, 0, cell_index, source, CellField::Capacity) {
+                Ok(_) => group.resize_with(cell_index + 1, || None),
+                Err(SysError::IndexOutOfBound) => {
+                    debug!(
+                        "  witnesses[{:>2}] The {:?}[{}] does not exist, skip indexing it.",
+                        entity.witness_index, source, cell_index
+                    );
+                    return Ok(());
                 }
-                debug!(
-                    "  witnesses[{:>2}] {{ data_type: {:?}, source: {:?}, index: {} }}",
-                    index, data_type, source, _cell_index
-                );
+                Err(e) => return Err(Box::new(Error::<ErrorCode>::from(e))),
             }
         }
 
+        // The same as searching from the beginning, the first witness of a cell takes effect.
+        if group[cell_index].is_none() {
+            group[cell_index] = Some(entity);
+        }
+
         Ok(())
     }
 
@@ -435,30 +515,37 @@ impl WitnessesParser {
         }
     }
 
-    fn parse_entity(
-        data_entity: DataEntity,
-        data_type: DataType,
-    ) -> Result<(u32, u32, DataType, Vec<u8>, Bytes), Box<dyn ScriptError>> {
-        let index = u32::from(data_entity.index());
+    fn parse_entity(data_entity: DataEntity) -> (u32, Vec<u8>, Bytes) {
         let version = u32::from(data_entity.version());
         let entity = data_entity.entity();
 
         let unwrapped_entity = entity.as_reader().raw_data();
         let hash = util::blake2b_256(unwrapped_entity).to_vec();
 
-        // debug!(
-        //     "entity: index = {} hash = {:?} entity = {:?}",
-        //     index, hash, unwrapped_entity
-        // );
+        (version, hash, entity)
+    }
 
-        Ok((index, version, data_type, hash, entity))
+    fn load_entity(witness_index: usize, source: Source) -> Result<(u32, Vec<u8>, Bytes), Box<dyn ScriptError>> {
+        debug!(
+            "  witnesses[{:>2}] Decoding the entity of {:?} lazily ...",
+            witness_index, source
+        );
+
+        let raw = util::load_das_witnesses(witness_index)?;
+        let data = Self::parse_data(raw.as_slice())?;
+        let data_entity = match source {
+            Source::Input => data.old().to_opt(),
+            Source::Output => data.new().to_opt(),
+            Source::CellDep => data.dep().to_opt(),
+            _ => None,
+        };
+
+        data_entity
+            .map(Self::parse_entity)
+            .ok_or_else(|| code_to_error!(ErrorCode::WitnessDataDecodingError))
     }
 
-    fn get(
-        &self,
-        index: u32,
-        source: Source,
-    ) -> Result<Option<&(u32, u32, DataType, Vec<u8>, Bytes)>, Box<dyn ScriptError>> {
+    fn get(&self, index: u32, source: Source) -> Result<Option<(u32, DataType, &[u8], &Bytes)>, Box<dyn ScriptError>> {
         let group = match source {
             Source::Input => &self.old,
             Source::Output => &self.new,
@@ -468,7 +555,15 @@ impl WitnessesParser {
             }
         };
 
-        Ok(group.iter().find(|&(i, _, _, _, _)| *i == index))
+        let witness_entity = match group.get(index as usize) {
+            Some(Some(val)) => val,
+            _ => return Ok(None),
+        };
+        let (version, hash, entity) = witness_entity
+            .entity
+            .get_or_try_init(|| Self::load_entity(witness_entity.witness_index, source))?;
+
+        Ok(Some((*version, witness_entity.data_type, hash.as_slice(), entity)))
     }
 
     pub fn verify_hash(&self, index: usize, source: Source) -> Result<(), Box<dyn ScriptError>> {
@@ -481,7 +576,7 @@ impl WitnessesParser {
             }
         };
 
-        if let Some((_, _, _, _hash, _)) = self.get(index as u32, source)? {
+        if let Some((_, _, _hash, _)) = self.get(index as u32, source)? {
             assert!(
                 expected_hash == _hash,
                 ErrorCode::WitnessDataHashOrTypeMissMatch,
@@ -571,9 +666,9 @@ impl WitnessesParser {
     ) -> Result<(u32, DataType, &Bytes), Box<dyn ScriptError>> {
         let version;
         let entity;
-        if let Some((_, _version, _data_type, _hash, _entity)) = self.get(index as u32, source)? {
-            if expected_hash == _hash.as_slice() && &data_type == _data_type {
-                version = _version.to_owned();
+        if let Some((_version, _data_type, _hash, _entity)) = self.get(index as u32, source)? {
+            if expected_hash == _hash && data_type == _data_type {
+                version = _version;
                 entity = _entity;
             } else {
                 // This error means the there is no hash(witness.data.dep/old/new.entity) matches the leading 32 bytes of the cell.
@@ -584,7 +679,7 @@ impl WitnessesParser {
                     data_type,
                     _data_type,
                     util::hex_string(expected_hash),
-                    util::hex_string(_hash.as_slice()),
+                    util::hex_string(_hash),
                     util::hex_string(_entity.as_reader().raw_data())
                 );
                 return Err(code_to_error!(ErrorCode::WitnessDataHashOrTypeMissMatch));
@@ -1,9 +1,12 @@
 use core::fmt::Display;
 
+use das_types::constants::DasLockType;
 use das_types::packed::DasLockTypeIdTableReader;
 
 pub type DynLibSize = [u8; 128 * 1024];
 
+pub const DYN_LIB_COUNT: usize = 8;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum DynLibName {
     CKBSignhash,
@@ -12,21 +15,85 @@ pub enum DynLibName {
     ETH,
     TRON,
     DOGE,
+    WebAuthn,
+    BTC,
 }
 
-impl DynLibName {
-    pub fn get_code_hash<'a>(&self, type_id_table_reader: DasLockTypeIdTableReader<'a>) -> &'a [u8] {
-        match &self {
-            &DynLibName::CKBSignhash => type_id_table_reader.ckb_signhash().raw_data(),
-            &DynLibName::CKBMultisig => type_id_table_reader.ckb_multisig().raw_data(),
-            &DynLibName::ED25519 => type_id_table_reader.ed25519().raw_data(),
-            &DynLibName::ETH => type_id_table_reader.eth().raw_data(),
-            &DynLibName::TRON => type_id_table_reader.tron().raw_data(),
-            &DynLibName::DOGE => type_id_table_reader.doge().raw_data(),
+/// The table which provides the type IDs of the dynamic libraries, None means the library is not deployed.
+pub trait DynLibTypeIdTable<'a> {
+    fn get_type_id(&self, name: DynLibName) -> Option<&'a [u8]>;
+}
+
+impl<'a> DynLibTypeIdTable<'a> for DasLockTypeIdTableReader<'a> {
+    fn get_type_id(&self, name: DynLibName) -> Option<&'a [u8]> {
+        match name {
+            DynLibName::CKBSignhash => Some(self.ckb_signhash().raw_data()),
+            DynLibName::CKBMultisig => Some(self.ckb_multisig().raw_data()),
+            DynLibName::ED25519 => Some(self.ed25519().raw_data()),
+            DynLibName::ETH => Some(self.eth().raw_data()),
+            DynLibName::TRON => Some(self.tron().raw_data()),
+            DynLibName::DOGE => Some(self.doge().raw_data()),
+            // The DasLockTypeIdTable has no slot for these libraries, see ExtendedDynLibTypeIdTable.
+            DynLibName::WebAuthn | DynLibName::BTC => None,
         }
     }
 }
 
+/// The DasLockTypeIdTable extended with the type IDs of the libraries which have no slot in it, they are configured separately
+/// in ConfigCellMain.
+#[derive(Clone, Copy)]
+pub struct ExtendedDynLibTypeIdTable<'a> {
+    pub das_lock_type_id_table: DasLockTypeIdTableReader<'a>,
+    pub webauthn: Option<&'a [u8]>,
+    pub btc: Option<&'a [u8]>,
+}
+
+impl<'a> DynLibTypeIdTable<'a> for ExtendedDynLibTypeIdTable<'a> {
+    fn get_type_id(&self, name: DynLibName) -> Option<&'a [u8]> {
+        match name {
+            DynLibName::WebAuthn => self.webauthn,
+            DynLibName::BTC => self.btc,
+            _ => self.das_lock_type_id_table.get_type_id(name),
+        }
+    }
+}
+
+/// The dynamic library which verifies the signatures of each DasLockType, the types not listed here are not supported.
+const DAS_LOCK_TYPE_TO_DYN_LIB: [(DasLockType, DynLibName); 9] = [
+    (DasLockType::CKBMulti, DynLibName::CKBMultisig),
+    (DasLockType::CKBSingle, DynLibName::CKBSignhash),
+    (DasLockType::ETH, DynLibName::ETH),
+    (DasLockType::TRON, DynLibName::TRON),
+    (DasLockType::ETHTypedData, DynLibName::ETH),
+    // The args of MIXIN is an Ed25519 public key, so it is verified by the Ed25519 library.
+    (DasLockType::MIXIN, DynLibName::ED25519),
+    (DasLockType::Doge, DynLibName::DOGE),
+    (DasLockType::WebAuthn, DynLibName::WebAuthn),
+    (DasLockType::BTC, DynLibName::BTC),
+];
+
+impl DynLibName {
+    pub fn from_das_lock_type(das_lock_type: DasLockType) -> Option<Self> {
+        DAS_LOCK_TYPE_TO_DYN_LIB
+            .iter()
+            .find(|(lock_type, _)| *lock_type as u8 == das_lock_type as u8)
+            .map(|(_, name)| *name)
+    }
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// The libraries of CKB only export the `validate` method, the others export both `validate` and `validate_str`.
+    pub fn has_validate_str(&self) -> bool {
+        !matches!(self, DynLibName::CKBSignhash | DynLibName::CKBMultisig)
+    }
+
+    pub fn get_code_hash<'a>(&self, type_id_table: impl DynLibTypeIdTable<'a>) -> Option<&'a [u8]> {
+        type_id_table.get_type_id(*self)
+    }
+}
+
 impl Display for DynLibName {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self)
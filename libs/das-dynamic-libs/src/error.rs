@@ -2,4 +2,6 @@
 #[repr(i32)]
 pub enum Error {
     UndefinedDasLockType = -1,
+    DynLibNotLoaded = -2,
+    UndefinedMethod = -3,
 }
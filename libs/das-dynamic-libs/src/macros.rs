@@ -26,7 +26,7 @@ macro_rules! log_loading {
         $crate::debug_log!(
             "Loading {} dynamic library with type ID 0x{} ...",
             $name,
-            $crate::util::hex_string($name.get_code_hash($type_id_table))
+            $crate::util::hex_string($name.get_code_hash($type_id_table).unwrap_or_default())
         );
     };
 }
@@ -36,7 +36,9 @@ macro_rules! load_lib {
     ($context:expr, $name:expr, $type_id_table:expr) => {
         $context
             .load_by(
-                $name.get_code_hash($type_id_table),
+                $name
+                    .get_code_hash($type_id_table)
+                    .expect("The type ID of the shared lib should be configured."),
                 ckb_std::ckb_types::core::ScriptHashType::Type,
             )
             .expect("The shared lib should be loaded successfully.")
@@ -46,15 +48,15 @@ macro_rules! load_lib {
 #[macro_export]
 macro_rules! load_2_methods {
     ($lib:expr) => {
-        Some($crate::sign_lib::SignLibWith2Methods {
-            c_validate: unsafe {
+        Some($crate::sign_lib::SignLibMethods {
+            c_validate: Some(unsafe {
                 $lib.get(b"validate")
                     .expect("Load function 'validate' from library failed.")
-            },
-            c_validate_str: unsafe {
+            }),
+            c_validate_str: Some(unsafe {
                 $lib.get(b"validate_str")
                     .expect("Load function 'validate_str' from library failed.")
-            },
+            }),
         })
     };
 }
@@ -62,11 +64,23 @@ macro_rules! load_2_methods {
 #[macro_export]
 macro_rules! load_1_method {
     ($lib:expr) => {
-        Some($crate::sign_lib::SignLibWith1Methods {
-            c_validate: unsafe {
+        Some($crate::sign_lib::SignLibMethods {
+            c_validate: Some(unsafe {
                 $lib.get(b"validate")
                     .expect("Load function 'validate' from library failed.")
-            },
+            }),
+            c_validate_str: None,
         })
     };
 }
+
+#[macro_export]
+macro_rules! load_methods {
+    ($lib:expr, $name:expr) => {
+        if $name.has_validate_str() {
+            $crate::load_2_methods!($lib)
+        } else {
+            $crate::load_1_method!($lib)
+        }
+    };
+}
@@ -3,6 +3,7 @@ use alloc::vec::Vec;
 use ckb_std::dynamic_loading_c_impl::Symbol;
 use das_types::constants::DasLockType;
 
+use super::constants::{DynLibName, DYN_LIB_COUNT};
 use super::error::Error;
 use super::util;
 
@@ -17,36 +18,36 @@ type ValidateStrFunction = unsafe extern "C" fn(
     lock_args: *const u8,
 ) -> i32;
 
-pub struct SignLibWith2Methods {
-    pub c_validate: Symbol<ValidateFunction>,
-    pub c_validate_str: Symbol<ValidateStrFunction>,
-}
-
-pub struct SignLibWith1Methods {
-    pub c_validate: Symbol<ValidateFunction>,
+/// The methods exported by a dynamic library, the methods which the library does not export are None.
+pub struct SignLibMethods {
+    pub c_validate: Option<Symbol<ValidateFunction>>,
+    pub c_validate_str: Option<Symbol<ValidateStrFunction>>,
 }
 
 pub struct SignLib {
-    pub ckb_signhash: Option<SignLibWith1Methods>,
-    pub ckb_multisig: Option<SignLibWith1Methods>,
-    pub ed25519: Option<SignLibWith2Methods>,
-    pub eth: Option<SignLibWith2Methods>,
-    pub tron: Option<SignLibWith2Methods>,
-    pub doge: Option<SignLibWith2Methods>,
+    // The methods of each dynamic library, indexed by DynLibName.
+    libs: [Option<SignLibMethods>; DYN_LIB_COUNT],
 }
 
 impl SignLib {
     pub fn new() -> Self {
         SignLib {
-            ckb_signhash: None,
-            ckb_multisig: None,
-            ed25519: None,
-            eth: None,
-            tron: None,
-            doge: None,
+            libs: Default::default(),
         }
     }
 
+    /// Set the methods loaded from the dynamic library, all the DasLockTypes mapped to the library will be verified by them.
+    pub fn set_methods(&mut self, name: DynLibName, methods: Option<SignLibMethods>) {
+        self.libs[name.index()] = methods;
+    }
+
+    /// Get the methods of the dynamic library which verifies the signatures of the DasLockType.
+    fn get_methods(&self, das_lock_type: DasLockType) -> Result<&SignLibMethods, i32> {
+        let name = DynLibName::from_das_lock_type(das_lock_type).ok_or(Error::UndefinedDasLockType as i32)?;
+
+        self.libs[name.index()].as_ref().ok_or(Error::DynLibNotLoaded as i32)
+    }
+
     /// Validate signatures
     ///
     /// costs: about 2_000_000 cycles
@@ -66,31 +67,11 @@ impl SignLib {
             util::hex_string(&lock_args)
         );
 
-        let func;
-
-        match das_lock_type {
-            DasLockType::CKBSingle => {
-                let lib = self.ckb_signhash.as_ref().unwrap();
-                func = &lib.c_validate;
-            }
-            DasLockType::CKBMulti => {
-                let lib = self.ckb_multisig.as_ref().unwrap();
-                func = &lib.c_validate;
-            }
-            DasLockType::ETH | DasLockType::ETHTypedData => {
-                let lib = self.eth.as_ref().unwrap();
-                func = &lib.c_validate;
-            }
-            DasLockType::TRON => {
-                let lib = self.tron.as_ref().unwrap();
-                func = &lib.c_validate;
-            }
-            DasLockType::Doge => {
-                let lib = self.doge.as_ref().unwrap();
-                func = &lib.c_validate;
-            }
-            _ => return Err(Error::UndefinedDasLockType as i32),
-        }
+        let func = self
+            .get_methods(das_lock_type)?
+            .c_validate
+            .as_ref()
+            .ok_or(Error::UndefinedMethod as i32)?;
 
         let error_code: i32 = unsafe { func(type_no, digest.as_ptr(), lock_bytes.as_ptr(), lock_args.as_ptr()) };
 
@@ -121,26 +102,11 @@ impl SignLib {
             util::hex_string(&lock_args)
         );
 
-        let func;
-        match das_lock_type {
-            // DasLockType::CKBSingle => {
-            //     let lib = self.ckb_signhash.as_ref().unwrap();
-            //     func = &lib.c_validate_str;
-            // }
-            DasLockType::ETH | DasLockType::ETHTypedData => {
-                let lib = self.eth.as_ref().unwrap();
-                func = &lib.c_validate_str;
-            }
-            DasLockType::TRON => {
-                let lib = self.tron.as_ref().unwrap();
-                func = &lib.c_validate_str;
-            }
-            DasLockType::Doge => {
-                let lib = self.doge.as_ref().unwrap();
-                func = &lib.c_validate_str;
-            }
-            _ => return Err(Error::UndefinedDasLockType as i32),
-        }
+        let func = self
+            .get_methods(das_lock_type)?
+            .c_validate_str
+            .as_ref()
+            .ok_or(Error::UndefinedMethod as i32)?;
 
         let error_code: i32 = unsafe {
             func(
@@ -212,19 +178,43 @@ impl SignLib {
         }
     }
 
+    pub fn verify_typed_data_sig(
+        &self,
+        das_lock_type: DasLockType,
+        typed_data_hash: Vec<u8>,
+        sig: Vec<u8>,
+        args: Vec<u8>,
+    ) -> Result<(), i32> {
+        if cfg!(feature = "dev") {
+            return Ok(());
+        }
+
+        // The hash of EIP712 typed data is signed directly, so it can not be passed to validate_str which will hash it again.
+        let type_no = 0i32;
+        self.validate(das_lock_type, type_no, typed_data_hash, sig, args)
+    }
+
     pub fn gen_digest(&self, das_lock_type: DasLockType, data: Vec<u8>) -> Result<Vec<u8>, i32> {
         let mut blake2b = util::new_blake2b();
         blake2b.update(&data);
         let mut h = [0u8; 32];
         blake2b.finalize(&mut h);
 
-        match das_lock_type {
-            // DasLockType::ETHTypedData => {
-            //     let prefix = "from did: ".as_bytes();
-            //     Ok([prefix, &h].concat())
-            // }
-            DasLockType::ETH | DasLockType::ETHTypedData | DasLockType::TRON | DasLockType::Doge => Ok(h.to_vec()),
+        // The digest is only used by validate_str, so the types whose library has no validate_str are not supported.
+        match DynLibName::from_das_lock_type(das_lock_type) {
+            Some(DynLibName::BTC) => Ok(to_btc_signed_message(&h)),
+            Some(name) if name.has_validate_str() => Ok(h.to_vec()),
             _ => Err(Error::UndefinedDasLockType as i32),
         }
     }
 }
+
+const BTC_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+/// Bitcoin wallets only sign human-readable messages with the prefix, so the digest is signed in hex and the whole prefixed
+/// message is passed to the BTC library which will hash it with double SHA-256.
+fn to_btc_signed_message(digest: &[u8]) -> Vec<u8> {
+    let message = util::hex_string(digest);
+    // The length is encoded as a varint, it is always 1 byte here because the message is shorter than 0xfd bytes.
+    [BTC_SIGNED_MESSAGE_PREFIX, &[message.len() as u8], message.as_bytes()].concat()
+}
@@ -0,0 +1,562 @@
+use alloc::format;
+use std::prelude::v1::*;
+
+use crate::eip712::{TypedDataV4, Value, DOMAIN_NAME, DOMAIN_VERIFYING_CONTRACT, DOMAIN_VERSION_V1, DOMAIN_VERSION_V2};
+use crate::semantic::{bytes_to_semantic, SemanticRecord};
+use crate::util::*;
+
+const PARAM_OMIT_SIZE: usize = 10;
+const DATA_OMIT_SIZE: usize = 20;
+
+/// The action and params of a transaction, the params are in raw bytes.
+#[derive(Debug, Clone, Default)]
+pub struct TypedAction {
+    pub action: String,
+    pub params: Vec<Vec<u8>>,
+}
+
+/// A script in raw bytes, the `name` is the readable name of the code_hash if it is a well-known script.
+#[derive(Debug, Clone, Default)]
+pub struct TypedScript {
+    pub name: Option<String>,
+    pub code_hash: Vec<u8>,
+    pub hash_type: u8,
+    pub args: Vec<u8>,
+}
+
+impl TypedScript {
+    /// Render the script as `code_hash,0x{hash_type},0x{args}`, the code_hash and args are truncated if they are too long.
+    pub fn to_semantic(&self) -> String {
+        let code_hash = match &self.name {
+            Some(name) => name.clone(),
+            None => format!("0x{}", to_omitted_hex(&self.code_hash)),
+        };
+
+        format!(
+            "{},0x{},0x{}",
+            code_hash,
+            hex::encode([self.hash_type]),
+            to_omitted_hex(&self.args)
+        )
+    }
+}
+
+/// The address of a lock, the payloads are the owner or manager part of the lock args.
+#[derive(Debug, Clone)]
+pub enum TypedAddress {
+    /// The secp256k1_blake160_signhash_all lock of CKB, rendered as a short address.
+    Ckb(Vec<u8>),
+    Eth(Vec<u8>),
+    Tron(Vec<u8>),
+    Doge(Vec<u8>),
+    BtcP2PKH(Vec<u8>),
+    BtcP2WPKH(Vec<u8>),
+    Ed25519(Vec<u8>),
+    /// Any other lock is rendered as a full address of CKB.
+    Full {
+        code_hash: Vec<u8>,
+        hash_type: u8,
+        args: Vec<u8>,
+    },
+}
+
+impl TypedAddress {
+    pub fn to_semantic(&self) -> Result<String, bech32::Error> {
+        let address = match self {
+            TypedAddress::Ckb(pubkey_hash) => to_short_address(vec![0], pubkey_hash.clone())?,
+            TypedAddress::Eth(pubkey_hash) => format!("0x{}", hex::encode(pubkey_hash)),
+            TypedAddress::Tron(pubkey_hash) => to_tron_address(pubkey_hash),
+            TypedAddress::Doge(pubkey_hash) => to_doge_address(pubkey_hash),
+            TypedAddress::BtcP2PKH(pubkey_hash) => to_btc_p2pkh_address(pubkey_hash),
+            TypedAddress::BtcP2WPKH(pubkey_hash) => to_btc_p2wpkh_address(pubkey_hash)?,
+            TypedAddress::Ed25519(pubkey) => to_ed25519_address(pubkey),
+            TypedAddress::Full {
+                code_hash,
+                hash_type,
+                args,
+            } => to_full_address(code_hash.clone(), vec![*hash_type], args.clone())?,
+        };
+
+        Ok(address)
+    }
+}
+
+/// The data of a cell, the AccountCell is rendered with the key fields of its data and witness.
+#[derive(Debug, Clone)]
+pub enum TypedCellData {
+    Raw(Vec<u8>),
+    AccountCell {
+        account: Vec<u8>,
+        expired_at: u64,
+        status: u8,
+        records_hash: Vec<u8>,
+    },
+}
+
+impl Default for TypedCellData {
+    fn default() -> Self {
+        TypedCellData::Raw(Vec::new())
+    }
+}
+
+impl TypedCellData {
+    /// Render the data as the `data` and `extraData` fields of a cell.
+    pub fn to_semantic(&self) -> (String, String) {
+        match self {
+            TypedCellData::Raw(data) => {
+                let data = if data.is_empty() {
+                    String::new()
+                } else {
+                    format!("0x{}", to_omitted_hex(data))
+                };
+                (data, String::new())
+            }
+            TypedCellData::AccountCell {
+                account,
+                expired_at,
+                status,
+                records_hash,
+            } => (
+                format!(
+                    "{{ account: {}, expired_at: {} }}",
+                    bytes_to_semantic(account),
+                    expired_at
+                ),
+                format!(
+                    "{{ status: {}, records_hash: 0x{} }}",
+                    status,
+                    hex::encode(records_hash)
+                ),
+            ),
+        }
+    }
+}
+
+/// A cell which will be displayed in the typed data, it is rendered to readable strings by the builder.
+#[derive(Debug, Clone, Default)]
+pub struct TypedCell {
+    pub capacity: u64,
+    /// Only used since v2, it is the address of the lock, a lock which can not be converted to address is left empty.
+    pub owner: Option<TypedAddress>,
+    pub lock: TypedScript,
+    pub type_: Option<TypedScript>,
+    pub data: TypedCellData,
+}
+
+impl TypedCell {
+    fn owner_to_semantic(&self) -> String {
+        self.owner
+            .as_ref()
+            .and_then(|owner| owner.to_semantic().ok())
+            .unwrap_or_default()
+    }
+
+    fn type_to_semantic(&self) -> String {
+        self.type_.as_ref().map(|type_| type_.to_semantic()).unwrap_or_default()
+    }
+}
+
+fn to_omitted_hex(bytes: &[u8]) -> String {
+    if bytes.len() > DATA_OMIT_SIZE {
+        format!("{}...", hex::encode(&bytes[..DATA_OMIT_SIZE]))
+    } else {
+        hex::encode(bytes)
+    }
+}
+
+/// All the information of a transaction which is needed by the typed data.
+///
+/// The contracts fill it from the transaction on chain and the off-chain services can fill it from a mock transaction, then
+/// both of them get the same typed data from `to_typed_data_v1` or `to_typed_data_v2`.
+#[derive(Debug, Clone, Default)]
+pub struct TypedTransaction {
+    pub chain_id: u64,
+    pub das_message: String,
+    pub action: TypedAction,
+    /// Only used since v2, it is the changes of records like the result of `semantic::records_diff`.
+    pub records: Vec<(String, SemanticRecord)>,
+    /// The total capacity of all inputs, including the cells which are not listed in inputs.
+    pub inputs_capacity: u64,
+    /// The total capacity of all outputs, including the cells which are not listed in outputs.
+    pub outputs_capacity: u64,
+    pub inputs: Vec<TypedCell>,
+    pub outputs: Vec<TypedCell>,
+}
+
+impl TypedTransaction {
+    pub fn to_typed_data_v1(&self) -> TypedDataV4 {
+        let mut params = Vec::new();
+        for param in self.action.params.iter() {
+            if param.len() > PARAM_OMIT_SIZE {
+                params.push(format!("0x{}...", hex::encode(&param[..PARAM_OMIT_SIZE])));
+            } else {
+                params.push(format!("0x{}", hex::encode(param)));
+            }
+        }
+        let action = typed_data_v4!(@object {
+            action: self.action.action.as_str(),
+            params: params.join(",")
+        });
+
+        let to_cells_v1 = |cells: &[TypedCell]| {
+            let items = cells
+                .iter()
+                .map(|cell| {
+                    let (data, extra_data) = cell.data.to_semantic();
+                    typed_data_v4!(@object {
+                        capacity: to_semantic_capacity(cell.capacity),
+                        lock: cell.lock.to_semantic(),
+                        type: cell.type_to_semantic(),
+                        data: data,
+                        extraData: extra_data
+                    })
+                })
+                .collect();
+            Value::Array(items)
+        };
+        let inputs = to_cells_v1(&self.inputs);
+        let outputs = to_cells_v1(&self.outputs);
+
+        let fee = if self.outputs_capacity <= self.inputs_capacity {
+            to_semantic_capacity(self.inputs_capacity - self.outputs_capacity)
+        } else {
+            format!(
+                "-{}",
+                to_semantic_capacity(self.outputs_capacity - self.inputs_capacity)
+            )
+        };
+
+        typed_data_v4!({
+            types: {
+                EIP712Domain: {
+                    chainId: "uint256",
+                    name: "string",
+                    verifyingContract: "address",
+                    version: "string"
+                },
+                Action: {
+                    action: "string",
+                    params: "string"
+                },
+                Cell: {
+                    capacity: "string",
+                    lock: "string",
+                    type: "string",
+                    data: "string",
+                    extraData: "string"
+                },
+                Transaction: {
+                    DAS_MESSAGE: "string",
+                    inputsCapacity: "string",
+                    outputsCapacity: "string",
+                    fee: "string",
+                    action: "Action",
+                    inputs: "Cell[]",
+                    outputs: "Cell[]",
+                    digest: "bytes32"
+                }
+            },
+            primaryType: "Transaction",
+            domain: {
+                chainId: self.chain_id.to_string(),
+                name: DOMAIN_NAME,
+                verifyingContract: DOMAIN_VERIFYING_CONTRACT,
+                version: DOMAIN_VERSION_V1
+            },
+            message: {
+                DAS_MESSAGE: self.das_message.as_str(),
+                inputsCapacity: to_semantic_capacity(self.inputs_capacity),
+                outputsCapacity: to_semantic_capacity(self.outputs_capacity),
+                fee: fee,
+                action: action,
+                inputs: inputs,
+                outputs: outputs,
+                digest: ""
+            }
+        })
+    }
+
+    /// Generate the v2 typed data, the main differences from v1 are:
+    ///
+    /// - capacities and fee are `uint256` in shannon instead of human readable strings;
+    /// - each cell has an `owner` field which is the address of its lock;
+    /// - the params of action are `bytes[]` instead of a joined string;
+    /// - the changes of records in edit_records transaction are listed in the `records` field as `AccountRecord[]`.
+    pub fn to_typed_data_v2(&self) -> TypedDataV4 {
+        let action = typed_data_v4!(@values {
+            action: Value::String(self.action.action.clone()),
+            params: Value::Array(
+                self.action
+                    .params
+                    .iter()
+                    .map(|param| Value::Bytes(format!("0x{}", hex::encode(param))))
+                    .collect()
+            )
+        });
+
+        let records = self
+            .records
+            .iter()
+            .map(|(operation, record)| {
+                typed_data_v4!(@object {
+                    action: operation.as_str(),
+                    type: record.type_.as_str(),
+                    key: record.key.as_str(),
+                    label: record.label.as_str(),
+                    value: record.value.as_str()
+                })
+            })
+            .collect();
+
+        let to_cells_v2 = |cells: &[TypedCell]| {
+            let items = cells
+                .iter()
+                .map(|cell| {
+                    let (data, extra_data) = cell.data.to_semantic();
+                    typed_data_v4!(@values {
+                        capacity: Value::Uint256(cell.capacity.to_string()),
+                        owner: Value::String(cell.owner_to_semantic()),
+                        lock: Value::String(cell.lock.to_semantic()),
+                        type: Value::String(cell.type_to_semantic()),
+                        data: Value::String(data),
+                        extraData: Value::String(extra_data)
+                    })
+                })
+                .collect();
+            Value::Array(items)
+        };
+
+        let fee = self.inputs_capacity.saturating_sub(self.outputs_capacity);
+
+        TypedDataV4 {
+            types: typed_data_v4!(@types_1 {
+                EIP712Domain: {
+                    chainId: "uint256",
+                    name: "string",
+                    verifyingContract: "address",
+                    version: "string"
+                },
+                Action: {
+                    action: "string",
+                    params: "bytes[]"
+                },
+                AccountRecord: {
+                    action: "string",
+                    type: "string",
+                    key: "string",
+                    label: "string",
+                    value: "string"
+                },
+                Cell: {
+                    capacity: "uint256",
+                    owner: "string",
+                    lock: "string",
+                    type: "string",
+                    data: "string",
+                    extraData: "string"
+                },
+                Transaction: {
+                    DAS_MESSAGE: "string",
+                    inputsCapacity: "uint256",
+                    outputsCapacity: "uint256",
+                    fee: "uint256",
+                    action: "Action",
+                    records: "AccountRecord[]",
+                    inputs: "Cell[]",
+                    outputs: "Cell[]",
+                    digest: "bytes32"
+                }
+            }),
+            primary_type: Value::String(String::from("Transaction")),
+            domain: typed_data_v4!(@domain {
+                chainId: self.chain_id.to_string(),
+                name: DOMAIN_NAME,
+                verifyingContract: DOMAIN_VERIFYING_CONTRACT,
+                version: DOMAIN_VERSION_V2
+            }),
+            message: typed_data_v4!(@values {
+                DAS_MESSAGE: Value::String(self.das_message.clone()),
+                inputsCapacity: Value::Uint256(self.inputs_capacity.to_string()),
+                outputsCapacity: Value::Uint256(self.outputs_capacity.to_string()),
+                fee: Value::Uint256(fee.to_string()),
+                action: action,
+                records: Value::Array(records),
+                inputs: to_cells_v2(&self.inputs),
+                outputs: to_cells_v2(&self.outputs),
+                digest: Value::Byte32(String::new())
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hex;
+
+    use super::*;
+    use crate::hash_data;
+
+    fn gen_account_cell(capacity: u64, records_hash: &str) -> TypedCell {
+        let owner = hex::decode("15a33588908cf8edb27d1abe3852bf287abd3891").unwrap();
+        let args = [vec![5], owner.clone(), vec![5], owner.clone()].concat();
+
+        TypedCell {
+            capacity,
+            owner: Some(TypedAddress::Eth(owner)),
+            lock: TypedScript {
+                name: Some(String::from("das-lock")),
+                code_hash: vec![0; 32],
+                hash_type: 1,
+                args,
+            },
+            type_: Some(TypedScript {
+                name: Some(String::from("account-cell-type")),
+                code_hash: vec![0; 32],
+                hash_type: 1,
+                args: vec![],
+            }),
+            data: TypedCellData::AccountCell {
+                account: b"tangzhihong005.bit".to_vec(),
+                expired_at: 1662629612,
+                status: 0,
+                records_hash: hex::decode(records_hash).unwrap(),
+            },
+        }
+    }
+
+    fn gen_edit_records_tx() -> TypedTransaction {
+        TypedTransaction {
+            chain_id: 5,
+            das_message: String::from("Edit records of account tangzhihong005.bit ."),
+            action: TypedAction {
+                action: String::from("edit_records"),
+                params: vec![vec![1]],
+            },
+            records: vec![(
+                String::from("CHANGE"),
+                SemanticRecord::new("profile", "twitter", "", "das"),
+            )],
+            inputs_capacity: 22_500_000_000,
+            outputs_capacity: 22_499_990_000,
+            inputs: vec![gen_account_cell(
+                22_500_000_000,
+                "55478d76900611eb079b22088081124ed6c8bae21a05dd1a0d197efcc7c114ce",
+            )],
+            outputs: vec![gen_account_cell(
+                22_499_990_000,
+                "75e9c7a4725177c157b31d8a39f73e40ad328be5244a2a2fb6e478a24612c51a",
+            )],
+        }
+    }
+
+    #[test]
+    fn test_eip712_builder_render_cells() {
+        let cell = gen_account_cell(
+            22_500_000_000,
+            "55478d76900611eb079b22088081124ed6c8bae21a05dd1a0d197efcc7c114ce",
+        );
+
+        assert_eq!(
+            cell.lock.to_semantic(),
+            "das-lock,0x01,0x0515a33588908cf8edb27d1abe3852bf287abd38..."
+        );
+        assert_eq!(cell.type_to_semantic(), "account-cell-type,0x01,0x");
+        assert_eq!(cell.owner_to_semantic(), "0x15a33588908cf8edb27d1abe3852bf287abd3891");
+        assert_eq!(
+            cell.data.to_semantic(),
+            (
+                String::from("{ account: tangzhihong005.bit, expired_at: 1662629612 }"),
+                String::from(
+                    "{ status: 0, records_hash: 0x55478d76900611eb079b22088081124ed6c8bae21a05dd1a0d197efcc7c114ce }"
+                )
+            )
+        );
+
+        let unknown_script = TypedScript {
+            name: None,
+            code_hash: vec![9; 32],
+            hash_type: 1,
+            args: vec![10; 21],
+        };
+        assert_eq!(
+            unknown_script.to_semantic(),
+            "0x0909090909090909090909090909090909090909...,0x01,0x0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a..."
+        );
+        assert_eq!(TypedCellData::Raw(vec![]).to_semantic(), (String::new(), String::new()));
+        assert_eq!(
+            TypedCellData::Raw(vec![1, 2]).to_semantic(),
+            (String::from("0x0102"), String::new())
+        );
+    }
+
+    #[test]
+    fn test_eip712_builder_v1() {
+        let mut typed_data = gen_edit_records_tx().to_typed_data_v1();
+        typed_data.digest(String::from(
+            "01bee5c80a6bd74440f0f96c983b1107f1a419e028bef7b33e77e8f968cbfae7",
+        ));
+
+        // The same hash as the typed data built by the typed_data_v4! macro directly.
+        let expected = "e2d3286d053a3422c90ca48cb5bfcdb774d114283b5c98034fa407e57e317cd2";
+        let data = hash_data(&typed_data).unwrap();
+
+        assert_eq!(hex::encode(data).as_str(), expected);
+    }
+
+    #[test]
+    fn test_eip712_builder_omit_long_params() {
+        let mut tx = gen_edit_records_tx();
+        tx.action.params = vec![vec![1], vec![2; 12]];
+
+        let typed_data = tx.to_typed_data_v1();
+        let params = match typed_data.message {
+            Value::Object((_, message)) => match message.get("action") {
+                Some(Value::Object((_, action))) => match action.get("params") {
+                    Some(Value::String(params)) => params.clone(),
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            },
+            _ => String::new(),
+        };
+
+        assert_eq!(params, "0x01,0x02020202020202020202...");
+    }
+
+    #[test]
+    fn test_eip712_builder_v2() {
+        let tx = gen_edit_records_tx();
+        let mut typed_data = tx.to_typed_data_v2();
+        typed_data.digest(String::from(
+            "01bee5c80a6bd74440f0f96c983b1107f1a419e028bef7b33e77e8f968cbfae7",
+        ));
+
+        let data = hash_data(&typed_data).unwrap();
+        let data_v1 = hash_data(&tx.to_typed_data_v1()).unwrap();
+
+        assert_eq!(data.len(), 32);
+        assert_ne!(data, data_v1);
+    }
+
+    #[test]
+    fn test_eip712_builder_v1_golden_json() {
+        let mut typed_data = gen_edit_records_tx().to_typed_data_v1();
+        typed_data.digest(String::from(
+            "01bee5c80a6bd74440f0f96c983b1107f1a419e028bef7b33e77e8f968cbfae7",
+        ));
+
+        let expected = include_str!("../testdata/edit_records_v1.json");
+        assert_eq!(typed_data.to_json(), expected.trim_end());
+    }
+
+    #[test]
+    fn test_eip712_builder_v2_golden_json() {
+        let mut typed_data = gen_edit_records_tx().to_typed_data_v2();
+        typed_data.digest(String::from(
+            "01bee5c80a6bd74440f0f96c983b1107f1a419e028bef7b33e77e8f968cbfae7",
+        ));
+
+        let expected = include_str!("../testdata/edit_records_v2.json");
+        assert_eq!(typed_data.to_json(), expected.trim_end());
+    }
+}
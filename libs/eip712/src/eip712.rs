@@ -1,14 +1,18 @@
 use alloc::collections::BTreeMap;
 use alloc::{format, vec};
 use core::cmp::Ordering;
-#[cfg(debug_assertions)]
-use core::fmt;
 use std::prelude::v1::*;
 
 use super::debug;
 use super::error::*;
 use super::util::*;
 
+/// The EIP712Domain shared by all the typed data signed for DAS, only the version is different between schemas.
+pub const DOMAIN_NAME: &str = "da.systems";
+pub const DOMAIN_VERIFYING_CONTRACT: &str = "0x0000000000000000000000000000000020210722";
+pub const DOMAIN_VERSION_V1: &str = "1";
+pub const DOMAIN_VERSION_V2: &str = "2";
+
 #[derive(Debug)]
 pub struct TypedDataV4 {
     pub types: Types,
@@ -39,40 +43,6 @@ impl TypedDataV4 {
     }
 }
 
-#[cfg(debug_assertions)]
-impl fmt::Display for TypedDataV4 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> ::core::fmt::Result {
-        macro_rules! types_to_json {
-            ($name:expr) => {{
-                let types_vec = self.types.get($name).unwrap();
-                let mut json_str = String::from("[ ");
-                let mut comma = "";
-                for (name, type_) in types_vec {
-                    json_str = json_str + comma + &format!(r#"{{ "name": "{}", "type": "{}" }}"#, name, type_);
-                    comma = ", ";
-                }
-                json_str += " ]";
-                json_str
-            }};
-        }
-
-        let type_eip712domain = types_to_json!("EIP712Domain");
-        let type_action = types_to_json!("Action");
-        let type_cell = types_to_json!("Cell");
-        let type_transaction = types_to_json!("Transaction");
-        let types = format!(
-            r#"{{ "EIP712Domain": {}, "Action": {}, "Cell": {}, "Transaction": {} }}"#,
-            type_eip712domain, type_action, type_cell, type_transaction
-        );
-
-        write!(
-            f,
-            r#"{{ "types": {}, "primary_type": "Transaction", "domain": {}, "message": {} }}"#,
-            types, self.domain, self.message
-        )
-    }
-}
-
 pub type Types = BTreeMap<String, Vec<(String, String)>>;
 
 #[derive(Debug)]
@@ -86,44 +56,6 @@ pub enum Value {
     Object((Vec<String>, BTreeMap<String, Value>)),
 }
 
-#[cfg(debug_assertions)]
-impl fmt::Display for Value {
-    fn fmt(&self, f: &mut fmt::Formatter) -> ::core::fmt::Result {
-        match self {
-            Value::Object((keys, value)) => {
-                let mut json_str = String::from("{ ");
-                let mut comma = "";
-                for key in keys.iter() {
-                    let item = value.get(key).unwrap();
-                    json_str = json_str + comma + &format!(r#""{}": {}"#, key, item);
-                    comma = ", "
-                }
-                json_str += " }";
-
-                write!(f, "{}", json_str)
-            }
-            Value::Array(value) => {
-                let mut json_str = String::from("[ ");
-                let mut comma = "";
-                for item in value.iter() {
-                    json_str = json_str + comma + &item.to_string();
-                    comma = ", "
-                }
-                json_str += " ]";
-
-                write!(f, "{}", json_str)
-            }
-            Value::String(value)
-            | Value::Bytes(value)
-            | Value::Byte32(value)
-            | Value::Address(value)
-            | Value::Uint256(value) => {
-                write!(f, r#""{}""#, value)
-            }
-        }
-    }
-}
-
 impl Value {
     fn encode(
         &self,
@@ -655,6 +587,95 @@ mod test {
         data
     }
 
+    fn gen_typed_data_v2() -> TypedDataV4 {
+        let action = typed_data_v4!(@values {
+            action: Value::String(String::from("edit_records")),
+            params: Value::Array(vec![Value::Bytes(String::from("0x01"))])
+        });
+        let records = Value::Array(vec![typed_data_v4!(@object {
+            action: "CHANGE",
+            type: "profile",
+            key: "twitter",
+            label: "",
+            value: "das"
+        })]);
+        let inputs = Value::Array(vec![typed_data_v4!(@values {
+            capacity: Value::Uint256(String::from("22500000000")),
+            owner: Value::String(String::from("0x15a33588908cf8edb27d1abe3852bf287abd3891")),
+            lock: Value::String(String::from("das-lock,0x01,0x0515a33588908cf8edb27d1abe3852bf287abd38...")),
+            type: Value::String(String::from("account-cell-type,0x01,0x")),
+            data: Value::String(String::from("{ account: tangzhihong005.bit, expired_at: 1662629612 }")),
+            extraData: Value::String(String::from("{ status: 0, records_hash: 0x55478d76900611eb079b22088081124ed6c8bae21a05dd1a0d197efcc7c114ce }"))
+        })]);
+        let outputs = Value::Array(vec![typed_data_v4!(@values {
+            capacity: Value::Uint256(String::from("22499990000")),
+            owner: Value::String(String::from("0x15a33588908cf8edb27d1abe3852bf287abd3891")),
+            lock: Value::String(String::from("das-lock,0x01,0x0515a33588908cf8edb27d1abe3852bf287abd38...")),
+            type: Value::String(String::from("account-cell-type,0x01,0x")),
+            data: Value::String(String::from("{ account: tangzhihong005.bit, expired_at: 1662629612 }")),
+            extraData: Value::String(String::from("{ status: 0, records_hash: 0x75e9c7a4725177c157b31d8a39f73e40ad328be5244a2a2fb6e478a24612c51a }"))
+        })]);
+
+        TypedDataV4 {
+            types: typed_data_v4!(@types_1 {
+                EIP712Domain: {
+                    chainId: "uint256",
+                    name: "string",
+                    verifyingContract: "address",
+                    version: "string"
+                },
+                Action: {
+                    action: "string",
+                    params: "bytes[]"
+                },
+                AccountRecord: {
+                    action: "string",
+                    type: "string",
+                    key: "string",
+                    label: "string",
+                    value: "string"
+                },
+                Cell: {
+                    capacity: "uint256",
+                    owner: "string",
+                    lock: "string",
+                    type: "string",
+                    data: "string",
+                    extraData: "string"
+                },
+                Transaction: {
+                    DAS_MESSAGE: "string",
+                    inputsCapacity: "uint256",
+                    outputsCapacity: "uint256",
+                    fee: "uint256",
+                    action: "Action",
+                    records: "AccountRecord[]",
+                    inputs: "Cell[]",
+                    outputs: "Cell[]",
+                    digest: "bytes32"
+                }
+            }),
+            primary_type: Value::String(String::from("Transaction")),
+            domain: typed_data_v4!(@domain {
+                chainId: "5",
+                name: "da.systems",
+                verifyingContract: "0x0000000000000000000000000000000020210722",
+                version: "2"
+            }),
+            message: typed_data_v4!(@values {
+                DAS_MESSAGE: Value::String(String::from("EDIT RECORDS OF ACCOUNT tangzhihong005.bit: CHANGE profile.twitter=das")),
+                inputsCapacity: Value::Uint256(String::from("22500000000")),
+                outputsCapacity: Value::Uint256(String::from("22499990000")),
+                fee: Value::Uint256(String::from("10000")),
+                action: action,
+                records: records,
+                inputs: inputs,
+                outputs: outputs,
+                digest: Value::Byte32(String::from("01bee5c80a6bd74440f0f96c983b1107f1a419e028bef7b33e77e8f968cbfae7"))
+            }),
+        }
+    }
+
     #[test]
     fn test_wip712_hash_data_macro() {
         let typed_data = gen_typed_data_v4();
@@ -697,6 +718,27 @@ mod test {
         assert_eq!(types_string, expected);
     }
 
+    #[test]
+    fn test_eip712_encode_type_v2() {
+        let typed_data = gen_typed_data_v2();
+
+        let expected = String::from("Transaction(string DAS_MESSAGE,uint256 inputsCapacity,uint256 outputsCapacity,uint256 fee,Action action,AccountRecord[] records,Cell[] inputs,Cell[] outputs,bytes32 digest)AccountRecord(string action,string type,string key,string label,string value)Action(string action,bytes[] params)Cell(uint256 capacity,string owner,string lock,string type,string data,string extraData)");
+        let types_string = encode_type(&typed_data.types, "Transaction", 0).unwrap();
+
+        assert_eq!(types_string, expected);
+    }
+
+    #[test]
+    fn test_eip712_hash_data_v2() {
+        let typed_data = gen_typed_data_v2();
+
+        let data = hash_data(&typed_data).unwrap();
+        let data_v1 = hash_data(&gen_typed_data_v4()).unwrap();
+
+        assert_eq!(data.len(), 32);
+        assert_ne!(data, data_v1);
+    }
+
     #[test]
     fn test_hash_message() {
         let typed_data = gen_typed_data_v4();
@@ -0,0 +1,138 @@
+use alloc::format;
+use std::prelude::v1::*;
+
+use crate::eip712::{TypedDataV4, Types, Value};
+use crate::util::parse_type;
+
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+impl TypedDataV4 {
+    /// Serialize the typed data to the JSON which can be passed to `eth_signTypedData_v4` directly.
+    ///
+    /// The output is canonical, so the same typed data always produces the same string:
+    ///
+    /// - no whitespaces between tokens;
+    /// - types are sorted by name, fields of objects are in the order of their type definitions;
+    /// - `uint256` values are JSON numbers unless they exceed 2^53 - 1, `address`, `bytes` and `bytes32` values are hex strings with `0x` prefix.
+    pub fn to_json(&self) -> String {
+        let primary_type = match &self.primary_type {
+            Value::String(val) => val.as_str(),
+            _ => unreachable!(),
+        };
+
+        let mut types = Vec::new();
+        for (type_name, fields) in self.types.iter() {
+            let fields = fields
+                .iter()
+                .map(|(name, type_)| {
+                    format!(
+                        r#"{{"name":{},"type":{}}}"#,
+                        to_json_string(name),
+                        to_json_string(type_)
+                    )
+                })
+                .collect::<Vec<_>>();
+            types.push(format!("{}:[{}]", to_json_string(type_name), fields.join(",")));
+        }
+
+        format!(
+            r#"{{"types":{{{}}},"primaryType":{},"domain":{},"message":{}}}"#,
+            types.join(","),
+            to_json_string(primary_type),
+            to_json_value(&self.types, "EIP712Domain", &self.domain),
+            to_json_value(&self.types, primary_type, &self.message)
+        )
+    }
+}
+
+fn to_json_value(types: &Types, type_: &str, value: &Value) -> String {
+    match value {
+        Value::Object((keys, object)) => {
+            let fields = match types.get(type_) {
+                Some(fields) => fields
+                    .iter()
+                    .map(|(name, type_)| (name.as_str(), type_.as_str()))
+                    .collect::<Vec<_>>(),
+                // Fallback to the order of keys if the type is not defined, it should not happen with valid typed data.
+                None => keys.iter().map(|key| (key.as_str(), "")).collect::<Vec<_>>(),
+            };
+
+            let mut items = Vec::new();
+            for (name, field_type) in fields {
+                let item = match object.get(name) {
+                    Some(item) => to_json_value(types, field_type, item),
+                    None => String::from("null"),
+                };
+                items.push(format!("{}:{}", to_json_string(name), item));
+            }
+
+            format!("{{{}}}", items.join(","))
+        }
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| to_json_value(types, parse_type(type_), item))
+                .collect::<Vec<_>>();
+
+            format!("[{}]", items.join(","))
+        }
+        Value::String(val) => to_json_string(val),
+        // The numbers which can not be represented exactly by the number of JavaScript are kept in strings, wallets accept both.
+        Value::Uint256(val) => match val.parse::<u64>() {
+            Ok(num) if num <= MAX_SAFE_INTEGER => num.to_string(),
+            _ => to_json_string(val),
+        },
+        Value::Address(val) | Value::Bytes(val) | Value::Byte32(val) => {
+            to_json_string(&format!("0x{}", val.trim_start_matches("0x")))
+        }
+    }
+}
+
+fn to_json_string(val: &str) -> String {
+    let mut ret = String::with_capacity(val.len() + 2);
+    ret.push('"');
+    for c in val.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eip712_to_json_string() {
+        assert_eq!(to_json_string("abc"), r#""abc""#);
+        assert_eq!(to_json_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(to_json_string("a\nb\u{1}"), r#""a\nb\u0001""#);
+        assert_eq!(to_json_string("中文"), "\"中文\"");
+    }
+
+    #[test]
+    fn test_eip712_to_json_uint256() {
+        let types = Types::new();
+        assert_eq!(
+            to_json_value(&types, "uint256", &Value::Uint256(String::from("5"))),
+            "5"
+        );
+        assert_eq!(
+            to_json_value(&types, "uint256", &Value::Uint256(String::from("9007199254740992"))),
+            r#""9007199254740992""#
+        );
+        assert_eq!(
+            to_json_value(&types, "bytes32", &Value::Byte32(String::from("01ab"))),
+            r#""0x01ab""#
+        );
+    }
+}
@@ -6,8 +6,12 @@ extern crate no_std_compat as std;
 #[macro_use]
 pub mod macros;
 
+pub mod builder;
 pub mod eip712;
 pub mod error;
+pub mod json;
+pub mod semantic;
+pub mod sub_account;
 pub mod util;
 
 pub use crate::eip712::hash_data;
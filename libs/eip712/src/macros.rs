@@ -35,6 +35,16 @@ macro_rules! typed_data_v4 {
         $crate::eip712::Value::Object((keys, object))
     }};
     (@object $val:expr) => { $val };
+    // Unlike @object, the values here should already be Value, so fields can be typed other than string.
+    (@values {$( $key:ident: $val:expr ),+}) => {{
+        let mut keys = alloc::vec::Vec::new();
+        let mut object = alloc::collections::BTreeMap::new();
+        $(
+            keys.push(alloc::string::String::from(stringify!($key)));
+            object.insert(alloc::string::String::from(stringify!($key)), $val);
+        )+
+        $crate::eip712::Value::Object((keys, object))
+    }};
     (@array [$( $item:tt ),+]) => {{
         let mut arr = alloc::vec::Vec::new();
         $(
@@ -0,0 +1,318 @@
+use std::prelude::v1::*;
+
+use crate::util::to_semantic_date;
+
+/// The max number of record changes listed in DAS_MESSAGE, the rest will be summarized as "AND n MORE".
+pub const RECORDS_DIFF_MAX_ITEMS: usize = 5;
+/// The max number of chars of a record value listed in DAS_MESSAGE, the rest will be replaced with "...".
+pub const RECORD_VALUE_MAX_CHARS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticRecord {
+    pub type_: String,
+    pub key: String,
+    pub label: String,
+    pub value: String,
+}
+
+impl SemanticRecord {
+    pub fn new(type_: &str, key: &str, label: &str, value: &str) -> Self {
+        SemanticRecord {
+            type_: type_.to_string(),
+            key: key.to_string(),
+            label: label.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    /// Build a SemanticRecord from the raw bytes of a record, the fields which are not valid UTF-8 will be rendered as hex.
+    pub fn from_raw(type_: &[u8], key: &[u8], label: &[u8], value: &[u8]) -> Self {
+        SemanticRecord {
+            type_: bytes_to_semantic(type_),
+            key: bytes_to_semantic(key),
+            label: bytes_to_semantic(label),
+            value: bytes_to_semantic(value),
+        }
+    }
+
+    fn is_same_slot(&self, other: &SemanticRecord) -> bool {
+        self.type_ == other.type_ && self.key == other.key && self.label == other.label
+    }
+
+    fn to_semantic(&self) -> String {
+        let mut value: String = self.value.chars().take(RECORD_VALUE_MAX_CHARS).collect();
+        if self.value.chars().count() > RECORD_VALUE_MAX_CHARS {
+            value += "...";
+        }
+
+        format!("{}.{}={}", self.type_, self.key, value)
+    }
+}
+
+/// Render bytes as a UTF-8 string if possible, otherwise render them as hex with the 0x prefix.
+pub fn bytes_to_semantic(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(val) => val.to_string(),
+        Err(_) => format!("0x{}", hex::encode(bytes)),
+    }
+}
+
+pub fn start_transfer_account_to_semantic(account: &str, pending_owner_address: &str) -> String {
+    format!("START TRANSFER OF ACCOUNT {} TO {}", account, pending_owner_address)
+}
+
+pub fn accept_transfer_account_to_semantic(account: &str) -> String {
+    format!("ACCEPT TRANSFER OF ACCOUNT {}", account)
+}
+
+pub fn cancel_transfer_account_to_semantic(account: &str) -> String {
+    format!("CANCEL TRANSFER OF ACCOUNT {}", account)
+}
+
+pub fn edit_manager_to_semantic(account: &str, manager_address: &str) -> String {
+    format!("EDIT MANAGER OF ACCOUNT {} TO {}", account, manager_address)
+}
+
+pub fn edit_manager_with_permissions_to_semantic(
+    account: &str,
+    manager_address: &str,
+    permissions: &[String],
+) -> String {
+    format!(
+        "EDIT MANAGER OF ACCOUNT {} TO {} WITH PERMISSIONS OF RECORDS {}",
+        account,
+        manager_address,
+        permissions.join(", ")
+    )
+}
+
+pub fn edit_records_to_semantic(
+    account: &str,
+    old_records: &[SemanticRecord],
+    new_records: &[SemanticRecord],
+) -> String {
+    format!(
+        "EDIT RECORDS OF ACCOUNT {}: {}",
+        account,
+        records_diff_to_semantic(old_records, new_records)
+    )
+}
+
+pub fn renew_account_to_semantic(account: &str, expired_at: u64) -> String {
+    format!("RENEW {} UNTIL {}", account, to_semantic_date(expired_at))
+}
+
+pub fn batch_renew_account_to_semantic(accounts: &[(String, u64)]) -> String {
+    let items = accounts
+        .iter()
+        .map(|(account, expired_at)| format!("{} UNTIL {}", account, to_semantic_date(*expired_at)))
+        .collect::<Vec<_>>();
+
+    format!("RENEW {}", items.join(", "))
+}
+
+pub fn enable_sub_account_to_semantic(account: &str) -> String {
+    format!("ENABLE SUB-ACCOUNT FOR {}", account)
+}
+
+pub fn config_sub_account_to_semantic(
+    account: &str,
+    price_rules: (usize, &[u8]),
+    preserved_rules: (usize, &[u8]),
+) -> String {
+    format!(
+        "CONFIG SUB-ACCOUNT FOR {} WITH {} PRICE RULE(S) 0x{} AND {} PRESERVED RULE(S) 0x{}",
+        account,
+        price_rules.0,
+        hex::encode(price_rules.1),
+        preserved_rules.0,
+        hex::encode(preserved_rules.1)
+    )
+}
+
+pub fn update_sub_account_to_semantic(account: &str, count: usize) -> String {
+    format!("UPDATE {} SUB-ACCOUNT(S) OF {}", count, account)
+}
+
+/// Generate the list of records like "address.eth=0x..., profile.twitter=xxx".
+pub fn records_to_semantic(records: &[SemanticRecord]) -> String {
+    records
+        .iter()
+        .map(|record| record.to_semantic())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Generate the diff of records as a list of (operation, record), the operation is one of ADD, CHANGE and REMOVE.
+///
+/// The records with the same type, key and label are treated as the same slot, so changing the value of a slot is a CHANGE.
+/// The changes are listed in the order of new records first and then the removed ones in the order of old records, so the
+/// result is deterministic for the same inputs.
+pub fn records_diff<'a>(
+    old_records: &'a [SemanticRecord],
+    new_records: &'a [SemanticRecord],
+) -> Vec<(&'static str, &'a SemanticRecord)> {
+    let mut old_remains: Vec<Option<&SemanticRecord>> = old_records.iter().map(Some).collect();
+    let mut new_remains = Vec::new();
+
+    // Remove the records which are not modified at all.
+    for record in new_records.iter() {
+        match old_remains
+            .iter()
+            .position(|item| item.map(|v| v == record).unwrap_or(false))
+        {
+            Some(i) => old_remains[i] = None,
+            None => new_remains.push(record),
+        }
+    }
+
+    let mut changes = Vec::new();
+    for record in new_remains.into_iter() {
+        match old_remains
+            .iter()
+            .position(|item| item.map(|v| v.is_same_slot(record)).unwrap_or(false))
+        {
+            Some(i) => {
+                old_remains[i] = None;
+                changes.push(("CHANGE", record));
+            }
+            None => changes.push(("ADD", record)),
+        }
+    }
+    for record in old_remains.into_iter().flatten() {
+        changes.push(("REMOVE", record));
+    }
+
+    changes
+}
+
+/// Generate the diff of records like "ADD address.eth=0x..., CHANGE profile.twitter=xxx, REMOVE dweb.ipfs=xxx".
+pub fn records_diff_to_semantic(old_records: &[SemanticRecord], new_records: &[SemanticRecord]) -> String {
+    let changes = records_diff(old_records, new_records)
+        .into_iter()
+        .map(|(operation, record)| format!("{} {}", operation, record.to_semantic()))
+        .collect::<Vec<_>>();
+
+    if changes.is_empty() {
+        return String::from("NO CHANGE");
+    }
+
+    let total = changes.len();
+    let mut ret = changes
+        .into_iter()
+        .take(RECORDS_DIFF_MAX_ITEMS)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if total > RECORDS_DIFF_MAX_ITEMS {
+        ret += format!(", AND {} MORE", total - RECORDS_DIFF_MAX_ITEMS).as_str();
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eip712_transfer_account_steps_to_semantic() {
+        let message = start_transfer_account_to_semantic("xxxxx.bit", "0x0000000000000000000000000000000000001111");
+        assert_eq!(
+            &message,
+            "START TRANSFER OF ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000001111"
+        );
+
+        let message = accept_transfer_account_to_semantic("xxxxx.bit");
+        assert_eq!(&message, "ACCEPT TRANSFER OF ACCOUNT xxxxx.bit");
+
+        let message = cancel_transfer_account_to_semantic("xxxxx.bit");
+        assert_eq!(&message, "CANCEL TRANSFER OF ACCOUNT xxxxx.bit");
+    }
+
+    #[test]
+    fn test_eip712_edit_manager_to_semantic() {
+        let expected = "EDIT MANAGER OF ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000001111";
+        let message = edit_manager_to_semantic("xxxxx.bit", "0x0000000000000000000000000000000000001111");
+        assert_eq!(&message, expected);
+    }
+
+    #[test]
+    fn test_eip712_edit_manager_with_permissions_to_semantic() {
+        let expected = "EDIT MANAGER OF ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000001111 WITH PERMISSIONS OF RECORDS profile., address.60";
+        let message = edit_manager_with_permissions_to_semantic(
+            "xxxxx.bit",
+            "0x0000000000000000000000000000000000001111",
+            &[String::from("profile."), String::from("address.60")],
+        );
+        assert_eq!(&message, expected);
+    }
+
+    #[test]
+    fn test_eip712_renew_account_to_semantic() {
+        let message = renew_account_to_semantic("xxxxx.bit", 1611200090);
+        assert_eq!(&message, "RENEW xxxxx.bit UNTIL 2021-01-21");
+    }
+
+    #[test]
+    fn test_eip712_batch_renew_account_to_semantic() {
+        let message = batch_renew_account_to_semantic(&[
+            (String::from("xxxxx.bit"), 1611200090),
+            (String::from("yyyyy.bit"), 1642736090),
+        ]);
+        assert_eq!(&message, "RENEW xxxxx.bit UNTIL 2021-01-21, yyyyy.bit UNTIL 2022-01-21");
+    }
+
+    #[test]
+    fn test_eip712_config_sub_account_to_semantic() {
+        let expected = "CONFIG SUB-ACCOUNT FOR xxxxx.bit WITH 2 PRICE RULE(S) 0x0102030405060708090a AND 0 PRESERVED RULE(S) 0x00000000000000000000";
+        let message =
+            config_sub_account_to_semantic("xxxxx.bit", (2, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]), (0, &[0u8; 10]));
+        assert_eq!(&message, expected);
+    }
+
+    #[test]
+    fn test_eip712_records_diff_to_semantic() {
+        let old_records = vec![
+            SemanticRecord::new("address", "eth", "", "0x0000000000000000000000000000000000001111"),
+            SemanticRecord::new("profile", "twitter", "", "das"),
+            SemanticRecord::new("dweb", "ipfs", "", "QmXXX"),
+        ];
+        let new_records = vec![
+            SemanticRecord::new("address", "eth", "", "0x0000000000000000000000000000000000002222"),
+            SemanticRecord::new("profile", "twitter", "", "das"),
+            SemanticRecord::new("address", "btc", "", "bc1qxxx"),
+        ];
+
+        let expected = "EDIT RECORDS OF ACCOUNT xxxxx.bit: CHANGE address.eth=0x000000000000000000000000000000..., ADD address.btc=bc1qxxx, REMOVE dweb.ipfs=QmXXX";
+        let message = edit_records_to_semantic("xxxxx.bit", &old_records, &new_records);
+        assert_eq!(&message, expected);
+    }
+
+    #[test]
+    fn test_eip712_records_from_raw_with_invalid_utf8() {
+        let record = SemanticRecord::from_raw(b"profile", b"twitter", b"", &[0xff, 0xfe, 0x01]);
+        assert_eq!(record, SemanticRecord::new("profile", "twitter", "", "0xfffe01"));
+
+        let record = SemanticRecord::from_raw(b"profile", b"twitter", b"", "小明".as_bytes());
+        assert_eq!(record, SemanticRecord::new("profile", "twitter", "", "小明"));
+    }
+
+    #[test]
+    fn test_eip712_records_diff_to_semantic_no_change() {
+        let records = vec![SemanticRecord::new("profile", "twitter", "", "das")];
+
+        let message = records_diff_to_semantic(&records, &records);
+        assert_eq!(&message, "NO CHANGE");
+    }
+
+    #[test]
+    fn test_eip712_records_diff_to_semantic_truncated() {
+        let new_records = (0..8)
+            .map(|i| SemanticRecord::new("profile", &format!("key{}", i), "", "value"))
+            .collect::<Vec<_>>();
+
+        let expected = "ADD profile.key0=value, ADD profile.key1=value, ADD profile.key2=value, ADD profile.key3=value, ADD profile.key4=value, AND 3 MORE";
+        let message = records_diff_to_semantic(&[], &new_records);
+        assert_eq!(&message, expected);
+    }
+}
@@ -0,0 +1,112 @@
+use std::prelude::v1::*;
+
+use crate::eip712::{TypedDataV4, Value, DOMAIN_NAME, DOMAIN_VERIFYING_CONTRACT, DOMAIN_VERSION_V1};
+
+/// Generate the typed data which is signed by the owner or manager of a sub-account when editing it.
+pub fn sub_account_edit_to_typed_data(
+    chain_id: u64,
+    account: &str,
+    edit_key: &str,
+    edit_value: &str,
+    nonce: u64,
+    sign_expired_at: u64,
+) -> TypedDataV4 {
+    TypedDataV4 {
+        types: typed_data_v4!(@types_1 {
+            EIP712Domain: {
+                chainId: "uint256",
+                name: "string",
+                verifyingContract: "address",
+                version: "string"
+            },
+            SubAccountEdit: {
+                account: "string",
+                editKey: "string",
+                editValue: "string",
+                nonce: "uint256",
+                signExpiredAt: "uint256"
+            }
+        }),
+        primary_type: Value::String(String::from("SubAccountEdit")),
+        domain: typed_data_v4!(@domain {
+            chainId: chain_id.to_string(),
+            name: DOMAIN_NAME,
+            verifyingContract: DOMAIN_VERIFYING_CONTRACT,
+            version: DOMAIN_VERSION_V1
+        }),
+        message: typed_data_v4!(@values {
+            account: Value::String(String::from(account)),
+            editKey: Value::String(String::from(edit_key)),
+            editValue: Value::String(String::from(edit_value)),
+            nonce: Value::Uint256(nonce.to_string()),
+            signExpiredAt: Value::Uint256(sign_expired_at.to_string())
+        }),
+    }
+}
+
+/// Generate the typed data which is signed by the owner or manager of the parent account when minting sub-accounts.
+pub fn sub_account_mint_to_typed_data(chain_id: u64, account_list_smt_root: &[u8], expired_at: u64) -> TypedDataV4 {
+    TypedDataV4 {
+        types: typed_data_v4!(@types_1 {
+            EIP712Domain: {
+                chainId: "uint256",
+                name: "string",
+                verifyingContract: "address",
+                version: "string"
+            },
+            SubAccountMint: {
+                accountListSmtRoot: "bytes32",
+                expiredAt: "uint256"
+            }
+        }),
+        primary_type: Value::String(String::from("SubAccountMint")),
+        domain: typed_data_v4!(@domain {
+            chainId: chain_id.to_string(),
+            name: DOMAIN_NAME,
+            verifyingContract: DOMAIN_VERIFYING_CONTRACT,
+            version: DOMAIN_VERSION_V1
+        }),
+        message: typed_data_v4!(@values {
+            accountListSmtRoot: Value::Byte32(hex::encode(account_list_smt_root)),
+            expiredAt: Value::Uint256(expired_at.to_string())
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eip712::{encode_type, hash_data};
+
+    #[test]
+    fn test_eip712_sub_account_edit_to_typed_data() {
+        let typed_data = sub_account_edit_to_typed_data(5, "sub.xxxxx.bit", "owner", "0x05...", 1, 1611200090);
+
+        let expected =
+            "SubAccountEdit(string account,string editKey,string editValue,uint256 nonce,uint256 signExpiredAt)";
+        assert_eq!(encode_type(&typed_data.types, "SubAccountEdit", 0).unwrap(), expected);
+
+        // The nonce should be signed, so the same signature can not be replayed.
+        let hash = hash_data(&typed_data).unwrap();
+        let other_hash = hash_data(&sub_account_edit_to_typed_data(
+            5,
+            "sub.xxxxx.bit",
+            "owner",
+            "0x05...",
+            2,
+            1611200090,
+        ))
+        .unwrap();
+        assert_eq!(hash.len(), 32);
+        assert_ne!(hash, other_hash);
+    }
+
+    #[test]
+    fn test_eip712_sub_account_mint_to_typed_data() {
+        let typed_data = sub_account_mint_to_typed_data(5, &[1u8; 32], 1611200090);
+
+        let expected = "SubAccountMint(bytes32 accountListSmtRoot,uint256 expiredAt)";
+        assert_eq!(encode_type(&typed_data.types, "SubAccountMint", 0).unwrap(), expected);
+        assert_eq!(hash_data(&typed_data).unwrap().len(), 32);
+    }
+}
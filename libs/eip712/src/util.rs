@@ -57,6 +57,52 @@ pub fn to_doge_address(pubkey_hash: impl AsRef<[u8]>) -> String {
     b58encode_check(payload)
 }
 
+const BTC_P2PKH_ADDR_PREFIX: u8 = 0x00;
+const BTC_P2SH_ADDR_PREFIX: u8 = 0x05;
+const BTC_SEGWIT_HRP: &str = "bc";
+
+/// Convert a HASH160 of public key to the legacy P2PKH address of Bitcoin like `1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH`.
+pub fn to_btc_p2pkh_address(pubkey_hash: impl AsRef<[u8]>) -> String {
+    let mut payload = vec![BTC_P2PKH_ADDR_PREFIX];
+    payload.extend(pubkey_hash.as_ref());
+    b58encode_check(payload)
+}
+
+/// Convert a HASH160 of redeem script to the P2SH address of Bitcoin like `3CNHUhP3uyB9EUtRLsmvFUmvGdjGdkTxJw`.
+pub fn to_btc_p2sh_address(script_hash: impl AsRef<[u8]>) -> String {
+    let mut payload = vec![BTC_P2SH_ADDR_PREFIX];
+    payload.extend(script_hash.as_ref());
+    b58encode_check(payload)
+}
+
+/// Convert a HASH160 of public key to the native segwit P2WPKH address of Bitcoin like `bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4`.
+pub fn to_btc_p2wpkh_address(pubkey_hash: impl AsRef<[u8]>) -> Result<String, bech32::Error> {
+    to_btc_segwit_address(0, pubkey_hash)
+}
+
+/// Convert a witness program to the segwit address of Bitcoin, the version 0 is encoded with bech32 and the others are
+/// encoded with bech32m according to BIP350.
+pub fn to_btc_segwit_address(version: u8, program: impl AsRef<[u8]>) -> Result<String, bech32::Error> {
+    // The witness version is prepended to the witness program as a 5-bit value.
+    let mut data = vec![bech32::u5::try_from_u8(version)?];
+    data.extend(program.as_ref().to_base32());
+
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    bech32::encode(BTC_SEGWIT_HRP, data, variant)
+}
+
+/// Convert an Ed25519 public key to the base58 address like Solana.
+pub fn to_ed25519_address(pubkey: impl AsRef<[u8]>) -> String {
+    let mut output = String::new();
+    bs58::encode(pubkey.as_ref()).into(&mut output).unwrap();
+
+    output
+}
+
 fn b58encode_check<T: AsRef<[u8]>>(raw: T) -> String {
     let mut hasher = Sha256::new();
     hasher.update(raw.as_ref());
@@ -100,6 +146,33 @@ pub fn to_semantic_capacity(capacity: u64) -> String {
     ret
 }
 
+/// Convert a timestamp in seconds to a date like 2021-01-21 in UTC.
+pub fn to_semantic_date(timestamp: u64) -> String {
+    // This is the days_to_civil algorithm from http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Calculate the EIP-191 personal_sign hash of the DAS_MESSAGE and the digest of transaction.
+///
+/// The signed message is `"\x19Ethereum Signed Message:\n" + len + DAS_MESSAGE + " 0x" + digest`, so wallets which can
+/// only sign human-readable strings can still show what they are signing.
+pub fn to_personal_sign_hash(das_message: &str, digest: &[u8]) -> Vec<u8> {
+    let message = format!("{} 0x{}", das_message, hex::encode(digest));
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+
+    keccak256([prefix.as_bytes(), message.as_bytes()].concat().as_slice())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -148,6 +221,45 @@ mod test {
         assert_eq!(&address, expected);
     }
 
+    #[test]
+    fn test_eip712_to_btc_address() {
+        // The HASH160 of the compressed public key of the generator point.
+        let payload = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+
+        assert_eq!(to_btc_p2pkh_address(&payload), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(
+            to_btc_p2wpkh_address(&payload).unwrap(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(to_btc_p2sh_address(&payload), "3CNHUhP3uyB9EUtRLsmvFUmvGdjGdkTxJw");
+
+        // Copy from https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+        let output_key = hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert_eq!(
+            to_btc_segwit_address(1, &output_key).unwrap(),
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+        );
+    }
+
+    #[test]
+    fn test_eip712_to_ed25519_address() {
+        let pubkey = hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a").unwrap();
+
+        let expected = "FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z";
+        let address = to_ed25519_address(pubkey);
+
+        assert_eq!(&address, expected);
+        assert_eq!(to_ed25519_address([0u8; 32]), "11111111111111111111111111111111");
+    }
+
+    #[test]
+    fn test_eip712_to_semantic_date() {
+        assert_eq!(to_semantic_date(0), "1970-01-01");
+        assert_eq!(to_semantic_date(951782400), "2000-02-29");
+        assert_eq!(to_semantic_date(1611200090), "2021-01-21");
+        assert_eq!(to_semantic_date(4102444799), "2099-12-31");
+    }
+
     #[test]
     fn test_eip712_to_semantic_capacity() {
         let expected = "0 CKB";
@@ -170,4 +282,17 @@ mod test {
         let result = to_semantic_capacity(100_000_000_000);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_eip712_to_personal_sign_hash() {
+        // This is the same as the result of personal_sign in wallets like MetaMask.
+        let message = "TRANSFER THE ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000001111 0x0101010101010101010101010101010101010101010101010101010101010101";
+        let expected = keccak256(format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message).as_bytes());
+
+        let hash = to_personal_sign_hash(
+            "TRANSFER THE ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000001111",
+            &[1u8; 32],
+        );
+        assert_eq!(hash, expected);
+    }
 }
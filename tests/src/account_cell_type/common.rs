@@ -6,7 +6,38 @@ use crate::util::template_generator::*;
 use crate::util::{self};
 
 pub fn init(action: &str, params_opt: Option<&str>) -> TemplateGenerator {
+    init_with_stablecoin_settings(action, params_opt, None)
+}
+
+pub fn init_with_stablecoin_settings(
+    action: &str,
+    params_opt: Option<&str>,
+    stablecoin_settings: Option<(Vec<u8>, u8)>,
+) -> TemplateGenerator {
+    init_with_config_main(action, params_opt, |template| {
+        template.config_main_stablecoin_settings = stablecoin_settings;
+    })
+}
+
+pub fn init_with_record_value_validation(action: &str, params_opt: Option<&str>, keys: &[&str]) -> TemplateGenerator {
+    init_with_config_main(action, params_opt, |template| {
+        template.config_main_record_value_validation_keys = keys.iter().map(|key| key.to_string()).collect();
+    })
+}
+
+pub fn init_with_account_registry_mode(action: &str, params_opt: Option<&str>, mode: u8) -> TemplateGenerator {
+    init_with_config_main(action, params_opt, |template| {
+        template.config_main_account_registry_settings = Some((mode, ACCOUNT_REGISTRY_MIGRATION_STARTED_AT));
+    })
+}
+
+fn init_with_config_main(
+    action: &str,
+    params_opt: Option<&str>,
+    set_config_main: impl FnOnce(&mut TemplateGenerator),
+) -> TemplateGenerator {
     let mut template = TemplateGenerator::new(action, params_opt.map(|raw| Bytes::from(util::hex_to_bytes(raw))));
+    set_config_main(&mut template);
 
     template.push_contract_cell("always_success", ContractType::DeployedContract);
     template.push_contract_cell("fake-das-lock", ContractType::DeployedContract);
@@ -25,7 +56,15 @@ pub fn init(action: &str, params_opt: Option<&str>) -> TemplateGenerator {
 }
 
 pub fn init_for_renew(action: &str, params_opt: Option<&str>) -> TemplateGenerator {
-    let mut template = init(action, params_opt);
+    init_for_renew_with_stablecoin_settings(action, params_opt, None)
+}
+
+pub fn init_for_renew_with_stablecoin_settings(
+    action: &str,
+    params_opt: Option<&str>,
+    stablecoin_settings: Option<(Vec<u8>, u8)>,
+) -> TemplateGenerator {
+    let mut template = init_with_stablecoin_settings(action, params_opt, stablecoin_settings);
 
     template.push_contract_cell("income-cell-type", ContractType::Contract);
     template.push_contract_cell("balance-cell-type", ContractType::Contract);
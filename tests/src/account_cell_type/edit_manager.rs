@@ -1,3 +1,4 @@
+use das_types_std::constants::*;
 use serde_json::json;
 
 use super::common::init;
@@ -173,3 +174,179 @@ fn challenge_account_edit_manager_not_modified() {
         AccountCellErrorCode::AccountCellManagerLockShouldBeModified,
     )
 }
+
+#[test]
+fn test_account_edit_manager_with_permissions() {
+    let mut template = before_each();
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_edit_manager_at": TIMESTAMP,
+                "records": [
+                    gen_manager_permission_record("profile.", RECEIVER),
+                    gen_manager_permission_record("address.60", RECEIVER)
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_account_edit_manager_permissions_only() {
+    let mut template = before_each();
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                // The manager is not changed, only the permissions of it are changed.
+                "manager_lock_args": SENDER
+            },
+            "witness": {
+                "last_edit_manager_at": TIMESTAMP,
+                "records": [
+                    gen_manager_permission_record("profile.", SENDER)
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+fn before_each_with_permissions() -> TemplateGenerator {
+    let mut template = init("edit_manager", Some("0x00"));
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": SENDER
+            },
+            "witness": {
+                "records": [
+                    gen_manager_permission_record("profile.", SENDER)
+                ]
+            }
+        }),
+    );
+
+    template
+}
+
+#[test]
+fn test_account_edit_manager_migrate_permissions() {
+    let mut template = before_each_with_permissions();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_edit_manager_at": TIMESTAMP,
+                "records": [
+                    // The permissions of the old manager are migrated to the new manager.
+                    gen_manager_permission_record("profile.", RECEIVER)
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_account_edit_manager_clear_permissions() {
+    let mut template = before_each_with_permissions();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_edit_manager_at": TIMESTAMP,
+                // The permissions of the old manager are cleared.
+                "records": []
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_manager_keep_permissions_of_old_manager() {
+    let mut template = before_each_with_permissions();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_edit_manager_at": TIMESTAMP,
+                "records": [
+                    // Simulate changing the manager while the permissions are still keyed to the old manager.
+                    gen_manager_permission_record("profile.", SENDER)
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordNotPermitted)
+}
+
+#[test]
+fn challenge_account_edit_manager_modify_other_records() {
+    let mut template = before_each();
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_edit_manager_at": TIMESTAMP,
+                "records": [
+                    gen_manager_permission_record("profile.", RECEIVER),
+                    // Simulate editing the records which are not manager_permission.
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    }
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellProtectFieldIsModified,
+    )
+}
@@ -1,7 +1,7 @@
 use das_types_std::constants::*;
 use serde_json::json;
 
-use super::common::init;
+use super::common::{init, init_with_record_value_validation};
 use crate::util::accounts::*;
 use crate::util::constants::*;
 use crate::util::error::*;
@@ -228,3 +228,350 @@ fn challenge_account_edit_records_invalid_coin_type() {
 
     challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordKeyInvalid)
 }
+
+fn before_each_with_manager_permission() -> TemplateGenerator {
+    let mut template = init("edit_records", Some("0x01"));
+
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    {
+                        "type": "profile",
+                        "key": "email",
+                        "label": "Company",
+                        "value": "0x00000000000000000000",
+                    },
+                    gen_manager_permission_record("profile.", MANAGER)
+                ]
+            }
+        }),
+    );
+
+    template
+}
+
+#[test]
+fn test_account_edit_records_with_manager_permission() {
+    let mut template = before_each_with_manager_permission();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    {
+                        "type": "profile",
+                        "key": "email",
+                        "label": "Company",
+                        "value": "0x11111111111111111111",
+                    },
+                    {
+                        "type": "profile",
+                        "key": "twitter",
+                        "label": "",
+                        "value": "0x22222222222222222222",
+                    },
+                    gen_manager_permission_record("profile.", MANAGER)
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_account_edit_records_with_permission_of_other_manager() {
+    let mut template = init("edit_records", Some("0x01"));
+
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    // The permission of another manager does not limit the current manager.
+                    gen_manager_permission_record("profile.", MANAGER_1)
+                ]
+            }
+        }),
+    );
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    gen_manager_permission_record("profile.", MANAGER_1)
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_not_permitted() {
+    let mut template = before_each_with_manager_permission();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        // Simulate editing a record out of the permission.
+                        "value": "0x0000000000000000000000000000000000001111",
+                    },
+                    {
+                        "type": "profile",
+                        "key": "email",
+                        "label": "Company",
+                        "value": "0x00000000000000000000",
+                    },
+                    gen_manager_permission_record("profile.", MANAGER)
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordNotPermitted)
+}
+
+#[test]
+fn challenge_account_edit_records_remove_manager_permission() {
+    let mut template = before_each_with_manager_permission();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    {
+                        "type": "profile",
+                        "key": "email",
+                        "label": "Company",
+                        "value": "0x00000000000000000000",
+                    }
+                    // Simulate the manager removing its own permission.
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordNotPermitted)
+}
+
+fn before_each_with_address_permission(prefix: &str) -> TemplateGenerator {
+    let mut template = init("edit_records", Some("0x01"));
+
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    gen_manager_permission_record(prefix, MANAGER)
+                ]
+            }
+        }),
+    );
+
+    template
+}
+
+fn push_output_account_cell_with_address_permission(template: &mut TemplateGenerator, prefix: &str) {
+    push_output_account_cell(
+        template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "60",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000001111",
+                    },
+                    gen_manager_permission_record(prefix, MANAGER)
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_account_edit_records_with_manager_permission_of_record_type() {
+    // The prefix without the trailing dot should also permit the records of the type.
+    let mut template = before_each_with_address_permission("address");
+    push_output_account_cell_with_address_permission(&mut template, "address");
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_with_manager_permission_of_partial_key() {
+    // Simulate a permission of address.6 which should not permit address.60 .
+    let mut template = before_each_with_address_permission("address.6");
+    push_output_account_cell_with_address_permission(&mut template, "address.6");
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordNotPermitted)
+}
+
+fn before_each_with_record_value_validation() -> TemplateGenerator {
+    let mut template =
+        init_with_record_value_validation("edit_records", Some("0x01"), &["address.eth", "dns.a", "dweb.ipfs"]);
+
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    // This record is saved before the validation is enabled.
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    }
+                ]
+            }
+        }),
+    );
+
+    template
+}
+
+#[test]
+fn test_account_edit_records_with_valid_values() {
+    let mut template = before_each_with_record_value_validation();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    gen_text_record("address", "eth", "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+                    gen_text_record("dns", "a", "192.168.0.1"),
+                    gen_text_record("dweb", "ipfs", "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+                    // The records without validation required should not be checked.
+                    gen_text_record("address", "btc", "xxxxx")
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_invalid_evm_address() {
+    let mut template = before_each_with_record_value_validation();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    // Simulate using an address with invalid EIP-55 checksum.
+                    gen_text_record("address", "eth", "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD")
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordValueInvalid)
+}
+
+#[test]
+fn challenge_account_edit_records_invalid_dns_record() {
+    let mut template = before_each_with_record_value_validation();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    // Simulate using an IPv4 address out of range.
+                    gen_text_record("dns", "a", "192.168.0.256")
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordValueInvalid)
+}
+
+#[test]
+fn challenge_account_edit_records_invalid_content_hash() {
+    let mut template = before_each_with_record_value_validation();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": TIMESTAMP,
+                "records": [
+                    // Simulate using a truncated CID.
+                    gen_text_record("dweb", "ipfs", "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd")
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellRecordValueInvalid)
+}
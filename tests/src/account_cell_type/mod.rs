@@ -9,4 +9,5 @@ mod enable_sub_account;
 mod lock_account_for_cross_chain;
 mod recycle_expired_account;
 mod renew_account;
+mod two_step_transfer;
 mod unlock_account_for_cross_chain;
@@ -1,10 +1,13 @@
-use das_types_std::constants::AccountStatus;
+use ckb_hash::blake2b_256;
+use das_types_std::constants::{AccountStatus, Source};
 use serde_json::json;
+use sparse_merkle_tree::H256;
 
-use super::common::init;
+use super::common::{init, init_with_account_registry_mode};
 use crate::util::accounts::*;
 use crate::util::constants::*;
 use crate::util::error::*;
+use crate::util::smt::SMTWithHistory;
 use crate::util::template_common_cell::*;
 use crate::util::template_generator::*;
 use crate::util::template_parser::*;
@@ -73,6 +76,75 @@ fn before_each() -> TemplateGenerator {
     template
 }
 
+/// Remove the account from an AccountRegistryCell containing the accounts, return (prev_root, current_root, proof).
+fn gen_account_registry_removal(accounts: &[&str], removed_account: &str) -> ([u8; 32], [u8; 32], Vec<u8>) {
+    let gen_leaf = |account: &str| -> (H256, H256) {
+        (
+            util::gen_smt_key_from_account(account).into(),
+            blake2b_256(account.as_bytes()).into(),
+        )
+    };
+
+    let mut smt = SMTWithHistory::new();
+    smt.restore_state(accounts.iter().map(|account| gen_leaf(account)).collect());
+    let prev_root = smt.current_root();
+
+    let (key, _) = gen_leaf(removed_account);
+    smt.insert(key, H256::zero());
+    let current_root = smt.current_root();
+    let proof = smt.get_compiled_proof(vec![key]);
+
+    (prev_root, current_root, proof)
+}
+
+fn before_each_with_account_registry(proof: &[u8]) -> TemplateGenerator {
+    let params = format!("0x{}", hex::encode(proof));
+    let mut template = init_with_account_registry_mode(
+        "recycle_expired_account",
+        Some(params.as_str()),
+        ACCOUNT_REGISTRY_MODE_ENABLED,
+    );
+
+    template.push_contract_cell("sub-account-cell-type", ContractType::Contract);
+    template.push_contract_cell("balance-cell-type", ContractType::Contract);
+    template.push_contract_cell("pre-account-cell-type", ContractType::Contract);
+
+    template
+}
+
+fn push_recycled_account_cells(template: &mut TemplateGenerator) {
+    push_prev_account_cell(template);
+    push_input_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_account_cell_capacity(8),
+            "data": {
+                "account": "das00002.bit",
+                "next": "das00003.bit",
+                "expired_at": TIMESTAMP - ACCOUNT_EXPIRATION_GRACE_PERIOD - ACCOUNT_EXPIRATION_AUCTION_PERIOD - ACCOUNT_EXPIRATION_AUCTION_CONFIRMATION_PERIOD - 1,
+            },
+            "witness": {
+                "enable_sub_account": 0,
+            }
+        }),
+    );
+    template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
+}
+
+fn push_output_recycled_account_cells(template: &mut TemplateGenerator) {
+    push_output_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_account_cell_capacity(8),
+            "data": {
+                "account": "das00001.bit",
+                "next": "das00003.bit",
+            },
+        }),
+    );
+    push_output_balance_cell(template, util::gen_account_cell_capacity(8), OWNER);
+}
+
 #[test]
 fn test_account_recycle_without_sub_account() {
     let mut template = before_each();
@@ -577,3 +649,52 @@ fn challenge_account_recycle_das_refunds_capacity() {
 
     challenge_tx(template.as_json(), ErrorCode::ChangeError);
 }
+
+#[test]
+fn test_account_recycle_with_account_registry() {
+    let (prev_root, current_root, proof) =
+        gen_account_registry_removal(&["das00001.bit", "das00002.bit", "das00003.bit"], "das00002.bit");
+    let mut template = before_each_with_account_registry(&proof);
+
+    // inputs
+    push_recycled_account_cells(&mut template);
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_recycled_account_cells(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_recycle_without_account_registry() {
+    let mut template = before_each_with_account_registry(&[]);
+
+    // inputs
+    // Simulate recycling the AccountCell without removing its account from the registry.
+    push_recycled_account_cells(&mut template);
+
+    // outputs
+    push_output_recycled_account_cells(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure);
+}
+
+#[test]
+fn challenge_account_recycle_with_account_registry_not_removed() {
+    let (prev_root, _, proof) =
+        gen_account_registry_removal(&["das00001.bit", "das00002.bit", "das00003.bit"], "das00002.bit");
+    let mut template = before_each_with_account_registry(&proof);
+
+    // inputs
+    push_recycled_account_cells(&mut template);
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_recycled_account_cells(&mut template);
+    // Simulate keeping the recycled account in the registry.
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError);
+}
@@ -8,6 +8,7 @@ use crate::util::error::*;
 use crate::util::template_common_cell::*;
 use crate::util::template_generator::TemplateGenerator;
 use crate::util::template_parser::*;
+use crate::util::{self};
 
 fn push_simple_output_income_cell(template: &mut TemplateGenerator) {
     push_output_income_cell(
@@ -70,6 +71,43 @@ fn test_account_renew_not_create_income_cell() {
     test_tx(template.as_json());
 }
 
+#[test]
+fn test_account_renew_with_capacity_payment_mode() {
+    // Simulate paying by CKB with the explicit param of payment mode.
+    let mut template = init_for_renew("renew_account", Some("0x00"));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "expired_at": TIMESTAMP
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_simple_output_income_cell(&mut template);
+    push_output_balance_cell(&mut template, 500_000_000_000, OWNER);
+
+    test_tx(template.as_json());
+}
+
 #[test]
 fn test_account_renew_create_income_cell() {
     let mut template = init_for_renew("renew_account", None);
@@ -448,3 +486,517 @@ fn challenge_account_renew_expired_account() {
 
     challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellHasExpired)
 }
+
+fn push_input_udt_cell(template: &mut TemplateGenerator, amount: u128, owner: &str) {
+    template.push_input(
+        gen_udt_cell(
+            amount,
+            json!({
+                "owner_lock_args": owner,
+                "manager_lock_args": owner
+            }),
+        ),
+        None,
+        None,
+    );
+    template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
+}
+
+fn push_output_udt_cell(template: &mut TemplateGenerator, amount: u128, owner: &str) {
+    template.push_output(
+        gen_udt_cell(
+            amount,
+            json!({
+                "owner_lock_args": owner,
+                "manager_lock_args": owner
+            }),
+        ),
+        None,
+    );
+}
+
+const PAYMENT_MODE_STABLECOIN: &str = "0x01";
+
+fn before_each_with_stablecoin() -> TemplateGenerator {
+    let mut template = init_for_renew_with_stablecoin_settings(
+        "renew_account",
+        Some(PAYMENT_MODE_STABLECOIN),
+        Some((gen_udt_type_hash(), 6)),
+    );
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "expired_at": TIMESTAMP
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, 100_000_000_000, OWNER);
+    push_input_udt_cell(&mut template, 10_000_000, OWNER);
+
+    template
+}
+
+#[test]
+fn test_account_renew_with_stablecoin() {
+    let mut template = before_each_with_stablecoin();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_output_udt_cell(&mut template, 5_000_000, OWNER);
+    push_output_udt_cell_to_das_wallet(&mut template, 5_000_000);
+    // The new UDT cell of the DAS wallet costs 200 CKB from the sender.
+    push_output_balance_cell(&mut template, 80_000_000_000, OWNER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_renew_without_income_cell() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    // Simulate paying by CKB without creating the IncomeCell.
+    push_output_balance_cell(&mut template, 500_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
+
+#[test]
+fn challenge_account_renew_stablecoin_without_payment_mode() {
+    // Simulate paying by stablecoin without the param of payment mode.
+    let mut template = init_for_renew_with_stablecoin_settings("renew_account", None, Some((gen_udt_type_hash(), 6)));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "expired_at": TIMESTAMP
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, 100_000_000_000, OWNER);
+    push_input_udt_cell(&mut template, 10_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_output_udt_cell(&mut template, 5_000_000, OWNER);
+    push_output_udt_cell_to_das_wallet(&mut template, 5_000_000);
+    push_output_balance_cell(&mut template, 80_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
+
+#[test]
+fn challenge_account_renew_stablecoin_not_configured() {
+    // Simulate paying by stablecoin when there is no stablecoin settings in ConfigCellMain.
+    let mut template = init_for_renew("renew_account", Some(PAYMENT_MODE_STABLECOIN));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "expired_at": TIMESTAMP
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, 100_000_000_000, OWNER);
+    push_input_udt_cell(&mut template, 10_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_output_udt_cell(&mut template, 5_000_000, OWNER);
+    push_output_udt_cell_to_das_wallet(&mut template, 5_000_000);
+    push_output_balance_cell(&mut template, 80_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::StablecoinPaymentError)
+}
+
+#[test]
+fn challenge_account_renew_stablecoin_with_income_cell() {
+    let mut template = before_each_with_stablecoin();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_output_udt_cell(&mut template, 5_000_000, OWNER);
+    push_output_udt_cell_to_das_wallet(&mut template, 5_000_000);
+    // Simulate creating an IncomeCell when paying by stablecoin.
+    push_simple_output_income_cell(&mut template);
+    push_output_balance_cell(&mut template, 80_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::StablecoinPaymentError)
+}
+
+#[test]
+fn challenge_account_renew_invalid_payment_mode() {
+    // Simulate the param of payment mode is undefined.
+    let mut template = init_for_renew("renew_account", Some("0x02"));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "expired_at": TIMESTAMP
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_simple_output_income_cell(&mut template);
+    push_output_balance_cell(&mut template, 500_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::ParamsDecodingError)
+}
+
+#[test]
+fn challenge_account_renew_stablecoin_not_received() {
+    let mut template = before_each_with_stablecoin();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    push_output_udt_cell(&mut template, 5_000_000, OWNER);
+    // Simulate the DAS wallet receives less than the sender paid.
+    push_output_udt_cell_to_das_wallet(&mut template, 4_000_000);
+    push_output_balance_cell(&mut template, 80_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::StablecoinPaymentError)
+}
+
+#[test]
+fn challenge_account_renew_stablecoin_less_than_one_year() {
+    let mut template = before_each_with_stablecoin();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": TIMESTAMP + 31_536_000,
+            }
+        }),
+    );
+    // Simulate the sender paid less than the price of one year.
+    push_output_udt_cell(&mut template, 6_000_000, OWNER);
+    push_output_udt_cell_to_das_wallet(&mut template, 4_000_000);
+    push_output_balance_cell(&mut template, 80_000_000_000, OWNER);
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellRenewDurationMustLongerThanYear,
+    )
+}
+
+fn push_input_account_cell_for_batch(template: &mut TemplateGenerator, account: &str, owner: &str) {
+    push_input_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": owner
+            },
+            "data": {
+                "account": account,
+                "expired_at": TIMESTAMP
+            },
+            "witness": {
+                "account": account
+            }
+        }),
+    );
+}
+
+fn push_output_account_cell_for_batch(template: &mut TemplateGenerator, account: &str, owner: &str, expired_at: u64) {
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": owner
+            },
+            "data": {
+                "account": account,
+                "expired_at": expired_at
+            },
+            "witness": {
+                "account": account
+            }
+        }),
+    );
+}
+
+fn push_output_income_cell_with_payment(template: &mut TemplateGenerator, capacity: u64) {
+    push_output_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": DAS_WALLET_LOCK_ARGS
+                        },
+                        "capacity": capacity.to_string()
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_account_renew_in_batch() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER);
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000);
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER, TIMESTAMP + 31_536_000 * 2);
+    // The payment of all the AccountCells is aggregated into one record.
+    push_output_income_cell_with_payment(&mut template, 1_500_000_000_000);
+    push_output_balance_cell(&mut template, 500_000_000_000, OWNER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_renew_in_batch_cell_number_mismatch() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER);
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000);
+    push_output_income_cell_with_payment(&mut template, 500_000_000_000);
+    push_output_balance_cell(&mut template, 1_500_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
+
+#[test]
+fn challenge_account_renew_in_batch_different_owners() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    // Simulate renewing an AccountCell of another owner in the same transaction.
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER_1);
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000);
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER_1, TIMESTAMP + 31_536_000);
+    push_output_income_cell_with_payment(&mut template, 1_000_000_000_000);
+    push_output_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
+
+#[test]
+fn challenge_account_renew_in_batch_payment_less_than_increment() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER);
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000);
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER, TIMESTAMP + 31_536_000 * 2);
+    // Simulate paying only one year for every AccountCell.
+    push_output_income_cell_with_payment(&mut template, 1_000_000_000_000);
+    push_output_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellRenewDurationBiggerThanPayed,
+    )
+}
+
+#[test]
+fn test_account_renew_in_batch_with_floated_duration() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER);
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    // The duration of one AccountCell may be floated within one day.
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000 + 80_000);
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER, TIMESTAMP + 31_536_000);
+    push_output_income_cell_with_payment(&mut template, 1_000_000_000_000);
+    push_output_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_renew_in_batch_duration_floated_more_than_one_day() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER);
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    // Simulate floating the duration of every AccountCell by almost one day, so the batch gets almost two days for free.
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000 + 80_000);
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_2, OWNER, TIMESTAMP + 31_536_000 + 80_000);
+    push_output_income_cell_with_payment(&mut template, 1_000_000_000_000);
+    push_output_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellRenewDurationBiggerThanPayed,
+    )
+}
+
+#[test]
+fn challenge_account_renew_in_batch_locked_for_cross_chain() {
+    let mut template = init_for_renew("renew_account", None);
+
+    // inputs
+    push_input_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER);
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "account": ACCOUNT_2,
+                "expired_at": TIMESTAMP
+            },
+            "witness": {
+                "account": ACCOUNT_2,
+                // Simulate one of the AccountCells is locked for cross chain.
+                "status": (AccountStatus::LockedForCrossChain as u8)
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, 2_000_000_000_000, OWNER);
+
+    // outputs
+    push_output_account_cell_for_batch(&mut template, ACCOUNT_1, OWNER, TIMESTAMP + 31_536_000);
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER
+            },
+            "data": {
+                "account": ACCOUNT_2,
+                "expired_at": TIMESTAMP + 31_536_000
+            },
+            "witness": {
+                "account": ACCOUNT_2,
+                "status": (AccountStatus::LockedForCrossChain as u8)
+            }
+        }),
+    );
+    push_output_income_cell_with_payment(&mut template, 1_000_000_000_000);
+    push_output_balance_cell(&mut template, 1_000_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellStatusLocked)
+}
@@ -0,0 +1,320 @@
+use das_types_std::constants::AccountStatus;
+use serde_json::json;
+
+use super::common::*;
+use crate::util::accounts::*;
+use crate::util::constants::*;
+use crate::util::error::*;
+use crate::util::template_common_cell::*;
+use crate::util::template_generator::TemplateGenerator;
+use crate::util::template_parser::*;
+
+fn before_each_start() -> TemplateGenerator {
+    let mut template = init("start_transfer_account", Some("0x00"));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": SENDER
+            }
+        }),
+    );
+
+    template
+}
+
+fn before_each_pending(action: &str, params: &str, started_at: u64) -> TemplateGenerator {
+    let mut template = init(action, Some(params));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": started_at,
+                "status": ACCOUNT_STATUS_PENDING_TRANSFER
+            }
+        }),
+    );
+
+    template
+}
+
+#[test]
+fn test_account_start_transfer() {
+    let mut template = before_each_start();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": ACCOUNT_STATUS_PENDING_TRANSFER
+            }
+        }),
+    );
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_account_start_transfer_to_owner() {
+    let mut template = before_each_start();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                // Simulate setting the owner as the pending owner.
+                "manager_lock_args": SENDER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": ACCOUNT_STATUS_PENDING_TRANSFER
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellPendingOwnerInvalid)
+}
+
+#[test]
+fn challenge_account_start_transfer_modify_owner() {
+    let mut template = before_each_start();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                // Simulate transferring the owner immediately.
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": ACCOUNT_STATUS_PENDING_TRANSFER
+            }
+        }),
+    );
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellOwnerLockShouldNotBeModified,
+    )
+}
+
+#[test]
+fn challenge_account_start_transfer_without_status() {
+    let mut template = before_each_start();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                // Simulate forgetting to mark the AccountCell as pending transfer.
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellStatusLocked)
+}
+
+#[test]
+fn test_account_accept_transfer() {
+    let mut template = before_each_pending("accept_transfer_account", "0x01", TIMESTAMP - 86400);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_account_accept_transfer_by_owner() {
+    // Simulate accepting the transfer with the owner's signature.
+    let mut template = before_each_pending("accept_transfer_account", "0x00", TIMESTAMP - 86400);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellPermissionDenied)
+}
+
+#[test]
+fn challenge_account_accept_transfer_out_of_window() {
+    let mut template = before_each_pending(
+        "accept_transfer_account",
+        "0x01",
+        TIMESTAMP - ACCOUNT_TRANSFER_ACCEPTANCE_WINDOW - 1,
+    );
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellTransferAcceptanceExpired,
+    )
+}
+
+#[test]
+fn challenge_account_accept_transfer_to_others() {
+    let mut template = before_each_pending("accept_transfer_account", "0x01", TIMESTAMP - 86400);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                // Simulate transferring the AccountCell to someone else than the pending owner.
+                "owner_lock_args": OWNER_1,
+                "manager_lock_args": OWNER_1
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellPendingOwnerInvalid)
+}
+
+#[test]
+fn challenge_account_accept_transfer_not_pending() {
+    let mut template = init("accept_transfer_account", Some("0x01"));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": RECEIVER
+            }
+        }),
+    );
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), AccountCellErrorCode::AccountCellStatusLocked)
+}
+
+#[test]
+fn test_account_cancel_transfer() {
+    let mut template = before_each_pending("cancel_transfer_account", "0x00", TIMESTAMP - 86400);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": SENDER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP - 86400,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_account_cancel_transfer_modify_owner() {
+    let mut template = before_each_pending("cancel_transfer_account", "0x00", TIMESTAMP - 86400);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                // Simulate transferring the owner when canceling.
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": TIMESTAMP - 86400,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(
+        template.as_json(),
+        AccountCellErrorCode::AccountCellOwnerLockShouldNotBeModified,
+    )
+}
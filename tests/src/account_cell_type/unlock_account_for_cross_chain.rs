@@ -1,4 +1,5 @@
-use das_types_std::constants::AccountStatus;
+use ckb_hash::blake2b_256;
+use das_types_std::constants::{AccountStatus, Source};
 use serde_json::{json, Value};
 
 use super::common::*;
@@ -6,11 +7,11 @@ use crate::util::accounts::*;
 use crate::util::constants::*;
 use crate::util::error::*;
 use crate::util::template_common_cell::*;
-use crate::util::template_generator::TemplateGenerator;
+use crate::util::template_generator::{gen_das_lock_args, TemplateGenerator};
 use crate::util::template_parser::*;
 use crate::util::{self};
 
-pub fn push_input_account_cell_with_multi_sign(template: &mut TemplateGenerator, cell_partial: Value) {
+fn gen_locked_account_cell(cell_partial: Value) -> Value {
     let mut cell = json!({
         "capacity": util::gen_account_cell_capacity(5),
         "lock": {
@@ -36,10 +37,94 @@ pub fn push_input_account_cell_with_multi_sign(template: &mut TemplateGenerator,
     });
     util::merge_json(&mut cell, cell_partial);
 
-    template.push_input(cell, None, Some(2));
+    cell
+}
+
+pub fn push_input_account_cell_with_multi_sign(template: &mut TemplateGenerator, cell_partial: Value) {
+    template.push_input(gen_locked_account_cell(cell_partial), None, Some(2));
     template.push_multi_sign_witness(0, 3, 5, "0x567419c40d0f2c3566e7630ee32697560fa97a7b543d8ec90d784f60cf920e76a359ae83839a5e7a14dd22136ce74aee2a007c71e5440143dab7b326619b019a75910e04d5f215ace571e5600d48b6766d6a5e1df00e2cf82dd4dcfbba444a94119ae2de");
 }
 
+fn push_input_account_cell_without_multi_sign(template: &mut TemplateGenerator, cell_partial: Value) {
+    template.push_input(gen_locked_account_cell(cell_partial), None, Some(2));
+    // The WitnessArgs.lock is empty, so there is no signature of the keepers at all.
+    template.push_witness_args(None, None, None);
+}
+
+fn push_light_client_cell(template: &mut TemplateGenerator, checkpoint_hash: &[u8], checkpoint_number: u64) {
+    let mut data = checkpoint_hash.to_vec();
+    data.extend(checkpoint_number.to_le_bytes());
+
+    template.push_cell(
+        40_000_000_000,
+        json!({
+            "code_hash": "{{always_success}}"
+        }),
+        json!({
+            "code_hash": "0x0100000000000000000000000000000000000000000000000000000000000000",
+            "hash_type": "type",
+            "args": "0x10"
+        }),
+        Some(data),
+        Source::CellDep,
+    );
+}
+
+/// Generate the params of unlock_account_for_cross_chain which contains a proof of the release event, and the checkpoint
+/// the proof is ending at.
+fn gen_params_with_release_proof(account: &str, lock_args: &str, confirmations: u64) -> (Vec<u8>, [u8; 32], u64) {
+    // The AccountCell is always the inputs[0] in these tests, so its out point is mocked as 0x00..{1_000_000}-0 .
+    let mut tx_hash = vec![0u8; 24];
+    tx_hash.extend(1_000_000u64.to_be_bytes());
+
+    let mut receipt = util::account_to_id(account);
+    receipt.extend(tx_hash);
+    receipt.extend(0u32.to_le_bytes());
+    receipt.extend(util::hex_to_bytes(lock_args));
+
+    // Put the receipt at index 1 of a receipts tree with two leaves, leaves and nodes are tagged with 0x00 and 0x01.
+    let sibling = blake2b_256([[0u8].as_slice(), b"other receipt".as_slice()].concat());
+    let leaf = blake2b_256([[0u8].as_slice(), receipt.as_slice()].concat());
+    let receipts_root = blake2b_256([[1u8].as_slice(), sibling.as_slice(), leaf.as_slice()].concat());
+
+    let mut headers = Vec::new();
+    let mut parent_hash = [0u8; 32];
+    let mut number = 100u64;
+    for i in 0..confirmations {
+        let mut header = parent_hash.to_vec();
+        header.extend(number.to_le_bytes());
+        if i == 0 {
+            header.extend(receipts_root);
+        } else {
+            header.extend([0u8; 32]);
+        }
+
+        parent_hash = blake2b_256(&header);
+        number += 1;
+        headers.extend(header);
+    }
+
+    let mut params = vec![0u8];
+    for field in [headers, receipt, 1u32.to_le_bytes().to_vec(), sibling.to_vec()] {
+        params.extend((field.len() as u32).to_le_bytes());
+        params.extend(field);
+    }
+
+    (params, parent_hash, number - 1)
+}
+
+fn before_each_with_release_proof(lock_args: &str, confirmations: u64) -> TemplateGenerator {
+    let (params, checkpoint_hash, checkpoint_number) =
+        gen_params_with_release_proof(ACCOUNT_1, lock_args, confirmations);
+    let mut template = init("unlock_account_for_cross_chain", Some(&util::bytes_to_hex(&params)));
+    push_light_client_cell(&mut template, &checkpoint_hash, checkpoint_number);
+
+    // inputs
+    push_input_account_cell_without_multi_sign(&mut template, json!({}));
+
+    template
+}
+
 fn before_each() -> TemplateGenerator {
     let mut template = init("unlock_account_for_cross_chain", Some("0x00"));
 
@@ -123,6 +208,96 @@ fn test_account_unlock_account_for_cross_chain_change_manager() {
     test_tx(template.as_json())
 }
 
+#[test]
+fn test_account_unlock_account_for_cross_chain_with_release_proof() {
+    let mut template = before_each_with_release_proof(&gen_das_lock_args(RECEIVER, Some(RECEIVER)), 12);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_account_unlock_account_for_cross_chain_with_release_proof_owner_mismatch() {
+    // Simulate the release event is releasing the account to the RECEIVER, but the keeper unlock it to the SENDER.
+    let mut template = before_each_with_release_proof(&gen_das_lock_args(RECEIVER, Some(RECEIVER)), 12);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SENDER,
+                "manager_lock_args": SENDER
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::CrossChainProofInvalid)
+}
+
+#[test]
+fn challenge_account_unlock_account_for_cross_chain_with_release_proof_not_confirmed() {
+    // Simulate the release event has not enough confirmations.
+    let mut template = before_each_with_release_proof(&gen_das_lock_args(RECEIVER, Some(RECEIVER)), 11);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::CrossChainProofInvalid)
+}
+
+#[test]
+fn challenge_account_unlock_account_for_cross_chain_without_proof_or_multi_sign() {
+    // Simulate there is neither the proof of the release event nor the signature of the keepers.
+    let mut template = init("unlock_account_for_cross_chain", Some("0x00"));
+
+    // inputs
+    push_input_account_cell_without_multi_sign(&mut template, json!({}));
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": RECEIVER,
+                "manager_lock_args": RECEIVER
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::WitnessArgsInvalid)
+}
+
 #[test]
 fn challenge_account_unlock_account_for_cross_chain_owner_and_manager_not_match() {
     let mut template = init("unlock_account_for_cross_chain", Some("0x00"));
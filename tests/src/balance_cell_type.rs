@@ -8,7 +8,15 @@ use crate::util::template_generator::*;
 use crate::util::template_parser::*;
 
 fn init(action: &str) -> TemplateGenerator {
+    init_with_contract_wallet_settings(action, None)
+}
+
+fn init_with_contract_wallet_settings(
+    action: &str,
+    contract_wallet_settings: Option<(u8, Vec<[u8; 20]>)>,
+) -> TemplateGenerator {
     let mut template = TemplateGenerator::new(action, Some(Bytes::from(vec![0])));
+    template.config_main_contract_wallet_settings = contract_wallet_settings;
 
     template.push_contract_cell("fake-das-lock", ContractType::DeployedContract);
     template.push_contract_cell("fake-secp256k1-blake160-signhash-all", ContractType::DeployedContract);
@@ -20,6 +28,48 @@ fn init(action: &str) -> TemplateGenerator {
     template
 }
 
+fn init_for_contract_wallet(action: &str) -> TemplateGenerator {
+    init_with_contract_wallet_settings(action, Some((2, vec![[17u8; 20], [34u8; 20], [51u8; 20]])))
+}
+
+fn push_input_eth_balance_cell(template: &mut TemplateGenerator, owner: &str) {
+    template.push_input(
+        json!({
+            "capacity": "10_000_000_000",
+            "lock": {
+                "owner_lock_args": owner,
+                "manager_lock_args": owner,
+            },
+            "type": {
+                "code_hash": "{{balance-cell-type}}"
+            }
+        }),
+        None,
+        None,
+    );
+}
+
+fn push_input_balance_cell_with_schema_version(template: &mut TemplateGenerator, owner: &str, schema_version: u8) {
+    template.push_input(
+        json!({
+            "capacity": "10_000_000_000",
+            "lock": {
+                "owner_lock_args": owner,
+                "manager_lock_args": owner,
+            },
+            "type": {
+                "code_hash": "{{balance-cell-type}}"
+            }
+        }),
+        None,
+        None,
+    );
+    template.push_das_lock_witness_with_schema_version(
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        schema_version,
+    );
+}
+
 #[test]
 fn test_balance_only_handle_type_5() {
     let mut template = init("transfer");
@@ -134,3 +184,175 @@ fn challenge_balance_without_type_in_outputs() {
 
     challenge_tx(template.as_json(), ErrorCode::BalanceCellFoundSomeOutputsLackOfType);
 }
+
+#[test]
+fn test_balance_with_eip712_schema_v2() {
+    let mut template = init("transfer");
+
+    // inputs
+    push_input_balance_cell_with_schema_version(&mut template, "0x050000000000000000000000000000000000001111", 2);
+    // The v1 signatures without the version byte are still accepted during the migration.
+    push_input_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x050000000000000000000000000000000000002222",
+    );
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        20_000_000_000,
+        "0x050000000000000000000000000000000000009999",
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_balance_with_unknown_eip712_schema_version() {
+    let mut template = init("transfer");
+
+    // inputs
+    // Simulate signing the typed data with an unknown schema version.
+    push_input_balance_cell_with_schema_version(&mut template, "0x050000000000000000000000000000000000001111", 3);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x050000000000000000000000000000000000009999",
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::EIP712SignatureError);
+}
+
+#[test]
+fn test_balance_with_eip191_personal_sign() {
+    let mut template = init("transfer");
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    template.push_personal_sign_witness();
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_balance_with_contract_wallet_quorum() {
+    let mut template = init_for_contract_wallet("transfer");
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    template.push_contract_wallet_quorum_witness(&[0, 2]);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_balance_with_contract_wallet_quorum_not_reached() {
+    let mut template = init_for_contract_wallet("transfer");
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    // Simulate providing less attestations than the threshold.
+    template.push_contract_wallet_quorum_witness(&[0]);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ContractWalletAttestationError);
+}
+
+#[test]
+fn challenge_balance_with_contract_wallet_quorum_duplicated() {
+    let mut template = init_for_contract_wallet("transfer");
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    // Simulate counting the same attester twice.
+    template.push_contract_wallet_quorum_witness(&[1, 1]);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ContractWalletAttestationError);
+}
+
+#[test]
+fn challenge_balance_with_contract_wallet_storage_proof_mismatched() {
+    let mut template = init_for_contract_wallet("transfer");
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    // Simulate providing a storage proof which does not prove the digest.
+    template.push_contract_wallet_storage_proof_witness(&[0u8; 32]);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ContractWalletAttestationError);
+}
+
+#[test]
+fn challenge_balance_with_contract_wallet_attesters_not_configured() {
+    // Simulate the attesters are not configured in ConfigCellMain.
+    let mut template = init("transfer");
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    template.push_contract_wallet_quorum_witness(&[0, 2]);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ContractWalletAttestationError);
+}
+
+#[test]
+fn challenge_balance_with_contract_wallet_threshold_invalid() {
+    // Simulate the threshold is larger than the number of attesters.
+    let mut template = init_with_contract_wallet_settings("transfer", Some((3, vec![[17u8; 20], [34u8; 20]])));
+
+    // inputs
+    push_input_eth_balance_cell(&mut template, "0x030000000000000000000000000000000000001111");
+    template.push_contract_wallet_quorum_witness(&[0, 1]);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        10_000_000_000,
+        "0x030000000000000000000000000000000000009999",
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ContractWalletAttestationError);
+}
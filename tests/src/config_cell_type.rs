@@ -1,9 +1,17 @@
+use ckb_hash::blake2b_256;
 use das_types_std::constants::*;
+use das_types_std::packed::*;
+use das_types_std::prelude::*;
+use das_types_std::util as das_util;
+use das_types_std::util::EntityWrapper;
+use serde_json::json;
 
 use super::util::constants::*;
+use super::util::error::*;
 use super::util::template_common_cell::*;
 use super::util::template_generator::*;
 use super::util::template_parser::*;
+use super::util::{self};
 
 fn init() -> TemplateGenerator {
     let mut template = TemplateGenerator::new("config", None);
@@ -62,3 +70,324 @@ fn test_config_edit() {
 
     test_tx(template.as_json());
 }
+
+#[test]
+fn challenge_config_price_missing_length() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    // Simulate removing the price of the longest priced length.
+    template.prices.remove(&8u8);
+    template.push_config_cell(DataType::ConfigCellPrice, Source::Output);
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+fn push_output_config_cell(
+    template: &mut TemplateGenerator,
+    config_type: DataType,
+    cell_data: Vec<u8>,
+    witness: Vec<u8>,
+) {
+    let lock_script = json!({
+      "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+      "args": CONFIG_LOCK_ARGS
+    });
+    let type_script = json!({
+      "code_hash": "{{config-cell-type}}",
+      "args": util::bytes_to_hex(&(config_type as u32).to_le_bytes()),
+    });
+    template.push_cell(0, lock_script, type_script, Some(cell_data), Source::Output);
+    template.outer_witnesses.push(util::bytes_to_hex(&witness));
+}
+
+fn push_output_config_cell_with_entity(template: &mut TemplateGenerator, config_type: DataType, entity: EntityWrapper) {
+    let cell_data = match &entity {
+        EntityWrapper::ConfigCellAccount(entity) => blake2b_256(entity.as_slice()),
+        EntityWrapper::ConfigCellMain(entity) => blake2b_256(entity.as_slice()),
+        EntityWrapper::ConfigCellProfitRate(entity) => blake2b_256(entity.as_slice()),
+        _ => panic!("The entity is not supported by this helper."),
+    };
+    let witness = das_util::wrap_entity_witness_v4(config_type, entity);
+
+    push_output_config_cell(template, config_type, cell_data.to_vec(), witness);
+}
+
+fn push_output_config_cell_char_set(template: &mut TemplateGenerator, config_type: DataType, chars: &[&str]) {
+    let mut raw = vec![0u8]; // global status
+    for char in chars {
+        raw.extend(char.as_bytes());
+        raw.push(0);
+    }
+    let raw = util::prepend_molecule_like_length(raw);
+    let cell_data = blake2b_256(raw.as_slice()).to_vec();
+    let witness = das_util::wrap_raw_witness_v2(config_type, raw);
+
+    push_output_config_cell(template, config_type, cell_data, witness);
+}
+
+#[test]
+fn test_config_create_char_sets() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    // The chars in the char sets are not sorted, this is how they are deployed.
+    template.push_config_cell(DataType::ConfigCellCharSetEmoji, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetDigit, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetEn, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetZhHans, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetZhHant, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetJa, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetKo, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetRu, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetTr, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetTh, Source::Output);
+    template.push_config_cell(DataType::ConfigCellCharSetVi, Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_config_char_set_in_any_order() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    push_output_config_cell_char_set(&mut template, DataType::ConfigCellCharSetEn, &["c", "a", "b"]);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_config_char_set_duplicated() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    // Simulate a char is listed twice and not next to each other.
+    push_output_config_cell_char_set(&mut template, DataType::ConfigCellCharSetEn, &["a", "b", "c", "a"]);
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+#[test]
+fn challenge_config_char_set_empty_char() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    // Simulate there are two 0x00 next to each other.
+    push_output_config_cell_char_set(&mut template, DataType::ConfigCellCharSetEn, &["a", "", "b"]);
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+#[test]
+fn challenge_config_profit_rate_sum_exceeded() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    let entity = match template.gen_config_cell_profit_rate().1 {
+        EntityWrapper::ConfigCellProfitRate(entity) => entity,
+        _ => unreachable!(),
+    };
+    // Simulate every profit rate of the sale is valid, but their sum exceeds RATE_BASE.
+    let entity = entity.as_builder().sale_das(Uint32::from(RATE_BASE as u32)).build();
+    push_output_config_cell_with_entity(
+        &mut template,
+        DataType::ConfigCellProfitRate,
+        EntityWrapper::ConfigCellProfitRate(entity),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+#[test]
+fn challenge_config_profit_rate_exceeded() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    let entity = match template.gen_config_cell_profit_rate().1 {
+        EntityWrapper::ConfigCellProfitRate(entity) => entity,
+        _ => unreachable!(),
+    };
+    // Simulate a single profit rate exceeds RATE_BASE.
+    let entity = entity
+        .as_builder()
+        .income_consolidate(Uint32::from(RATE_BASE as u32 + 1))
+        .build();
+    push_output_config_cell_with_entity(
+        &mut template,
+        DataType::ConfigCellProfitRate,
+        EntityWrapper::ConfigCellProfitRate(entity),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+#[test]
+fn challenge_config_account_zero_throttle() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    let entity = match template.gen_config_cell_account().1 {
+        EntityWrapper::ConfigCellAccount(entity) => entity,
+        _ => unreachable!(),
+    };
+    // Simulate the throttle of editing records is 0.
+    let entity = entity.as_builder().edit_records_throttle(Uint32::from(0)).build();
+    push_output_config_cell_with_entity(
+        &mut template,
+        DataType::ConfigCellAccount,
+        EntityWrapper::ConfigCellAccount(entity),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+#[test]
+fn challenge_config_main_zero_type_id() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    let entity = match template.gen_config_cell_main().1 {
+        EntityWrapper::ConfigCellMain(entity) => entity,
+        _ => unreachable!(),
+    };
+    // Simulate the type ID of AccountCell is 0.
+    let type_id_table = entity
+        .type_id_table()
+        .as_builder()
+        .account_cell(Hash::default())
+        .build();
+    let entity = entity.as_builder().type_id_table(type_id_table).build();
+    push_output_config_cell_with_entity(
+        &mut template,
+        DataType::ConfigCellMain,
+        EntityWrapper::ConfigCellMain(entity),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+#[test]
+fn challenge_config_main_zero_das_lock_type_id() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    let entity = match template.gen_config_cell_main().1 {
+        EntityWrapper::ConfigCellMain(entity) => entity,
+        _ => unreachable!(),
+    };
+    // Simulate the type ID of the ETH sign library is 0.
+    let das_lock_type_id_table = entity
+        .das_lock_type_id_table()
+        .as_builder()
+        .eth(Hash::default())
+        .build();
+    let entity = entity
+        .as_builder()
+        .das_lock_type_id_table(das_lock_type_id_table)
+        .build();
+    push_output_config_cell_with_entity(
+        &mut template,
+        DataType::ConfigCellMain,
+        EntityWrapper::ConfigCellMain(entity),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellIsInvalid);
+}
+
+fn before_each_update_of_config_main() -> (TemplateGenerator, Vec<u8>, Vec<u8>) {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    template.push_config_cell(DataType::ConfigCellMain, Source::Input);
+    let active_hash = template.get_config_cell_active_hash(Source::Input, 1);
+
+    // Simulate pausing an action which is a modification needs to be staged.
+    template.config_main_paused_actions = vec!["transfer_account"];
+    template.push_config_cell(DataType::ConfigCellMain, Source::Output);
+    let pending_hash = template.get_config_cell_active_hash(Source::Output, 0);
+
+    (template, active_hash, pending_hash)
+}
+
+#[test]
+fn test_config_stage_update() {
+    let (mut template, active_hash, pending_hash) = before_each_update_of_config_main();
+
+    template.stage_config_cell(
+        Source::Output,
+        0,
+        &active_hash,
+        &pending_hash,
+        TIMESTAMP + CONFIG_CELL_ACTIVATION_DELAY,
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_config_activate_pending_update() {
+    let (mut template, active_hash, pending_hash) = before_each_update_of_config_main();
+
+    template.stage_config_cell(Source::Input, 1, &active_hash, &pending_hash, TIMESTAMP);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_config_switch_status_in_emergency() {
+    let mut template = init();
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+
+    template.push_config_cell(DataType::ConfigCellMain, Source::Input);
+    template.config_main_status = 0;
+    template.push_config_cell(DataType::ConfigCellMain, Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_config_update_without_staging() {
+    let (template, _, _) = before_each_update_of_config_main();
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellStagingError);
+}
+
+#[test]
+fn challenge_config_stage_update_with_short_delay() {
+    let (mut template, active_hash, pending_hash) = before_each_update_of_config_main();
+
+    // Simulate staging an update which takes effect earlier than the activation delay.
+    template.stage_config_cell(
+        Source::Output,
+        0,
+        &active_hash,
+        &pending_hash,
+        TIMESTAMP + CONFIG_CELL_ACTIVATION_DELAY - 1,
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellStagingError);
+}
+
+#[test]
+fn challenge_config_activate_pending_update_too_early() {
+    let (mut template, active_hash, pending_hash) = before_each_update_of_config_main();
+
+    // Simulate activating a pending update before its effective time.
+    template.stage_config_cell(Source::Input, 1, &active_hash, &pending_hash, TIMESTAMP + 1);
+
+    challenge_tx(template.as_json(), ErrorCode::ConfigCellStagingError);
+}
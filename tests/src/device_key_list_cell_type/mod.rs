@@ -35,7 +35,7 @@ impl DeviceKeyListCell {
         }
     }
 
-    fn default_new(capacity: u64, lock_args: Bytes, witness: DeviceKeyListCellData) -> Self {
+    pub(crate) fn default_new(capacity: u64, lock_args: Bytes, witness: DeviceKeyListCellData) -> Self {
         Self::simple_new(capacity, "fake-das-lock", lock_args, witness)
     }
 
@@ -61,7 +61,7 @@ impl DeviceKeyListCell {
         }
     }
 
-    fn push(&self, template: &mut TemplateGenerator, source: Source) {
+    pub(crate) fn push(&self, template: &mut TemplateGenerator, source: Source) {
         let cell = json!({
             "capacity": self.capacity,
             "type": {
@@ -1,6 +1,9 @@
 use das_types_std::constants::*;
+use das_types_std::prelude::*;
+use serde_json::{json, Value};
 
 use crate::util::template_generator::*;
+use crate::util::{self};
 
 pub fn init(action: &str) -> TemplateGenerator {
     let mut template = TemplateGenerator::new(action, None);
@@ -14,3 +17,24 @@ pub fn init(action: &str) -> TemplateGenerator {
 
     template
 }
+
+pub const VESTING_BENEFICIARY: &str = "0x0000000000000000000000000000000000000020";
+
+pub fn gen_vesting_record(start: u64, cliff: u64, duration: u64, total: u64, capacity: u64) -> Value {
+    let args = [
+        start.to_le_bytes().to_vec(),
+        cliff.to_le_bytes().to_vec(),
+        duration.to_le_bytes().to_vec(),
+        total.to_le_bytes().to_vec(),
+        gen_fake_signhash_all_lock(VESTING_BENEFICIARY).as_slice().to_vec(),
+    ]
+    .concat();
+
+    json!({
+        "belong_to": {
+            "code_hash": "{{income-cell-type}}",
+            "args": util::bytes_to_hex(&args)
+        },
+        "capacity": capacity
+    })
+}
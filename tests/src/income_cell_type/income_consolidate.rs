@@ -1,7 +1,7 @@
 use das_types_std::constants::{DataType, Source};
-use serde_json::json;
+use serde_json::{json, Value};
 
-use super::common::init;
+use super::common::*;
 use crate::util::accounts::*;
 use crate::util::constants::*;
 use crate::util::error::*;
@@ -997,3 +997,237 @@ fn challenge_income_consolidate_eip712_cells_without_type_script() {
     //     ],
     // );
 }
+
+fn before_vesting() -> TemplateGenerator {
+    let mut template = before();
+
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    template
+}
+
+fn push_vesting_inputs(template: &mut TemplateGenerator, vesting_record: Value) {
+    push_input_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": 20_000_000_000u64,
+                    },
+                    vesting_record,
+                ]
+            }
+        }),
+    );
+    push_input_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": 20_000_000_000u64,
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 1_000_000_000u64,
+                    },
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_income_consolidate_vesting_record() {
+    let mut template = before_vesting();
+
+    let start = TIMESTAMP - 50 * DAY_SEC;
+
+    // inputs
+    push_vesting_inputs(
+        &mut template,
+        gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+    );
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    // Half of the total is vested, so only the other half should be kept.
+                    gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 100_000_000_000),
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 1_000_000_000u64,
+                    },
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+    push_output_normal_cell(&mut template, 99_000_000_000, VESTING_BENEFICIARY);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_income_consolidate_vesting_record_partially_released() {
+    let mut template = before_vesting();
+
+    let start = TIMESTAMP - 60 * DAY_SEC;
+
+    // inputs
+    // 50 CKB of the total has been released before.
+    push_vesting_inputs(
+        &mut template,
+        gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 150_000_000_000),
+    );
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    // 60% of the total is vested, so 120 CKB - 50 CKB = 70 CKB should be released this time.
+                    gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 80_000_000_000),
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 1_000_000_000u64,
+                    },
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+    push_output_normal_cell(&mut template, 69_300_000_000, VESTING_BENEFICIARY);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_income_consolidate_vesting_record_before_cliff() {
+    let mut template = before_vesting();
+
+    let start = TIMESTAMP - 5 * DAY_SEC;
+
+    // inputs
+    push_vesting_inputs(
+        &mut template,
+        gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+    );
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    // Nothing is vested before the cliff.
+                    gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 1_000_000_000u64,
+                    },
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_income_consolidate_vesting_record_release_unvested() {
+    let mut template = before_vesting();
+
+    let start = TIMESTAMP - 50 * DAY_SEC;
+
+    // inputs
+    push_vesting_inputs(
+        &mut template,
+        gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+    );
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 1_000_000_000u64,
+                    },
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+    // Simulate releasing the unvested capacity to the beneficiary.
+    push_output_normal_cell(&mut template, 198_000_000_000, VESTING_BENEFICIARY);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellTransferError)
+}
+
+#[test]
+fn challenge_income_consolidate_vesting_record_invalid_schedule() {
+    let mut template = before_vesting();
+
+    let start = TIMESTAMP - 50 * DAY_SEC;
+
+    // inputs
+    // Simulate a vesting record with a cliff longer than its duration.
+    push_vesting_inputs(
+        &mut template,
+        gen_vesting_record(start, 200 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+    );
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_vesting_record(start, 200 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 1_000_000_000u64,
+                    },
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellVestingRecordInvalid)
+}
@@ -0,0 +1,283 @@
+use das_types_std::constants::{DataType, Source};
+use serde_json::{json, Value};
+
+use super::common::*;
+use crate::util::accounts::*;
+use crate::util::constants::*;
+use crate::util::error::*;
+use crate::util::template_common_cell::*;
+use crate::util::template_generator::*;
+use crate::util::template_parser::*;
+
+const OWNER_OF_RECORD: &str = "0x0000000000000000000000000000000000000010";
+const OTHER_OWNER_OF_RECORD: &str = "0x0000000000000000000000000000000000000020";
+
+fn before() -> TemplateGenerator {
+    let mut template = init("withdraw_income");
+
+    template.push_config_cell(DataType::ConfigCellProfitRate, Source::CellDep);
+
+    template
+}
+
+fn gen_record(args: &str, capacity: u64) -> Value {
+    json!({
+        "belong_to": {
+            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+            "args": args
+        },
+        "capacity": capacity
+    })
+}
+
+fn push_common_inputs(template: &mut TemplateGenerator) {
+    push_input_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_record(OWNER_OF_RECORD, 50_000_000_000u64),
+                    gen_record(OTHER_OWNER_OF_RECORD, 5_000_000_000u64),
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_income_withdraw() {
+    let mut template = before();
+
+    // inputs
+    push_common_inputs(&mut template);
+    push_input_normal_cell(&mut template, 10_000_000_000, OWNER_OF_RECORD);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_record(OTHER_OWNER_OF_RECORD, 5_000_000_000u64),
+                    // The fee is 1% of the withdrawn capacity.
+                    gen_record(DAS_WALLET_LOCK_ARGS, 500_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 59_500_000_000, OWNER_OF_RECORD);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_income_withdraw_by_creator() {
+    let mut template = before();
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 30_000_000_000u64),
+                    gen_record(OWNER_OF_RECORD, 5_000_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_input_normal_cell(&mut template, 10_000_000_000, COMMON_INCOME_CREATOR);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_record(OWNER_OF_RECORD, 5_000_000_000u64),
+                ]
+            }
+        }),
+    );
+    // The creator should be free from the fee.
+    push_output_normal_cell(&mut template, 20_000_000_000, COMMON_INCOME_CREATOR);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_income_withdraw_without_owner_lock() {
+    let mut template = before();
+
+    // inputs
+    push_common_inputs(&mut template);
+    // Simulate withdrawing a record without unlocking any cell of its owner.
+    push_input_normal_cell(&mut template, 10_000_000_000, OTHER_OWNER_OF_RECORD);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_record(OTHER_OWNER_OF_RECORD, 5_000_000_000u64),
+                    gen_record(DAS_WALLET_LOCK_ARGS, 500_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 59_500_000_000, OTHER_OWNER_OF_RECORD);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_other_record_modified() {
+    let mut template = before();
+
+    // inputs
+    push_common_inputs(&mut template);
+    push_input_normal_cell(&mut template, 10_000_000_000, OWNER_OF_RECORD);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    // Simulate withdrawing the record of others.
+                    gen_record(DAS_WALLET_LOCK_ARGS, 550_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 64_450_000_000, OWNER_OF_RECORD);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_new_record_created() {
+    let mut template = before();
+
+    // inputs
+    push_common_inputs(&mut template);
+    push_input_normal_cell(&mut template, 10_000_000_000, OWNER_OF_RECORD);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_record(OTHER_OWNER_OF_RECORD, 5_000_000_000u64),
+                    gen_record(DAS_WALLET_LOCK_ARGS, 500_000_000u64),
+                    // Simulate moving part of the withdrawn capacity to a new record.
+                    gen_record("0x0000000000000000000000000000000000000030", 1_000_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 58_500_000_000, OWNER_OF_RECORD);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_fee_not_paid() {
+    let mut template = before();
+
+    // inputs
+    push_common_inputs(&mut template);
+    push_input_normal_cell(&mut template, 10_000_000_000, OWNER_OF_RECORD);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_record(OTHER_OWNER_OF_RECORD, 5_000_000_000u64),
+                    // Simulate paying less fee than expected.
+                    gen_record(DAS_WALLET_LOCK_ARGS, 100_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 59_900_000_000, OWNER_OF_RECORD);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_basic_capacity_of_creator() {
+    let mut template = before();
+
+    // inputs
+    push_common_inputs(&mut template);
+    push_input_normal_cell(&mut template, 10_000_000_000, COMMON_INCOME_CREATOR);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    // Simulate withdrawing the basic capacity kept by the creator.
+                    gen_record(COMMON_INCOME_CREATOR, 10_000_000_000u64),
+                    gen_record(OWNER_OF_RECORD, 50_000_000_000u64),
+                    gen_record(OTHER_OWNER_OF_RECORD, 5_000_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 20_000_000_000, COMMON_INCOME_CREATOR);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_vesting_record() {
+    let mut template = before();
+
+    let start = TIMESTAMP - 50 * DAY_SEC;
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 200_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_normal_cell(&mut template, 10_000_000_000, VESTING_BENEFICIARY);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000u64),
+                    // Simulate the beneficiary withdrawing the vested part without releasing it by consolidate_income.
+                    gen_vesting_record(start, 10 * DAY_SEC, 100 * DAY_SEC, 200_000_000_000, 100_000_000_000),
+                    gen_record(DAS_WALLET_LOCK_ARGS, 1_000_000_000u64),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 109_000_000_000, VESTING_BENEFICIARY);
+
+    challenge_tx(template.as_json(), ErrorCode::IncomeCellVestingRecordInvalid)
+}
@@ -3,3 +3,4 @@ mod common;
 
 mod income_consolidate;
 mod income_create;
+mod income_withdraw;
@@ -0,0 +1,628 @@
+use ckb_hash::blake2b_256;
+use das_types_std::constants::*;
+use das_types_std::packed::*;
+use serde_json::json;
+
+use super::common::*;
+use crate::util::accounts::*;
+use crate::util::constants::*;
+use crate::util::error::*;
+use crate::util::smt::SMTWithHistory;
+use crate::util::template_common_cell::*;
+use crate::util::template_generator::*;
+use crate::util::template_parser::*;
+use crate::util::{self};
+
+const MODE_MIGRATING: Option<u8> = Some(ACCOUNT_REGISTRY_MODE_MIGRATING);
+const MODE_ENABLED: Option<u8> = Some(ACCOUNT_REGISTRY_MODE_ENABLED);
+
+fn gen_leaf(account: &str) -> ([u8; 32], [u8; 32]) {
+    (util::gen_smt_key_from_account(account), blake2b_256(account.as_bytes()))
+}
+
+fn gen_registry_root(accounts: &[&str]) -> [u8; 32] {
+    let mut smt = SMTWithHistory::new();
+    for account in accounts.iter() {
+        let (key, value) = gen_leaf(account);
+        smt.insert(key.into(), value.into());
+    }
+    smt.current_root()
+}
+
+/// Insert the accounts into the SMT and return (prev_root, current_root, compiled proof of the inserted accounts).
+fn gen_registry_transition(existing_accounts: &[&str], new_accounts: &[&str]) -> ([u8; 32], [u8; 32], Vec<u8>) {
+    let mut smt = SMTWithHistory::new();
+    smt.restore_state(
+        existing_accounts
+            .iter()
+            .map(|account| {
+                let (key, value) = gen_leaf(account);
+                (key.into(), value.into())
+            })
+            .collect(),
+    );
+
+    let prev_root = smt.current_root();
+    let mut keys = Vec::new();
+    for account in new_accounts.iter() {
+        let (key, value) = gen_leaf(account);
+        smt.insert(key.into(), value.into());
+        keys.push(key.into());
+    }
+    let current_root = smt.current_root();
+    let proof = smt.get_compiled_proof(keys);
+
+    (prev_root, current_root, proof)
+}
+
+/// Remove the accounts from the SMT and return (prev_root, current_root, compiled proof of the removed accounts).
+fn gen_registry_removal(existing_accounts: &[&str], removed_accounts: &[&str]) -> ([u8; 32], [u8; 32], Vec<u8>) {
+    let mut smt = SMTWithHistory::new();
+    smt.restore_state(
+        existing_accounts
+            .iter()
+            .map(|account| {
+                let (key, value) = gen_leaf(account);
+                (key.into(), value.into())
+            })
+            .collect(),
+    );
+
+    let prev_root = smt.current_root();
+    let mut keys = Vec::new();
+    for account in removed_accounts.iter() {
+        let (key, _) = gen_leaf(account);
+        smt.insert(key.into(), [0u8; 32].into());
+        keys.push(key.into());
+    }
+    let current_root = smt.current_root();
+    let proof = smt.get_compiled_proof(keys);
+
+    (prev_root, current_root, proof)
+}
+
+fn before_each_pre_register(mode: Option<u8>, proof: Vec<u8>) -> TemplateGenerator {
+    let mut template = init_with_account_registry_mode(
+        "pre_register",
+        Some(gen_pre_register_params(PAYMENT_MODE_CAPACITY, proof)),
+        mode,
+    );
+    template.push_config_cell_derived_by_account(ACCOUNT_SP_1, Source::CellDep);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    template
+}
+
+fn push_output_simple_pre_account_cell(template: &mut TemplateGenerator) {
+    push_output_pre_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_register_fee_v2(ACCOUNT_SP_1, 8, true),
+            "witness": {
+                "account": ACCOUNT_SP_1,
+                "price": {
+                    "length": 8,
+                    "new": ACCOUNT_PRICE_5_CHAR,
+                    "renew": ACCOUNT_PRICE_5_CHAR
+                },
+                "inviter_id": "0x0000000000000000000000000000000000000000",
+                "inviter_lock": {
+                    "code_hash": "{{fake-das-lock}}",
+                    "args": gen_das_lock_args(INVITER, None)
+                },
+                "channel_lock": {
+                    "code_hash": "{{fake-das-lock}}",
+                    "args": gen_das_lock_args(CHANNEL, None)
+                },
+                "invited_discount": INVITED_DISCOUNT,
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_account_registry_create() {
+    let mut template = init_with_account_registry_mode("create_account_registry", None, None);
+
+    // inputs
+    push_input_normal_cell(&mut template, 0, SUPER_LOCK_ARGS);
+
+    // outputs
+    push_account_registry_cell(&mut template, [0u8; 32], None, Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_registry_create_without_super_lock() {
+    let mut template = init_with_account_registry_mode("create_account_registry", None, None);
+
+    // inputs
+    push_input_normal_cell(&mut template, 0, OWNER_WITHOUT_TYPE);
+
+    // outputs
+    push_account_registry_cell(&mut template, [0u8; 32], None, Source::Output);
+
+    challenge_tx(template.as_json(), ErrorCode::SuperLockIsRequired);
+}
+
+#[test]
+fn challenge_account_registry_create_with_non_empty_root() {
+    let mut template = init_with_account_registry_mode("create_account_registry", None, None);
+
+    // inputs
+    push_input_normal_cell(&mut template, 0, SUPER_LOCK_ARGS);
+
+    // outputs
+    // Simulate creating the AccountRegistryCell with some accounts already in it.
+    let (_, current_root, _) = gen_registry_transition(&[], &[ACCOUNT_1]);
+    push_account_registry_cell(&mut template, current_root, None, Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError);
+}
+
+#[test]
+fn test_account_registry_migrate() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[], &[ACCOUNT_1, ACCOUNT_2]);
+    let mut template =
+        init_with_account_registry_mode("migrate_account_registry", Some(Bytes::from(proof)), MODE_MIGRATING);
+
+    // cell_deps
+    push_dep_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": ACCOUNT_1,
+            },
+            "witness": {
+                "account": ACCOUNT_1,
+            }
+        }),
+    );
+    push_dep_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": ACCOUNT_2,
+            },
+            "witness": {
+                "account": ACCOUNT_2,
+            }
+        }),
+    );
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_registry_migrate_not_started() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[], &[ACCOUNT_1]);
+    // Simulate migrating accounts when the AccountRegistryCell is off in ConfigCellMain.
+    let mut template = init_with_account_registry_mode("migrate_account_registry", Some(Bytes::from(proof)), None);
+
+    // cell_deps
+    push_dep_account_cell(&mut template, json!({}));
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryIsNotEnabled);
+}
+
+#[test]
+fn challenge_account_registry_migrate_account_already_migrated() {
+    // Simulate migrating an account which is already in the registry.
+    let prev_root = gen_registry_root(&[ACCOUNT_1]);
+    let (_, current_root, proof) = gen_registry_transition(&[], &[ACCOUNT_1]);
+    let mut template =
+        init_with_account_registry_mode("migrate_account_registry", Some(Bytes::from(proof)), MODE_MIGRATING);
+
+    // cell_deps
+    push_dep_account_cell(&mut template, json!({}));
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    challenge_tx(
+        template.as_json(),
+        PreAccountCellErrorCode::AccountAlreadyExistOrProofInvalid,
+    );
+}
+
+#[test]
+fn test_account_registry_finish_migration() {
+    let root = gen_registry_root(&[ACCOUNT_1, ACCOUNT_2]);
+    let mut template = init_with_account_registry_mode("finish_account_registry_migration", None, MODE_MIGRATING);
+
+    // inputs
+    push_input_normal_cell(&mut template, 0, SUPER_LOCK_ARGS);
+    push_account_registry_cell(&mut template, root, None, Source::Input);
+
+    // outputs
+    push_account_registry_cell(&mut template, root, Some(true), Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_registry_finish_migration_without_super_lock() {
+    let root = gen_registry_root(&[ACCOUNT_1, ACCOUNT_2]);
+    let mut template = init_with_account_registry_mode("finish_account_registry_migration", None, MODE_MIGRATING);
+
+    // inputs
+    push_input_normal_cell(&mut template, 0, OWNER_WITHOUT_TYPE);
+    push_account_registry_cell(&mut template, root, None, Source::Input);
+
+    // outputs
+    push_account_registry_cell(&mut template, root, Some(true), Source::Output);
+
+    challenge_tx(template.as_json(), ErrorCode::SuperLockIsRequired);
+}
+
+#[test]
+fn challenge_account_registry_finish_migration_root_changed() {
+    let (prev_root, current_root, _) = gen_registry_transition(&[ACCOUNT_1], &[ACCOUNT_2]);
+    let mut template = init_with_account_registry_mode("finish_account_registry_migration", None, MODE_MIGRATING);
+
+    // inputs
+    push_input_normal_cell(&mut template, 0, SUPER_LOCK_ARGS);
+    push_account_registry_cell(&mut template, prev_root, None, Source::Input);
+
+    // outputs
+    // Simulate inserting accounts without any proof when finishing the migration.
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError);
+}
+
+#[test]
+fn challenge_account_registry_migrate_with_flag_changed() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[], &[ACCOUNT_1]);
+    let mut template =
+        init_with_account_registry_mode("migrate_account_registry", Some(Bytes::from(proof)), MODE_MIGRATING);
+
+    // cell_deps
+    push_dep_account_cell(&mut template, json!({}));
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    // Simulate marking the migration as finished without the super lock.
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError);
+}
+
+#[test]
+fn test_pre_register_with_account_registry() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    let mut template = before_each_pre_register(MODE_ENABLED, proof);
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_pre_register_with_account_registry_migrating() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    let mut template = before_each_pre_register(MODE_MIGRATING, proof);
+
+    // cell_deps
+    push_dep_simple_account_cell(&mut template);
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_before_migration_started() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    // Simulate registering before the migration_started_at in ConfigCellMain.
+    let mut template = init_with_config_main(
+        "pre_register",
+        Some(gen_pre_register_params(PAYMENT_MODE_CAPACITY, proof)),
+        Some((ACCOUNT_REGISTRY_MODE_MIGRATING, TIMESTAMP + 1)),
+        Vec::new(),
+        None,
+        Vec::new(),
+        None,
+    );
+    template.push_config_cell_derived_by_account(ACCOUNT_SP_1, Source::CellDep);
+
+    // cell_deps
+    push_dep_simple_account_cell(&mut template);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryIsNotEnabled);
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_migrating_without_account_cell() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    // Simulate registering with the AccountRegistryCell only while it is still migrating.
+    let mut template = before_each_pre_register(MODE_MIGRATING, proof);
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure);
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_migrating_without_registry() {
+    let (_, _, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    let mut template = before_each_pre_register(MODE_MIGRATING, proof);
+
+    // cell_deps
+    push_dep_simple_account_cell(&mut template);
+
+    // outputs
+    // Simulate skipping the insertion of the account while the AccountRegistryCell is migrating.
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure);
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_migration_not_finished() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    let mut template = before_each_pre_register(MODE_ENABLED, proof);
+
+    // inputs
+    // Simulate trusting the AccountRegistryCell before all the existing accounts are migrated.
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryIsNotEnabled);
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_not_enabled() {
+    let (prev_root, current_root, proof) = gen_registry_transition(&[ACCOUNT_1, ACCOUNT_2], &[ACCOUNT_SP_1]);
+    // Simulate registering with the AccountRegistryCell while it is off in ConfigCellMain.
+    let mut template = before_each_pre_register(None, proof);
+
+    // cell_deps
+    push_dep_simple_account_cell(&mut template);
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure);
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_enabled_without_registry() {
+    let mut template = before_each_pre_register(MODE_ENABLED, Vec::new());
+
+    // cell_deps
+    push_dep_simple_account_cell(&mut template);
+
+    // outputs
+    // Simulate registering with the neighbouring AccountCell only after the AccountRegistryCell is enabled.
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure);
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_account_exist() {
+    // Simulate registering an account which is already in the registry.
+    let prev_root = gen_registry_root(&[ACCOUNT_1, ACCOUNT_SP_1]);
+    let (_, current_root, proof) = gen_registry_transition(&[ACCOUNT_1], &[ACCOUNT_SP_1]);
+    let mut template = before_each_pre_register(MODE_ENABLED, proof);
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    challenge_tx(
+        template.as_json(),
+        PreAccountCellErrorCode::AccountAlreadyExistOrProofInvalid,
+    );
+}
+
+#[test]
+fn challenge_pre_register_with_account_registry_root_not_updated() {
+    let (prev_root, _, proof) = gen_registry_transition(&[ACCOUNT_1], &[ACCOUNT_SP_1]);
+    let mut template = before_each_pre_register(MODE_ENABLED, proof);
+
+    // inputs
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    // Simulate forgetting to insert the account into the registry.
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Output);
+
+    challenge_tx(
+        template.as_json(),
+        PreAccountCellErrorCode::AccountAlreadyExistOrProofInvalid,
+    );
+}
+
+fn push_input_refundable_pre_account_cell(template: &mut TemplateGenerator, account: &str, created_at: u64) {
+    push_input_pre_account_cell(
+        template,
+        json!({
+            "capacity": 100_000_000_000u64,
+            "witness": {
+                "account": account,
+                "refund_lock": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": OWNER_WITHOUT_TYPE
+                },
+                "created_at": created_at,
+            }
+        }),
+        *SINCE_1_D,
+    );
+}
+
+#[test]
+fn test_pre_register_refund_with_account_registry() {
+    let (prev_root, current_root, proof) =
+        gen_registry_removal(&[ACCOUNT_1, ACCOUNT_2, ACCOUNT_3], &[ACCOUNT_1, ACCOUNT_2]);
+    let mut template = init_for_refund_with_account_registry_mode(Some(Bytes::from(proof)), MODE_ENABLED);
+
+    // inputs
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, TIMESTAMP);
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_2, TIMESTAMP);
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_normal_cell(&mut template, 200_000_000_000, OWNER_WITHOUT_TYPE);
+    push_account_registry_cell(&mut template, current_root, Some(true), Source::Output);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_pre_register_refund_with_account_registry_created_before_migration() {
+    // The PreAccountCells created before the migration starts have never been inserted into the registry.
+    let root = gen_registry_root(&[ACCOUNT_3]);
+    let mut template = init_for_refund_with_account_registry_mode(None, MODE_MIGRATING);
+
+    // inputs
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, ACCOUNT_REGISTRY_MIGRATION_STARTED_AT - 1);
+    push_account_registry_cell(&mut template, root, Some(false), Source::Input);
+
+    // outputs
+    push_output_normal_cell(&mut template, 100_000_000_000, OWNER_WITHOUT_TYPE);
+    push_account_registry_cell(&mut template, root, Some(false), Source::Output);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_pre_register_refund_with_account_registry_mixed() {
+    // Only the account of the PreAccountCell created after the migration started is removed.
+    let (prev_root, current_root, proof) = gen_registry_removal(&[ACCOUNT_2, ACCOUNT_3], &[ACCOUNT_2]);
+    let mut template = init_for_refund_with_account_registry_mode(Some(Bytes::from(proof)), MODE_MIGRATING);
+
+    // inputs
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, ACCOUNT_REGISTRY_MIGRATION_STARTED_AT - 1);
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_2, TIMESTAMP);
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_output_normal_cell(&mut template, 200_000_000_000, OWNER_WITHOUT_TYPE);
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_pre_register_refund_with_account_registry_created_before_migration_removed() {
+    // Simulate the account of a stale PreAccountCell has been registered again after the migration started.
+    let (prev_root, current_root, proof) = gen_registry_removal(&[ACCOUNT_1, ACCOUNT_3], &[ACCOUNT_1]);
+    let mut template = init_for_refund_with_account_registry_mode(Some(Bytes::from(proof)), MODE_MIGRATING);
+
+    // inputs
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, ACCOUNT_REGISTRY_MIGRATION_STARTED_AT - 1);
+    push_account_registry_cell(&mut template, prev_root, Some(false), Source::Input);
+
+    // outputs
+    push_output_normal_cell(&mut template, 100_000_000_000, OWNER_WITHOUT_TYPE);
+    // Simulate removing the account of the newer registration when refunding the stale PreAccountCell.
+    push_account_registry_cell(&mut template, current_root, Some(false), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError)
+}
+
+#[test]
+fn challenge_pre_register_refund_without_account_registry() {
+    let mut template = init_for_refund_with_account_registry_mode(None, MODE_ENABLED);
+
+    // inputs
+    // Simulate refunding the PreAccountCell without removing its account from the registry.
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, TIMESTAMP);
+
+    // outputs
+    push_output_normal_cell(&mut template, 100_000_000_000, OWNER_WITHOUT_TYPE);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
+
+#[test]
+fn challenge_pre_register_refund_with_account_registry_not_removed() {
+    let (prev_root, _, proof) = gen_registry_removal(&[ACCOUNT_1, ACCOUNT_3], &[ACCOUNT_1]);
+    let mut template = init_for_refund_with_account_registry_mode(Some(Bytes::from(proof)), MODE_ENABLED);
+
+    // inputs
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, TIMESTAMP);
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Input);
+
+    // outputs
+    push_output_normal_cell(&mut template, 100_000_000_000, OWNER_WITHOUT_TYPE);
+    // Simulate keeping the account in the registry after its PreAccountCell is refunded.
+    push_account_registry_cell(&mut template, prev_root, Some(true), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError)
+}
+
+#[test]
+fn challenge_pre_register_refund_with_account_registry_not_inserted() {
+    // Simulate refunding a PreAccountCell created after the migration started, but its account is not in the registry.
+    let root = gen_registry_root(&[ACCOUNT_3]);
+    let (_, _, proof) = gen_registry_removal(&[ACCOUNT_3], &[ACCOUNT_1]);
+    let mut template = init_for_refund_with_account_registry_mode(Some(Bytes::from(proof)), MODE_MIGRATING);
+
+    // inputs
+    push_input_refundable_pre_account_cell(&mut template, ACCOUNT_1, TIMESTAMP);
+    push_account_registry_cell(&mut template, root, Some(false), Source::Input);
+
+    // outputs
+    push_output_normal_cell(&mut template, 100_000_000_000, OWNER_WITHOUT_TYPE);
+    push_account_registry_cell(&mut template, root, Some(false), Source::Output);
+
+    challenge_tx(template.as_json(), PreAccountCellErrorCode::AccountRegistryCellError)
+}
@@ -1,4 +1,5 @@
 use das_types_std::constants::*;
+use das_types_std::packed::*;
 use lazy_static::lazy_static;
 use serde_json::json;
 
@@ -9,6 +10,8 @@ use crate::util::template_generator::*;
 
 pub const ACCOUNT_SP_1: &str = "✨das🎉001.bit";
 pub const INPUT_CAPACITY_OF_REFUND_LOCK: u64 = 6_100_000_000;
+pub const PAYMENT_MODE_CAPACITY: u8 = 0;
+pub const PAYMENT_MODE_STABLECOIN: u8 = 1;
 
 lazy_static! {
     pub static ref SINCE_1_D: Option<u64> = gen_since(SinceFlag::Relative, SinceFlag::Timestamp, DAY_SEC);
@@ -16,7 +19,36 @@ lazy_static! {
 }
 
 pub fn init() -> TemplateGenerator {
-    let mut template = TemplateGenerator::new("pre_register", None);
+    init_with_account_registry_mode("pre_register", None, None)
+}
+
+pub fn init_with_account_registry_mode(action: &str, params_opt: Option<Bytes>, mode: Option<u8>) -> TemplateGenerator {
+    init_with_config_main(
+        action,
+        params_opt,
+        mode.map(|mode| (mode, ACCOUNT_REGISTRY_MIGRATION_STARTED_AT)),
+        Vec::new(),
+        None,
+        Vec::new(),
+        None,
+    )
+}
+
+pub fn init_with_config_main(
+    action: &str,
+    params_opt: Option<Bytes>,
+    account_registry_settings: Option<(u8, u64)>,
+    paused_actions: Vec<&'static str>,
+    oracle_settings: Option<(u64, u8, u32)>,
+    quote_oracles: Vec<u8>,
+    stablecoin_settings: Option<(Vec<u8>, u8)>,
+) -> TemplateGenerator {
+    let mut template = TemplateGenerator::new(action, params_opt);
+    template.config_main_account_registry_settings = account_registry_settings;
+    template.config_main_paused_actions = paused_actions;
+    template.config_main_oracle_settings = oracle_settings;
+    template.config_main_quote_oracles = quote_oracles;
+    template.config_main_stablecoin_settings = stablecoin_settings;
 
     template.push_contract_cell("always_success", ContractType::DeployedContract);
     template.push_contract_cell("fake-das-lock", ContractType::DeployedContract);
@@ -25,8 +57,14 @@ pub fn init() -> TemplateGenerator {
     template.push_contract_cell("pre-account-cell-type", ContractType::Contract);
 
     template.push_oracle_cell(1, OracleCellType::Height, HEIGHT);
-    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
-    template.push_oracle_cell(1, OracleCellType::Quote, CKB_QUOTE);
+    // With the oracle settings, the QuoteCells should be pushed by the test itself, and the TimeCell need a header to check
+    // its freshness.
+    if oracle_settings.is_none() {
+        template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+        template.push_oracle_cell(1, OracleCellType::Quote, CKB_QUOTE);
+    } else {
+        template.push_oracle_cell_with_header(1, OracleCellType::Time, TIMESTAMP, TIMESTAMP);
+    }
 
     template.push_config_cell(DataType::ConfigCellAccount, Source::CellDep);
     template.push_config_cell(DataType::ConfigCellApply, Source::CellDep);
@@ -42,6 +80,11 @@ pub fn init() -> TemplateGenerator {
     template
 }
 
+/// Generate the params of pre_register, the first byte is the payment mode and the remaining bytes are the SMT proof.
+pub fn gen_pre_register_params(payment_mode: u8, proof: Vec<u8>) -> Bytes {
+    Bytes::from([vec![payment_mode], proof].concat())
+}
+
 pub fn init_with_timestamp(timestamp: u64) -> TemplateGenerator {
     let mut template = TemplateGenerator::new("pre_register", None);
 
@@ -72,7 +115,12 @@ pub fn init_with_timestamp(timestamp: u64) -> TemplateGenerator {
 }
 
 pub fn init_for_refund() -> TemplateGenerator {
-    let mut template = TemplateGenerator::new("refund_pre_register", None);
+    init_for_refund_with_account_registry_mode(None, None)
+}
+
+pub fn init_for_refund_with_account_registry_mode(params_opt: Option<Bytes>, mode: Option<u8>) -> TemplateGenerator {
+    let mut template = TemplateGenerator::new("refund_pre_register", params_opt);
+    template.config_main_account_registry_settings = mode.map(|mode| (mode, ACCOUNT_REGISTRY_MIGRATION_STARTED_AT));
 
     template.push_contract_cell("always_success", ContractType::DeployedContract);
     template.push_contract_cell("fake-das-lock", ContractType::DeployedContract);
@@ -86,7 +134,32 @@ pub fn init_for_refund() -> TemplateGenerator {
 }
 
 pub fn before_each(account: &str) -> TemplateGenerator {
-    let mut template = init();
+    before_each_with_paused_actions(account, Vec::new())
+}
+
+pub fn before_each_with_paused_actions(account: &str, paused_actions: Vec<&'static str>) -> TemplateGenerator {
+    let mut template = init_with_config_main("pre_register", None, None, paused_actions, None, Vec::new(), None);
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+
+    push_dep_simple_account_cell(&mut template);
+
+    template
+}
+
+pub fn before_each_with_oracle_settings(
+    account: &str,
+    oracle_settings: (u64, u8, u32),
+    quote_oracles: Vec<u8>,
+) -> TemplateGenerator {
+    let mut template = init_with_config_main(
+        "pre_register",
+        None,
+        None,
+        Vec::new(),
+        Some(oracle_settings),
+        quote_oracles,
+        None,
+    );
     template.push_config_cell_derived_by_account(account, Source::CellDep);
 
     push_dep_simple_account_cell(&mut template);
@@ -1,7 +1,9 @@
+mod account_registry;
 mod account_release;
 mod char_set;
 mod common;
 mod preserved_accounts;
 mod refund_pre_register;
 mod simple;
+mod stablecoin;
 mod unavailable_accounts;
@@ -123,6 +123,157 @@ fn test_pre_register_simple_v3() {
     test_tx(template.as_json());
 }
 
+#[test]
+fn test_pre_register_when_other_actions_paused() {
+    let mut template = before_each_with_paused_actions(ACCOUNT_SP_1, vec!["buy_account", "accept_offer"]);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_pre_register_action_paused() {
+    let mut template = before_each_with_paused_actions(ACCOUNT_SP_1, vec!["buy_account", "pre_register"]);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::ActionPaused);
+}
+
+#[test]
+fn test_pre_register_with_median_quote() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (0, 3, 500), vec![1, 2, 3]);
+    template.push_quote_cell_of_oracle(1, CKB_QUOTE - 10, None);
+    template.push_quote_cell_of_oracle(2, CKB_QUOTE + 30, None);
+    template.push_quote_cell_of_oracle(3, CKB_QUOTE, None);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_pre_register_with_fresh_quote() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (3600, 1, 500), vec![]);
+    template.push_oracle_cell_with_header(1, OracleCellType::Quote, CKB_QUOTE, TIMESTAMP - 3600);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_pre_register_quote_sources_not_enough() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (0, 3, 500), vec![1, 2, 3]);
+    template.push_quote_cell_of_oracle(1, CKB_QUOTE, None);
+    template.push_quote_cell_of_oracle(2, CKB_QUOTE, None);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::OracleCellIsRequired);
+}
+
+#[test]
+fn challenge_pre_register_quote_from_unknown_oracle() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (0, 2, 500), vec![1, 2]);
+    template.push_quote_cell_of_oracle(1, CKB_QUOTE, None);
+    // Simulate a QuoteCell of an oracle which is not configured, it should not be counted.
+    template.push_quote_cell_of_oracle(3, CKB_QUOTE, None);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::OracleCellIsRequired);
+}
+
+#[test]
+fn challenge_pre_register_quote_from_same_oracle() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (0, 2, 500), vec![1, 2]);
+    template.push_quote_cell_of_oracle(1, CKB_QUOTE, None);
+    // Simulate pushing multiple QuoteCells of the same oracle to control the median.
+    template.push_quote_cell_of_oracle(1, CKB_QUOTE, None);
+    template.push_quote_cell_of_oracle(2, CKB_QUOTE, None);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure);
+}
+
+#[test]
+fn challenge_pre_register_quote_deviation_too_large() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (0, 3, 500), vec![1, 2, 3]);
+    template.push_quote_cell_of_oracle(1, CKB_QUOTE, None);
+    template.push_quote_cell_of_oracle(2, CKB_QUOTE, None);
+    // Simulate a stuck QuoteCell which is far away from the others.
+    template.push_quote_cell_of_oracle(3, CKB_QUOTE * 2, None);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::OracleQuoteDeviationTooLarge);
+}
+
+#[test]
+fn challenge_pre_register_quote_stale() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (3600, 1, 500), vec![]);
+    // Simulate a QuoteCell which has not been updated for too long.
+    template.push_oracle_cell_with_header(1, OracleCellType::Quote, CKB_QUOTE, TIMESTAMP - 3601);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::OracleCellIsStale);
+}
+
+#[test]
+fn challenge_pre_register_time_cell_stale() {
+    let mut template = before_each_with_oracle_settings(ACCOUNT_SP_1, (3600, 1, 500), vec![]);
+    // Simulate the TimeCell has not been updated for too long, so it is much older than the QuoteCell.
+    template.push_oracle_cell_with_header(1, OracleCellType::Quote, CKB_QUOTE, TIMESTAMP + 3601);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::OracleCellIsStale);
+}
+
 #[test]
 fn challenge_pre_register_initial_record_key_invalid() {
     let mut template = before_each(ACCOUNT_SP_1);
@@ -0,0 +1,140 @@
+use das_types_std::constants::*;
+use serde_json::json;
+
+use super::common::*;
+use crate::util::accounts::*;
+use crate::util::constants::*;
+use crate::util::error::*;
+use crate::util::template_common_cell::*;
+use crate::util::template_generator::*;
+use crate::util::template_parser::*;
+use crate::util::{self};
+
+// The PreAccountCell below costs 4974 CKB, which is worth 4.974 USD at the CKB_QUOTE.
+const UDT_AMOUNT_OF_PRE_ACCOUNT_CELL: u128 = 4_974_000;
+
+fn before_each(stablecoin_settings: Option<(Vec<u8>, u8)>) -> TemplateGenerator {
+    let mut template = init_with_config_main(
+        "pre_register",
+        Some(gen_pre_register_params(PAYMENT_MODE_STABLECOIN, vec![])),
+        None,
+        Vec::new(),
+        None,
+        Vec::new(),
+        stablecoin_settings,
+    );
+    template.push_config_cell_derived_by_account(ACCOUNT_SP_1, Source::CellDep);
+
+    // cell_deps
+    push_dep_simple_account_cell(&mut template);
+
+    // inputs
+    push_input_simple_apply_register_cell(&mut template, ACCOUNT_SP_1);
+    push_input_udt_cell_of_refund_lock(&mut template, 10_000_000);
+
+    template
+}
+
+fn gen_refund_lock() -> serde_json::Value {
+    json!({
+        "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+        "args": OWNER_WITHOUT_TYPE
+    })
+}
+
+fn push_input_udt_cell_of_refund_lock(template: &mut TemplateGenerator, amount: u128) {
+    template.push_input(gen_udt_cell(amount, gen_refund_lock()), None, None);
+    template.push_empty_witness();
+}
+
+fn push_output_udt_cell_of_refund_lock(template: &mut TemplateGenerator, amount: u128) {
+    template.push_output(gen_udt_cell(amount, gen_refund_lock()), None);
+}
+
+fn push_output_simple_pre_account_cell(template: &mut TemplateGenerator) {
+    push_output_pre_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_register_fee_v2(ACCOUNT_SP_1, 8, true),
+            "witness": {
+                "account": ACCOUNT_SP_1,
+                "refund_lock": gen_refund_lock(),
+                "price": {
+                    "length": 8,
+                    "new": ACCOUNT_PRICE_5_CHAR,
+                    "renew": ACCOUNT_PRICE_5_CHAR
+                },
+                "inviter_id": "0x0000000000000000000000000000000000000000",
+                "inviter_lock": {
+                    "code_hash": "{{fake-das-lock}}",
+                    "args": gen_das_lock_args(INVITER, None)
+                },
+                "channel_lock": {
+                    "code_hash": "{{fake-das-lock}}",
+                    "args": gen_das_lock_args(CHANNEL, None)
+                },
+                "invited_discount": INVITED_DISCOUNT,
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_pre_register_with_stablecoin() {
+    let mut template = before_each(Some((gen_udt_type_hash(), 6)));
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_output_udt_cell_of_refund_lock(&mut template, 10_000_000 - UDT_AMOUNT_OF_PRE_ACCOUNT_CELL);
+    push_output_udt_cell_to_das_wallet(&mut template, UDT_AMOUNT_OF_PRE_ACCOUNT_CELL);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_pre_register_with_stablecoin_not_configured() {
+    // Simulate paying by stablecoin when there is no stablecoin settings in ConfigCellMain.
+    let mut template = before_each(None);
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_output_udt_cell_of_refund_lock(&mut template, 10_000_000 - UDT_AMOUNT_OF_PRE_ACCOUNT_CELL);
+    push_output_udt_cell_to_das_wallet(&mut template, UDT_AMOUNT_OF_PRE_ACCOUNT_CELL);
+
+    challenge_tx(template.as_json(), ErrorCode::StablecoinPaymentError);
+}
+
+#[test]
+fn challenge_pre_register_with_stablecoin_less_than_capacity() {
+    let mut template = before_each(Some((gen_udt_type_hash(), 6)));
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    // Simulate paying less stablecoin than the capacity of the PreAccountCell is worth.
+    push_output_udt_cell_of_refund_lock(&mut template, 10_000_000 - UDT_AMOUNT_OF_PRE_ACCOUNT_CELL + 1);
+    push_output_udt_cell_to_das_wallet(&mut template, UDT_AMOUNT_OF_PRE_ACCOUNT_CELL - 1);
+
+    challenge_tx(template.as_json(), ErrorCode::StablecoinPaymentError);
+}
+
+#[test]
+fn challenge_pre_register_with_stablecoin_not_received_by_das() {
+    let mut template = before_each(Some((gen_udt_type_hash(), 6)));
+
+    // outputs
+    push_output_simple_pre_account_cell(&mut template);
+    push_output_udt_cell_of_refund_lock(&mut template, 10_000_000 - UDT_AMOUNT_OF_PRE_ACCOUNT_CELL);
+    // Simulate transferring the stablecoin to someone else instead of the DAS wallet.
+    template.push_output(
+        gen_udt_cell(
+            UDT_AMOUNT_OF_PRE_ACCOUNT_CELL,
+            json!({
+                "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                "args": "0x0000000000000000000000000000000000009999"
+            }),
+        ),
+        None,
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::StablecoinPaymentError);
+}
@@ -419,6 +419,38 @@ fn test_proposal_confirm_create_income_cell() {
     test_tx(template.as_json());
 }
 
+#[test]
+fn challenge_proposal_confirm_account_cell_out_of_range() {
+    let mut template = before_each();
+
+    let account_cell_partial = json!({
+        "capacity": util::gen_account_cell_capacity(8),
+        "lock": {
+            "owner_lock_args": "0x000000000000000000000000000000000000003333",
+            "manager_lock_args": "0x000000000000000000000000000000000000003333"
+        },
+        "data": {
+            "account": "das00010.bit",
+            "next": "das00004.bit"
+        },
+        "witness": {
+            "account": "das00010.bit",
+            "status": (AccountStatus::Normal as u8)
+        }
+    });
+    // Simulate modifying an AccountCell which is out of the ranges of the proposal, it may belong to a parallel proposal.
+    push_input_account_cell(&mut template, account_cell_partial.clone());
+
+    // outputs
+    push_output_slice_0(&mut template);
+    push_output_slice_1(&mut template);
+    push_output_account_cell(&mut template, account_cell_partial);
+    push_output_income_cell_with_profit(&mut template);
+    push_output_normal_cell_with_refund(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::ProposalRelatedCellOutOfRange);
+}
+
 #[test]
 fn challenge_proposal_confirm_height() {
     let mut template = init_with_confirm();
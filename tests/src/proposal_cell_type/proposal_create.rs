@@ -1170,3 +1170,74 @@ fn challenge_proposal_create_exist_account_4() {
 
     challenge_tx(template.as_json(), ErrorCode::ProposalSliceItemMustBeUniqueAccount)
 }
+
+#[test]
+fn challenge_proposal_create_with_proposal_in_deps() {
+    let mut template = before_each();
+
+    // cell_deps
+    // Simulate carrying a parallel proposal in cell_deps, the parallel proposals are never trusted when creating a proposal.
+    push_dep_proposal_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "slices": [
+                    [
+                        {
+                            "account_id": "das00011.bit",
+                            "item_type": ProposalSliceItemType::Exist as u8,
+                            "next": "das00006.bit"
+                        },
+                        {
+                            "account_id": "das00006.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00019.bit"
+                        },
+                    ],
+                ]
+            }
+        }),
+    );
+
+    // outputs
+    push_output_proposal_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "slices": [
+                    [
+                        {
+                            "account_id": "das00012.bit",
+                            "item_type": ProposalSliceItemType::Exist as u8,
+                            "next": "das00009.bit"
+                        },
+                        {
+                            "account_id": "das00009.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00002.bit"
+                        },
+                        {
+                            "account_id": "das00002.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00013.bit"
+                        },
+                    ],
+                    [
+                        {
+                            "account_id": "das00004.bit",
+                            "item_type": ProposalSliceItemType::Exist as u8,
+                            "next": "das00018.bit"
+                        },
+                        {
+                            "account_id": "das00018.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00011.bit"
+                        },
+                    ]
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
@@ -1,38 +1,40 @@
 use das_types_std::constants::*;
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::common::*;
 use crate::util::accounts::*;
 use crate::util::constants::*;
+use crate::util::error::*;
 use crate::util::template_common_cell::*;
 use crate::util::template_generator::TemplateGenerator;
 use crate::util::template_parser::*;
 
 fn before_each() -> TemplateGenerator {
+    before_each_with_prev_proposal(json!({
+        "witness": {
+            "slices": [
+                [
+                    {
+                        "account_id": "das00012.bit",
+                        "item_type": ProposalSliceItemType::Exist as u8,
+                        "next": "das00005.bit"
+                    },
+                    {
+                        "account_id": "das00005.bit",
+                        "item_type": ProposalSliceItemType::New as u8,
+                        "next": "das00013.bit"
+                    },
+                ],
+            ]
+        }
+    }))
+}
+
+fn before_each_with_prev_proposal(prev_proposal: Value) -> TemplateGenerator {
     let mut template = init("extend_proposal");
 
     // cell_deps
-    push_dep_proposal_cell(
-        &mut template,
-        json!({
-            "witness": {
-                "slices": [
-                    [
-                        {
-                            "account_id": "das00012.bit",
-                            "item_type": ProposalSliceItemType::Exist as u8,
-                            "next": "das00005.bit"
-                        },
-                        {
-                            "account_id": "das00005.bit",
-                            "item_type": ProposalSliceItemType::New as u8,
-                            "next": "das00013.bit"
-                        },
-                    ],
-                ]
-            }
-        }),
-    );
+    push_dep_proposal_cell(&mut template, prev_proposal);
     // slices[0]
     push_dep_pre_account_cell(
         &mut template,
@@ -94,13 +96,9 @@ fn before_each() -> TemplateGenerator {
     template
 }
 
-#[test]
-fn test_proposal_extend() {
-    let mut template = before_each();
-
-    // outputs
+fn push_output_extended_proposal_cell(template: &mut TemplateGenerator) {
     push_output_proposal_cell(
-        &mut template,
+        template,
         json!({
             "witness": {
                 "slices": [
@@ -137,6 +135,76 @@ fn test_proposal_extend() {
             }
         }),
     );
+}
+
+#[test]
+fn test_proposal_extend() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_extended_proposal_cell(&mut template);
 
     test_tx(template.as_json());
 }
+
+#[test]
+fn challenge_proposal_extend_with_multiple_proposals() {
+    let mut template = before_each();
+
+    // cell_deps
+    // Simulate carrying a parallel proposal in cell_deps, so which one is extended is ambiguous.
+    push_dep_proposal_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "slices": [
+                    [
+                        {
+                            "account_id": "das00011.bit",
+                            "item_type": ProposalSliceItemType::Exist as u8,
+                            "next": "das00006.bit"
+                        },
+                        {
+                            "account_id": "das00006.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00019.bit"
+                        },
+                    ],
+                ]
+            }
+        }),
+    );
+
+    // outputs
+    push_output_extended_proposal_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::InvalidTransactionStructure)
+}
+
+#[test]
+fn challenge_proposal_extend_unrelated_proposal() {
+    // Simulate extending a proposal which does not contain the proposed item das00005.bit.
+    let mut template = before_each_with_prev_proposal(json!({
+        "witness": {
+            "slices": [
+                [
+                    {
+                        "account_id": "das00011.bit",
+                        "item_type": ProposalSliceItemType::Exist as u8,
+                        "next": "das00006.bit"
+                    },
+                    {
+                        "account_id": "das00006.bit",
+                        "item_type": ProposalSliceItemType::New as u8,
+                        "next": "das00019.bit"
+                    },
+                ],
+            ]
+        }
+    }));
+
+    // outputs
+    push_output_extended_proposal_cell(&mut template);
+
+    challenge_tx(template.as_json(), ErrorCode::PrevProposalItemNotFound)
+}
@@ -18,7 +18,16 @@ pub const SCRIPT_ARGS: &str = "0x0011223300";
 pub const DUMMY_CHANNEL: &str = "0x00000000000000000000000000000000000000000000000000000000";
 
 pub fn init(action: &str, params_opt: Option<&str>) -> TemplateGenerator {
+    init_with_extra_type_id_table(action, params_opt, Vec::new())
+}
+
+pub fn init_with_extra_type_id_table(
+    action: &str,
+    params_opt: Option<&str>,
+    extra_type_id_table: Vec<&'static str>,
+) -> TemplateGenerator {
     let mut template = TemplateGenerator::new(action, params_opt.map(|raw| Bytes::from(util::hex_to_bytes(raw))));
+    template.config_main_extra_type_id_table = extra_type_id_table;
 
     template.push_contract_cell("always_success", ContractType::DeployedContract);
     template.push_contract_cell("fake-das-lock", ContractType::DeployedContract);
@@ -45,12 +54,17 @@ pub fn init_config(action: &str, params_opt: Option<&str>) -> TemplateGenerator
 }
 
 pub fn init_update() -> TemplateGenerator {
-    let mut template = init("update_sub_account", None);
+    init_update_with_extra_type_id_table(Vec::new())
+}
+
+pub fn init_update_with_extra_type_id_table(extra_type_id_table: Vec<&'static str>) -> TemplateGenerator {
+    let mut template = init_with_extra_type_id_table("update_sub_account", None, extra_type_id_table);
 
     template.push_contract_cell("ckb_sign.so", ContractType::SharedLib);
     template.push_contract_cell("eth_sign.so", ContractType::SharedLib);
     template.push_contract_cell("tron_sign.so", ContractType::SharedLib);
     template.push_contract_cell("doge_sign.so", ContractType::SharedLib);
+    template.push_contract_cell("ed25519_sign.so", ContractType::SharedLib);
     template.push_contract_cell("secp256k1_data", ContractType::DeployedSharedLib);
 
     template.push_config_cell(DataType::ConfigCellCharSetEmoji, Source::CellDep);
@@ -310,7 +310,10 @@ fn challenge_sub_account_edit_spend_balance_cell_1() {
     );
     push_simple_output_sub_account_cell(&mut template, 0, 0);
 
-    challenge_tx(template.as_json(), SubAccountCellErrorCode::SomeCellWithDasLockMayBeAbused);
+    challenge_tx(
+        template.as_json(),
+        SubAccountCellErrorCode::SomeCellWithDasLockMayBeAbused,
+    );
 }
 
 #[test]
@@ -352,7 +355,10 @@ fn challenge_sub_account_edit_spend_balance_cell_2() {
     );
     push_simple_output_sub_account_cell(&mut template, 0, 0);
 
-    challenge_tx(template.as_json(), SubAccountCellErrorCode::SomeCellWithDasLockMayBeAbused);
+    challenge_tx(
+        template.as_json(),
+        SubAccountCellErrorCode::SomeCellWithDasLockMayBeAbused,
+    );
 }
 
 /// If the transaction only contains edit action, then the owner_profit must be consistent.
@@ -608,3 +614,104 @@ fn challenge_sub_account_edit_has_expired() {
 
     challenge_tx(template.as_json(), SubAccountCellErrorCode::AccountHasInGracePeriod);
 }
+
+// The owner_lock_args of a BTC P2WPKH das-lock, it is type(1) + addr_type(1) + pubkey_hash(20).
+const BTC_OWNER: &str = "0x09010000000000000000000000000000000000009999";
+
+fn before_each_with_btc_owner() -> TemplateGenerator {
+    let mut template =
+        init_update_with_extra_type_id_table(vec!["webauthn_sign.so", "device-key-list-cell-type", "btc_sign.so"]);
+
+    // cell_deps
+    push_simple_dep_account_cell(&mut template);
+
+    // inputs
+    template.restore_sub_account(vec![json!({
+        "lock": {
+            "owner_lock_args": BTC_OWNER,
+            "manager_lock_args": MANAGER_1
+        },
+        "account": SUB_ACCOUNT_1,
+        "suffix": SUB_ACCOUNT_SUFFIX,
+        "registered_at": TIMESTAMP,
+        "expired_at": TIMESTAMP + YEAR_SEC,
+    })]);
+    push_simple_input_sub_account_cell(&mut template, 0, 0);
+
+    template
+}
+
+#[test]
+fn test_sub_account_edit_by_btc_owner() {
+    let mut template = before_each_with_btc_owner();
+
+    // outputs
+    push_simple_sub_account_witness(
+        &mut template,
+        json!({
+            "sub_account": {
+                "lock": {
+                    "owner_lock_args": BTC_OWNER,
+                    "manager_lock_args": MANAGER_1
+                },
+                "account": SUB_ACCOUNT_1,
+            },
+            "edit_key": "manager",
+            // Simulate modifying manager by the BTC owner.
+            "edit_value": gen_das_lock_args(BTC_OWNER, Some(MANAGER_2))
+        }),
+    );
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_sub_account_edit_owner_to_btc() {
+    let mut template = before_each();
+
+    // outputs
+    push_simple_sub_account_witness(
+        &mut template,
+        json!({
+            "sub_account": {
+                "lock": {
+                    "owner_lock_args": OWNER_1,
+                    "manager_lock_args": MANAGER_1
+                },
+                "account": SUB_ACCOUNT_1,
+            },
+            "edit_key": "owner",
+            // Simulate transferring the sub-account to a BTC owner.
+            "edit_value": gen_das_lock_args(BTC_OWNER, Some(BTC_OWNER))
+        }),
+    );
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_sub_account_edit_btc_owner_changed_when_edit_manager() {
+    let mut template = before_each_with_btc_owner();
+
+    // outputs
+    push_simple_sub_account_witness(
+        &mut template,
+        json!({
+            "sub_account": {
+                "lock": {
+                    "owner_lock_args": BTC_OWNER,
+                    "manager_lock_args": MANAGER_1
+                },
+                "account": SUB_ACCOUNT_1,
+            },
+            "edit_key": "manager",
+            // Simulate changing the last byte of the 20 bytes pubkey_hash of the BTC owner when editing manager.
+            "edit_value": gen_das_lock_args("0x09010000000000000000000000000000000000009998", Some(MANAGER_2))
+        }),
+    );
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    challenge_tx(template.as_json(), SubAccountCellErrorCode::SubAccountEditLockError);
+}
@@ -0,0 +1,242 @@
+use ckb_hash::blake2b_256;
+use ckb_types::prelude::Pack;
+use das_types_std::constants::*;
+use das_types_std::packed::{Byte10, DeviceKey, DeviceKeyList, DeviceKeyListCellData, Script};
+use das_types_std::prelude::*;
+use serde_json::json;
+
+use super::common::*;
+use crate::device_key_list_cell_type::DeviceKeyListCell;
+use crate::util::accounts::*;
+use crate::util::constants::*;
+use crate::util::error::*;
+use crate::util::template_generator::*;
+use crate::util::template_parser::*;
+use crate::util::{self};
+
+// The pubkey of the device which signs in these tests, the signature itself is not verified in development mode.
+const DEVICE_PUBKEY: &str = "0x4dbfb3cfd65ad8501e2e84accd50bcd0d3065da1167b8aaff4d420bff506c9053c9defc5d88fa20f6e04ea80ad11fb5f126019a102383d4dff153df50749be21";
+const AUTHENTICATOR_DATA: &str = "0x49960de5880e8c687434170f6476605b8fe4aeb9a28632c7995cf3ba831d97630500000001";
+const BASE64URL_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const WEBAUTHN_SUB_ALG_ID: &str = "07";
+const WEBAUTHN_CID: &str = "00000000000000000001";
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut ret = String::new();
+    for chunk in bytes.chunks(3) {
+        let n =
+            (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..=chunk.len() {
+            ret.push(BASE64URL_CHARS[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    ret
+}
+
+/// The pubkey_hash of WebAuthn is the first 10 bytes of the pubkey hashed by blake2b for 5 times.
+fn to_pubkey_hash(pubkey: &[u8]) -> Vec<u8> {
+    let mut hash = blake2b_256(pubkey);
+    for _ in 1..5 {
+        hash = blake2b_256(hash);
+    }
+
+    hash[..10].to_vec()
+}
+
+/// Generate the owner_lock_args of a WebAuthn das-lock, it is type(1) + sub_alg_id(1) + pubkey_hash(10) + cid(10).
+fn gen_webauthn_lock_args(pubkey_hash: &[u8]) -> String {
+    format!(
+        "0x{:02x}{}{}{}",
+        DasLockType::WebAuthn as u8,
+        WEBAUTHN_SUB_ALG_ID,
+        hex::encode(pubkey_hash),
+        WEBAUTHN_CID
+    )
+}
+
+/// Generate the WebAuthn signature which is signature(64) + pubkey(64) + authenticator_data_len(2) + authenticator_data +
+/// client_data_json, the challenge in client_data_json is the digest of the sub-account edit.
+fn gen_webauthn_signature(digest: &[u8]) -> String {
+    let authenticator_data = util::hex_to_bytes(AUTHENTICATOR_DATA);
+    let client_data_json = format!(
+        r#"{{"type":"webauthn.get","challenge":"{}","origin":"http://localhost:8080","crossOrigin":false}}"#,
+        base64url_encode(digest)
+    );
+    let signature = [
+        vec![0u8; 64],
+        util::hex_to_bytes(DEVICE_PUBKEY),
+        (authenticator_data.len() as u16).to_le_bytes().to_vec(),
+        authenticator_data,
+        client_data_json.into_bytes(),
+    ]
+    .concat();
+
+    format!("0x{}", hex::encode(signature))
+}
+
+/// The digest of the sub-account edit is blake2b(account_id + edit_key + edit_value + nonce + sign_expired_at).
+fn gen_edit_digest(account: &str, edit_key: &str, edit_value: &str, nonce: u64, sign_expired_at: u64) -> [u8; 32] {
+    blake2b_256(
+        [
+            util::account_to_id(account),
+            edit_key.as_bytes().to_vec(),
+            util::hex_to_bytes(edit_value),
+            nonce.to_le_bytes().to_vec(),
+            sign_expired_at.to_le_bytes().to_vec(),
+        ]
+        .concat(),
+    )
+}
+
+fn before_each(owner_lock_args: &str, extra_type_id_table: Vec<&'static str>) -> TemplateGenerator {
+    let mut template = init_update_with_extra_type_id_table(extra_type_id_table);
+
+    // cell_deps
+    push_simple_dep_account_cell(&mut template);
+
+    // inputs
+    template.restore_sub_account(vec![json!({
+        "lock": {
+            "owner_lock_args": owner_lock_args,
+            "manager_lock_args": MANAGER_1
+        },
+        "account": SUB_ACCOUNT_1,
+        "suffix": SUB_ACCOUNT_SUFFIX,
+        "registered_at": TIMESTAMP,
+        "expired_at": TIMESTAMP + YEAR_SEC,
+    })]);
+    push_simple_input_sub_account_cell(&mut template, 0, 0);
+
+    template
+}
+
+fn push_dep_device_key_list_cell(template: &mut TemplateGenerator, owner_lock_args: &str, pubkey_hashes: &[Vec<u8>]) {
+    let mut keys = DeviceKeyList::new_builder();
+    for pubkey_hash in pubkey_hashes {
+        keys = keys.push(
+            DeviceKey::new_builder()
+                .pubkey(Byte10::from_slice(pubkey_hash).unwrap())
+                .build(),
+        );
+    }
+    let witness = DeviceKeyListCellData::new_builder()
+        .refund_lock(Script::default())
+        .keys(keys.build())
+        .build();
+
+    let lock_args = util::hex_to_bytes(&gen_das_lock_args(owner_lock_args, Some(owner_lock_args)));
+    DeviceKeyListCell::default_new(161 * 10u64.pow(8), lock_args.pack(), witness).push(template, Source::CellDep);
+}
+
+fn push_webauthn_sub_account_witness(template: &mut TemplateGenerator, owner_lock_args: &str, signature: &str) {
+    let edit_value = gen_das_lock_args(owner_lock_args, Some(MANAGER_2));
+    template.push_sub_account_witness_v2(json!({
+        "action": SubAccountAction::Edit.to_string(),
+        "signature": signature,
+        "sign_role": "0x00",
+        "sign_expired_at": TIMESTAMP,
+        "sub_account": {
+            "lock": {
+                "owner_lock_args": owner_lock_args,
+                "manager_lock_args": MANAGER_1
+            },
+            "account": SUB_ACCOUNT_1,
+            "suffix": SUB_ACCOUNT_SUFFIX,
+            "registered_at": TIMESTAMP,
+            "expired_at": TIMESTAMP + YEAR_SEC,
+        },
+        "edit_key": "manager",
+        // Simulate modifying manager.
+        "edit_value": edit_value
+    }));
+}
+
+fn gen_valid_signature(owner_lock_args: &str) -> String {
+    let edit_value = gen_das_lock_args(owner_lock_args, Some(MANAGER_2));
+    let digest = gen_edit_digest(SUB_ACCOUNT_1, "manager", &edit_value, 0, TIMESTAMP);
+
+    gen_webauthn_signature(&digest)
+}
+
+#[test]
+fn test_sub_account_edit_by_webauthn_device() {
+    // Simulate signing with the device in das-lock args.
+    let owner = gen_webauthn_lock_args(&to_pubkey_hash(&util::hex_to_bytes(DEVICE_PUBKEY)));
+    let mut template = before_each(&owner, vec![]);
+
+    // outputs
+    push_webauthn_sub_account_witness(&mut template, &owner, &gen_valid_signature(&owner));
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_sub_account_edit_by_webauthn_device_in_key_list() {
+    // Simulate signing with another device of the owner which is in the DeviceKeyListCell.
+    let owner = gen_webauthn_lock_args(&[1u8; 10]);
+    let mut template = before_each(&owner, vec!["webauthn_sign.so", "device-key-list-cell-type"]);
+
+    // cell_deps
+    push_dep_device_key_list_cell(
+        &mut template,
+        &owner,
+        &[to_pubkey_hash(&util::hex_to_bytes(DEVICE_PUBKEY))],
+    );
+
+    // outputs
+    push_webauthn_sub_account_witness(&mut template, &owner, &gen_valid_signature(&owner));
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_sub_account_edit_by_webauthn_challenge_mismatched() {
+    let owner = gen_webauthn_lock_args(&to_pubkey_hash(&util::hex_to_bytes(DEVICE_PUBKEY)));
+    let mut template = before_each(&owner, vec![]);
+
+    // outputs
+    // Simulate the challenge in clientDataJSON is not the digest of the sub-account edit.
+    push_webauthn_sub_account_witness(&mut template, &owner, &gen_webauthn_signature(&[0u8; 32]));
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    challenge_tx(template.as_json(), ErrorCode::WebAuthnSignatureError)
+}
+
+#[test]
+fn challenge_sub_account_edit_by_webauthn_device_not_in_key_list() {
+    let owner = gen_webauthn_lock_args(&[1u8; 10]);
+    let mut template = before_each(&owner, vec!["webauthn_sign.so", "device-key-list-cell-type"]);
+
+    // cell_deps
+    // Simulate the device which signs is not in the DeviceKeyListCell.
+    push_dep_device_key_list_cell(&mut template, &owner, &[vec![2u8; 10]]);
+
+    // outputs
+    push_webauthn_sub_account_witness(&mut template, &owner, &gen_valid_signature(&owner));
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    challenge_tx(template.as_json(), ErrorCode::WebAuthnSignatureError)
+}
+
+#[test]
+fn challenge_sub_account_edit_by_webauthn_key_list_not_configured() {
+    let owner = gen_webauthn_lock_args(&[1u8; 10]);
+    // Simulate the type ID of DeviceKeyListCell is not configured in ConfigCellMain.
+    let mut template = before_each(&owner, vec![]);
+
+    // cell_deps
+    push_dep_device_key_list_cell(
+        &mut template,
+        &owner,
+        &[to_pubkey_hash(&util::hex_to_bytes(DEVICE_PUBKEY))],
+    );
+
+    // outputs
+    push_webauthn_sub_account_witness(&mut template, &owner, &gen_valid_signature(&owner));
+    push_simple_output_sub_account_cell(&mut template, 0, 0);
+
+    challenge_tx(template.as_json(), ErrorCode::WebAuthnSignatureError)
+}
@@ -7,6 +7,7 @@ mod create_flag_custom_rule;
 mod create_flag_custom_script;
 mod create_flag_manual;
 mod edit_sub_account;
+mod edit_sub_account_by_webauthn;
 mod recycle_sub_account;
 mod renew_by_custom_rule;
 mod renew_by_manual_other;
@@ -34,6 +34,11 @@ pub const TIMESTAMP: u64 = 1611200090u64;
 pub const TIMESTAMP_20221018: u64 = 1666094400u64;
 pub const HEIGHT: u64 = 1000000u64;
 
+pub const CONFIG_CELL_ACTIVATION_DELAY: u64 = 3 * 86400;
+
+pub const ACCOUNT_STATUS_PENDING_TRANSFER: u8 = 4;
+pub const ACCOUNT_TRANSFER_ACCEPTANCE_WINDOW: u64 = 7 * 86400;
+
 pub const PRE_ACCOUNT_TIMEOUT_LIMIT: u64 = 86400;
 pub const PRE_ACCOUNT_SHORT_TIMEOUT_LIMIT: u64 = 3600;
 pub const PRE_ACCOUNT_REFUND_AVAILABLE_FEE: u64 = 86400;
@@ -91,6 +96,13 @@ pub const DAS_WALLET_LOCK_ARGS: &str = "0x03000000000000000000000000000000000000
 pub const QUOTE_LOCK_ARGS: &str = "0x0100000000000000000000000000000000000000";
 pub const PROFIT_LOCK_ARGS: &str = "0x0400000000000000000000000000000000000000";
 pub const DUMMY_LOCK_ARGS: &str = "0xff00000000000000000000000000000000000000";
+// The hex of b"account-registry".
+pub const ACCOUNT_REGISTRY_CELL_ARGS: &str = "0x6163636f756e742d7265676973747279";
+// The modes of the AccountRegistryCell in ConfigCellMain.
+pub const ACCOUNT_REGISTRY_MODE_MIGRATING: u8 = 1;
+pub const ACCOUNT_REGISTRY_MODE_ENABLED: u8 = 2;
+// The PreAccountCells created before this time are not in the AccountRegistryCell.
+pub const ACCOUNT_REGISTRY_MIGRATION_STARTED_AT: u64 = TIMESTAMP - DAY_SEC;
 
 #[derive(Debug)]
 #[repr(u8)]
@@ -228,6 +240,14 @@ lazy_static! {
             "doge_sign.so",
             "0x0480767c09c1c26abe4e180d08238ed551f11dc293ab1286d8a7b7cac95e55a7",
         );
+        map.insert(
+            "webauthn_sign.so",
+            "0x3f8e0c9d5a7b1e24c6f0a9d83b5e7c1f2a4d6b8e0c2f4a6d8b0e2c4f6a8d0b2e",
+        );
+        map.insert(
+            "btc_sign.so",
+            "0x6d2b1f0e8a4c3b5d7e9f1a2c4b6d8e0f2a4c6e8b0d2f4a6c8e0b2d4f6a8c0e2b",
+        );
         map.insert(
             "device-key-list-cell-type",
             "0x982f5e9cc7503f4cd0d0e2ed7115b70f9007f6adcdbdefb5465228d39c94f12a"
@@ -97,16 +97,22 @@ pub enum ErrorCode {
     ProposalRecycleNeedWaitLonger,
     ProposalRecycleRefundAmountError,
     PrevProposalItemNotFound,
+    ProposalRangeOverlapped, // 120
+    ProposalRelatedCellOutOfRange,
     IncomeCellConsolidateConditionNotSatisfied = -126,
     IncomeCellConsolidateError,
     IncomeCellConsolidateWaste,
     IncomeCellTransferError,
     IncomeCellCapacityError,
     IncomeCellProfitMismatch,
+    IncomeCellWithdrawError,
+    IncomeCellVestingRecordInvalid,
     EIP712SerializationError = -90,
     EIP712SematicError,
     EIP712DecodingWitnessArgsError,
     EIP712SignatureError,
+    ContractWalletAttestationError,
+    WebAuthnSignatureError,
     BalanceCellFoundSomeOutputsLackOfType = -80,
     BalanceCellCanNotBeSpent,
     AccountSaleCellCapacityError,
@@ -130,6 +136,13 @@ pub enum ErrorCode {
     UpgradeDefaultValueOfNewFieldIsError,
     CrossChainLockError,
     CrossChainUnlockError,
+    CrossChainProofInvalid,
+    ActionPaused,
+    ConfigCellIsInvalid,
+    ConfigCellStagingError,
+    OracleCellIsStale,
+    OracleQuoteDeviationTooLarge,
+    StablecoinPaymentError,
     UnittestError = -2,
     SystemOff = -1,
 }
@@ -172,6 +185,8 @@ pub enum PreAccountCellErrorCode {
     InviterIdIsInvalid,
     InviteeDiscountShouldBeEmpty,
     InviteeDiscountIsInvalid,
+    AccountRegistryIsNotEnabled,
+    AccountRegistryCellError,
 }
 
 impl Into<i8> for PreAccountCellErrorCode {
@@ -219,6 +234,10 @@ pub enum AccountCellErrorCode {
     // 70
     AccountCellHasExpired,
     AccountCellStillCanNotRecycle,
+    AccountCellPendingOwnerInvalid,
+    AccountCellTransferAcceptanceExpired,
+    AccountCellRecordNotPermitted,
+    AccountCellRecordValueInvalid,
 }
 
 impl Into<i8> for AccountCellErrorCode {
@@ -1,4 +1,9 @@
-use das_types_std::constants::AccountStatus;
+use ckb_hash::blake2b_256;
+use ckb_types::bytes::Bytes;
+use ckb_types::core::ScriptHashType;
+use ckb_types::packed::{Byte32, Script};
+use ckb_types::prelude::{Builder, Entity, Pack};
+use das_types_std::constants::{AccountStatus, Source};
 use serde_json::{json, Value};
 
 use super::accounts::*;
@@ -612,3 +617,94 @@ pub fn push_input_playground_cell(template: &mut TemplateGenerator) {
     );
     template.push_empty_witness();
 }
+
+/// Generate a manager_permission record which permits the manager to edit the records starting with the prefix.
+pub fn gen_manager_permission_record(prefix: &str, manager_lock_args: &str) -> Value {
+    json!({
+        "type": "manager_permission",
+        "key": prefix,
+        "label": "",
+        // The value is the manager in 0x-prefixed hex string, so it is encoded again here.
+        "value": format!("0x{}", hex::encode(manager_lock_args.to_lowercase())),
+    })
+}
+
+/// Generate a record with a plain text value, the value is hex encoded here because the generator decodes all record values as hex.
+pub fn gen_text_record(record_type: &str, key: &str, value: &str) -> Value {
+    json!({
+        "type": record_type,
+        "key": key,
+        "label": "",
+        "value": format!("0x{}", hex::encode(value)),
+    })
+}
+
+pub fn push_account_registry_cell(
+    template: &mut TemplateGenerator,
+    root: [u8; 32],
+    migration_finished: Option<bool>,
+    source: Source,
+) {
+    let cell = json!({
+        "capacity": REVERSE_RECORD_BASIC_CAPACITY,
+        "lock": {
+            "code_hash": "{{always_success}}"
+        },
+        "type": {
+            "code_hash": "{{pre-account-cell-type}}",
+            "args": ACCOUNT_REGISTRY_CELL_ARGS
+        },
+        "data": {
+            "root": String::from("0x") + &hex::encode(&root),
+            "migration_finished": migration_finished,
+        }
+    });
+
+    match source {
+        Source::Input => {
+            template.push_input(cell, None, None);
+            template.push_empty_witness();
+        }
+        _ => {
+            template.push_output(cell, None);
+        }
+    }
+}
+
+pub const UDT_ARGS: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+pub fn gen_udt_type_hash() -> Vec<u8> {
+    let code_hash = Byte32::from_slice(&util::get_type_id_bytes("always_success")).unwrap();
+    let script = Script::new_builder()
+        .code_hash(code_hash)
+        .hash_type(ScriptHashType::Type.into())
+        .args(Bytes::from(util::hex_to_bytes(UDT_ARGS)).pack())
+        .build();
+
+    blake2b_256(script.as_slice()).to_vec()
+}
+
+pub fn gen_udt_cell(amount: u128, lock: Value) -> Value {
+    json!({
+        "capacity": "20_000_000_000",
+        "lock": lock,
+        "type": {
+            "code_hash": "{{always_success}}",
+            "args": UDT_ARGS
+        },
+        "data": format!("0x{}", hex::encode(amount.to_le_bytes()))
+    })
+}
+
+pub fn push_output_udt_cell_to_das_wallet(template: &mut TemplateGenerator, amount: u128) {
+    template.push_output(
+        gen_udt_cell(
+            amount,
+            json!({
+                "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                "args": DAS_WALLET_LOCK_ARGS
+            }),
+        ),
+        None,
+    );
+}
@@ -106,6 +106,19 @@ pub fn gen_since(relative_flag: SinceFlag, metric_flag: SinceFlag, value: u64) -
     Some(since)
 }
 
+/// The type hash of the QuoteCells pushed by TemplateGenerator::push_quote_cell_of_oracle.
+pub fn gen_quote_cell_type_hash(oracle: u8) -> Vec<u8> {
+    let mut code_hash = vec![0u8; 32];
+    code_hash[0] = 1;
+    let script = Script::new_builder()
+        .code_hash(Hash::try_from(code_hash).unwrap())
+        .hash_type(Byte::new(1))
+        .args(Bytes::from(vec![OracleCellType::Quote as u8, oracle]))
+        .build();
+
+    blake2b_256(script.as_slice()).to_vec()
+}
+
 fn gen_price_config(length: u8, new_price: u64, renew_price: u64) -> PriceConfig {
     PriceConfig::new_builder()
         .length(Uint8::from(length))
@@ -536,6 +549,22 @@ pub struct TemplateGenerator {
     pub charsets: HashMap<u32, (Bytes, Vec<u8>)>,
     pub smt_with_history: SMTWithHistory,
     pub new_sub_account_smt: SMTWithHistory,
+    pub config_main_status: u8,
+    pub config_main_paused_actions: Vec<&'static str>,
+    // The max_age, min_quote_sources and max_quote_deviation of oracles.
+    pub config_main_oracle_settings: Option<(u64, u8, u32)>,
+    // The oracles whose QuoteCells are accepted, see gen_quote_cell_type_hash.
+    pub config_main_quote_oracles: Vec<u8>,
+    // The type hash and decimals of the stablecoin.
+    pub config_main_stablecoin_settings: Option<(Vec<u8>, u8)>,
+    // The record keys whose values should be validated.
+    pub config_main_record_value_validation_keys: Vec<String>,
+    // The threshold and the ETH addresses of attesters which can attest approvals of contract wallets.
+    pub config_main_contract_wallet_settings: Option<(u8, Vec<[u8; 20]>)>,
+    // The names of the scripts in the extra type ID table, they should be in the order of the table.
+    pub config_main_extra_type_id_table: Vec<&'static str>,
+    // The mode and migration_started_at of the AccountRegistryCell.
+    pub config_main_account_registry_settings: Option<(u8, u64)>,
 }
 
 impl TemplateGenerator {
@@ -569,6 +598,15 @@ impl TemplateGenerator {
             charsets: HashMap::new(),
             smt_with_history: SMTWithHistory::new(),
             new_sub_account_smt: SMTWithHistory::new(),
+            config_main_status: 1,
+            config_main_paused_actions: Vec::new(),
+            config_main_oracle_settings: None,
+            config_main_quote_oracles: Vec::new(),
+            config_main_stablecoin_settings: None,
+            config_main_record_value_validation_keys: Vec::new(),
+            config_main_contract_wallet_settings: None,
+            config_main_extra_type_id_table: Vec::new(),
+            config_main_account_registry_settings: None,
         }
     }
 
@@ -606,6 +644,37 @@ impl TemplateGenerator {
         self.push_witness_args(Some(&lock), None, None);
     }
 
+    pub fn push_das_lock_witness_with_schema_version(&mut self, type_data_hash_hex: &str, schema_version: u8) {
+        let signature = util::hex_to_bytes("0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000FF");
+        let type_data_hash = util::hex_to_bytes(type_data_hash_hex);
+        let chain_id = util::hex_to_bytes("0x0000000000000001");
+        let lock = [signature, type_data_hash, chain_id, vec![schema_version]].concat();
+        self.push_witness_args(Some(&lock), None, None);
+    }
+
+    pub fn push_personal_sign_witness(&mut self) {
+        let signature = util::hex_to_bytes("0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000FF");
+        // The flag byte indicates the signature is an EIP-191 personal_sign of DAS_MESSAGE.
+        let lock = [signature, vec![1u8]].concat();
+        self.push_witness_args(Some(&lock), None, None);
+    }
+
+    pub fn push_contract_wallet_quorum_witness(&mut self, attester_indexes: &[u8]) {
+        let mut lock = vec![0u8, attester_indexes.len() as u8];
+        for index in attester_indexes {
+            lock.push(*index);
+            lock.extend_from_slice(&[0u8; 65]);
+        }
+        // The flag byte indicates the lock is held by a contract wallet.
+        lock.push(255u8);
+        self.push_witness_args(Some(&lock), None, None);
+    }
+
+    pub fn push_contract_wallet_storage_proof_witness(&mut self, proof: &[u8]) {
+        let lock = [vec![1u8], proof.to_vec(), vec![255u8]].concat();
+        self.push_witness_args(Some(&lock), None, None);
+    }
+
     pub fn push_multi_sign_witness(
         &mut self,
         require_first_n: u8,
@@ -695,7 +764,30 @@ impl TemplateGenerator {
         );
     }
 
-    fn gen_config_cell_account(&mut self) -> (Vec<u8>, EntityWrapper) {
+    /// Push an OracleCell with the block header, the updated_at will be the timestamp of the header in seconds.
+    pub fn push_oracle_cell_with_header(&mut self, index: u8, type_: OracleCellType, data: u64, updated_at: u64) {
+        self.push_oracle_cell(index, type_, data);
+
+        let cell = self.cell_deps.last_mut().unwrap();
+        cell["tmp_header"] = json!({
+            // Use the index as block number to make the headers different from each other.
+            "number": index,
+            "timestamp": updated_at * 1000
+        });
+    }
+
+    /// Push a QuoteCell of the given oracle, the QuoteCells of different oracles have different type scripts.
+    pub fn push_quote_cell_of_oracle(&mut self, oracle: u8, quote: u64, updated_at_opt: Option<u64>) {
+        match updated_at_opt {
+            Some(updated_at) => self.push_oracle_cell_with_header(oracle, OracleCellType::Quote, quote, updated_at),
+            None => self.push_oracle_cell(oracle, OracleCellType::Quote, quote),
+        }
+
+        let cell = self.cell_deps.last_mut().unwrap();
+        cell["type"]["args"] = json!(util::bytes_to_hex(&[OracleCellType::Quote as u8, oracle]));
+    }
+
+    pub(crate) fn gen_config_cell_account(&mut self) -> (Vec<u8>, EntityWrapper) {
         let entity = ConfigCellAccount::new_builder()
             .max_length(Uint32::from(42))
             .basic_capacity(Uint64::from(ACCOUNT_BASIC_CAPACITY))
@@ -742,7 +834,7 @@ impl TemplateGenerator {
         (cell_data, EntityWrapper::ConfigCellIncome(entity))
     }
 
-    fn gen_config_cell_main(&mut self) -> (Vec<u8>, EntityWrapper) {
+    pub(crate) fn gen_config_cell_main(&mut self) -> (Vec<u8>, EntityWrapper) {
         let type_id_table = TypeIdTable::new_builder()
             .account_cell(Hash::try_from(util::get_type_id_bytes("account-cell-type")).unwrap())
             .apply_register_cell(Hash::try_from(util::get_type_id_bytes("apply-register-cell-type")).unwrap())
@@ -769,12 +861,106 @@ impl TemplateGenerator {
             .build();
 
         let entity = ConfigCellMain::new_builder()
-            .status(Uint8::from(1))
+            .status(Uint8::from(self.config_main_status))
             .type_id_table(type_id_table)
             .das_lock_out_point_table(DasLockOutPointTable::default())
             .das_lock_type_id_table(das_lock_type_id_table)
             .build();
 
+        // The paused actions, oracle settings, stablecoin settings, record value validation keys, contract wallet settings,
+        // extra type ID table and account registry settings are appended as extra fields in order, so the contracts can read
+        // them from a compatible ConfigCellMain. The empty Bytes means the settings are not set, and the trailing empty
+        // fields are omitted.
+        let paused_actions = BytesVec::new_builder()
+            .set(
+                self.config_main_paused_actions
+                    .iter()
+                    .map(|action| Bytes::from(action.as_bytes().to_vec()))
+                    .collect(),
+            )
+            .build();
+
+        let mut oracle_settings = Vec::new();
+        if let Some((max_age, min_quote_sources, max_quote_deviation)) = self.config_main_oracle_settings {
+            oracle_settings.extend(max_age.to_le_bytes().iter());
+            oracle_settings.push(min_quote_sources);
+            oracle_settings.extend(max_quote_deviation.to_le_bytes().iter());
+            for oracle in self.config_main_quote_oracles.iter() {
+                oracle_settings.extend(gen_quote_cell_type_hash(*oracle));
+            }
+        }
+
+        let mut stablecoin_settings = Vec::new();
+        if let Some((type_hash, decimals)) = &self.config_main_stablecoin_settings {
+            stablecoin_settings.extend(type_hash);
+            stablecoin_settings.push(*decimals);
+        }
+
+        // Join all record keys with 0x00 byte just like the ConfigCellRecordKeyNamespace.
+        let mut record_value_validation_keys = Vec::new();
+        for key in self.config_main_record_value_validation_keys.iter() {
+            record_value_validation_keys.extend(key.as_bytes());
+            record_value_validation_keys.push(0u8);
+        }
+
+        let mut contract_wallet_settings = Vec::new();
+        if let Some((threshold, attesters)) = &self.config_main_contract_wallet_settings {
+            contract_wallet_settings.push(*threshold);
+            for attester in attesters.iter() {
+                contract_wallet_settings.extend(attester);
+            }
+        }
+
+        let mut extra_type_id_table = Vec::new();
+        for name in self.config_main_extra_type_id_table.iter() {
+            extra_type_id_table.extend(util::get_type_id_bytes(name));
+        }
+
+        let mut account_registry_settings = Vec::new();
+        if let Some((mode, migration_started_at)) = self.config_main_account_registry_settings {
+            account_registry_settings.push(mode);
+            account_registry_settings.extend(migration_started_at.to_le_bytes().iter());
+        }
+
+        let extra_fields = vec![
+            (
+                self.config_main_paused_actions.is_empty(),
+                paused_actions.as_slice().to_vec(),
+            ),
+            (
+                oracle_settings.is_empty(),
+                Bytes::from(oracle_settings).as_slice().to_vec(),
+            ),
+            (
+                stablecoin_settings.is_empty(),
+                Bytes::from(stablecoin_settings).as_slice().to_vec(),
+            ),
+            (
+                record_value_validation_keys.is_empty(),
+                Bytes::from(record_value_validation_keys).as_slice().to_vec(),
+            ),
+            (
+                contract_wallet_settings.is_empty(),
+                Bytes::from(contract_wallet_settings).as_slice().to_vec(),
+            ),
+            (
+                extra_type_id_table.is_empty(),
+                Bytes::from(extra_type_id_table).as_slice().to_vec(),
+            ),
+            (
+                account_registry_settings.is_empty(),
+                Bytes::from(account_registry_settings).as_slice().to_vec(),
+            ),
+        ];
+
+        let mut raw = entity.as_slice().to_vec();
+        if let Some(last) = extra_fields.iter().rposition(|(is_empty, _)| !is_empty) {
+            for (_, field) in extra_fields.iter().take(last + 1) {
+                raw = util::append_table_field(&raw, field);
+            }
+        }
+        let entity = ConfigCellMain::new_unchecked(raw.into());
+
         let cell_data = blake2b_256(entity.as_slice()).to_vec();
 
         (cell_data, EntityWrapper::ConfigCellMain(entity))
@@ -814,7 +1000,7 @@ impl TemplateGenerator {
         (cell_data, EntityWrapper::ConfigCellProposal(entity))
     }
 
-    fn gen_config_cell_profit_rate(&mut self) -> (Vec<u8>, EntityWrapper) {
+    pub(crate) fn gen_config_cell_profit_rate(&mut self) -> (Vec<u8>, EntityWrapper) {
         let entity = ConfigCellProfitRate::new_builder()
             .channel(Uint32::from(800))
             .inviter(Uint32::from(800))
@@ -1071,6 +1257,45 @@ impl TemplateGenerator {
         (cell_data, raw)
     }
 
+    fn get_config_cell(&self, source: Source, index: usize) -> &Value {
+        match source {
+            Source::Input => &self.inputs[index]["previous_output"],
+            Source::Output => &self.outputs[index],
+            _ => panic!("Only Input and Output are supported"),
+        }
+    }
+
+    fn get_config_cell_mut(&mut self, source: Source, index: usize) -> &mut Value {
+        match source {
+            Source::Input => &mut self.inputs[index]["previous_output"],
+            Source::Output => &mut self.outputs[index],
+            _ => panic!("Only Input and Output are supported"),
+        }
+    }
+
+    /// Get the hash of the active config entity in the ConfigCell.
+    pub fn get_config_cell_active_hash(&self, source: Source, index: usize) -> Vec<u8> {
+        let data = self.get_config_cell(source, index)["tmp_data"].as_str().unwrap();
+        util::hex_to_bytes(data).get(..32).unwrap().to_vec()
+    }
+
+    /// Stage the config entity with pending_hash in the ConfigCell, the data will be [ active_hash | pending_hash | effective_at ].
+    pub fn stage_config_cell(
+        &mut self,
+        source: Source,
+        index: usize,
+        active_hash: &[u8],
+        pending_hash: &[u8],
+        effective_at: u64,
+    ) {
+        let mut data = Vec::new();
+        data.extend(active_hash);
+        data.extend(pending_hash);
+        data.extend(effective_at.to_le_bytes().iter());
+
+        self.get_config_cell_mut(source, index)["tmp_data"] = json!(util::bytes_to_hex(&data));
+    }
+
     pub fn push_config_cell(&mut self, config_type: DataType, source: Source) {
         fn push_cell(
             generator: &mut TemplateGenerator,
@@ -1413,6 +1638,9 @@ impl TemplateGenerator {
                     "offer-cell-type" => {
                         push_cell!(DataType::OfferCellData, gen_offer_cell, version_opt, cell)
                     }
+                    "pre-account-cell-type" if type_script["args"] == ACCOUNT_REGISTRY_CELL_ARGS => {
+                        push_cell!(gen_account_registry_cell, cell)
+                    }
                     "pre-account-cell-type" => {
                         push_cell!(DataType::PreAccountCellData, gen_pre_account_cell, version_opt, cell)
                     }
@@ -1425,6 +1653,8 @@ impl TemplateGenerator {
                     "reverse-record-cell-type" => push_cell!(gen_reverse_record_cell, cell),
                     "reverse-record-root-cell-type" => push_cell!(gen_reverse_record_root_cell, cell),
                     "test-env" => push_cell!(gen_custom_cell, cell),
+                    // The always_success is used as the type script of UDT cells.
+                    "always_success" => push_cell!(gen_custom_cell, cell),
                     "playground" => push_cell!(gen_custom_cell, cell),
                     _ => panic!("Unknown type ID {}", type_id),
                 };
@@ -2589,6 +2819,35 @@ impl TemplateGenerator {
     ///     }
     /// })
     /// ```
+    fn gen_account_registry_cell(&mut self, cell: Value) -> (Value, Option<EntityWrapper>) {
+        let capacity: u64 = util::parse_json_u64("cell.capacity", &cell["capacity"], Some(0));
+        let lock_script = parse_json_script("cell.lock", &cell["lock"]);
+        let type_script = parse_json_script("cell.type", &cell["type"]);
+
+        let outputs_data = if cell["data"].is_null() {
+            String::from("")
+        } else {
+            let data = &cell["data"];
+            let mut bytes = util::parse_json_hex("cell.data.root", &data["root"]);
+            if let Some(migration_finished) = data["migration_finished"].as_bool() {
+                bytes.push(migration_finished as u8);
+            }
+            util::bytes_to_hex(&bytes)
+        };
+
+        (
+            json!({
+                "tmp_header": cell["header"],
+                "tmp_type": "full",
+                "capacity": capacity,
+                "lock": lock_script,
+                "type": type_script,
+                "tmp_data": outputs_data
+            }),
+            None,
+        )
+    }
+
     fn gen_reverse_record_root_cell(&mut self, cell: Value) -> (Value, Option<EntityWrapper>) {
         let capacity: u64 = util::parse_json_u64("cell.capacity", &cell["capacity"], Some(0));
         let lock_script = parse_json_script("cell.lock", &cell["lock"]);
@@ -49,6 +49,10 @@ impl FromStr for BinaryVersion {
 }
 
 pub fn test_tx(tx: Value) {
+    test_tx_and_get_cycles(tx);
+}
+
+pub fn test_tx_and_get_cycles(tx: Value) -> Cycle {
     // println!("Transaction template: {}", serde_json::to_string_pretty(&tx).unwrap());
     let mut parser = TemplateParser::from_data(tx, 350_000_000);
     match parser.try_parse() {
@@ -64,6 +68,8 @@ Transaction size: {} bytes,
                     tx_view.data().total_size() + 4,
                     cycles
                 );
+
+                cycles
             }
             Err(e) => {
                 panic!(
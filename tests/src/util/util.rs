@@ -1,3 +1,4 @@
+use std::convert::TryInto;
 use std::error::Error;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Lines};
@@ -140,6 +141,25 @@ pub fn prepend_molecule_like_length(raw: Vec<u8>) -> Vec<u8> {
     entity
 }
 
+/// Append a field to the end of a molecule table, this simulates the newer version of a table with more fields.
+pub fn append_table_field(table: &[u8], field: &[u8]) -> Vec<u8> {
+    let total_size = u32::from_le_bytes(table[0..4].try_into().unwrap()) as usize;
+    let first_offset = u32::from_le_bytes(table[4..8].try_into().unwrap()) as usize;
+    let field_count = first_offset / 4 - 1;
+
+    // Every offset is moved forward by the new offset in the header.
+    let mut ret = ((total_size + 4 + field.len()) as u32).to_le_bytes().to_vec();
+    for i in 0..field_count {
+        let offset = u32::from_le_bytes(table[4 * (i + 1)..4 * (i + 2)].try_into().unwrap());
+        ret.extend((offset + 4).to_le_bytes());
+    }
+    ret.extend(((total_size + 4) as u32).to_le_bytes());
+    ret.extend(&table[first_offset..]);
+    ret.extend(field);
+
+    ret
+}
+
 pub fn read_lines(file_name: &str) -> io::Result<Lines<BufReader<File>>> {
     let dir = env::current_dir().unwrap();
     let mut file_path = PathBuf::new();
@@ -73,6 +73,26 @@ fn parse_witness_entity_config() {
     test_tx(template.as_json());
 }
 
+#[test]
+fn parse_witness_entity_config_with_pending_update() {
+    let mut template = init("test_parse_witness_entity_config");
+
+    let (lock_script, type_script, mut cell_data, entity) = gen_config_cell_account();
+    // Simulate there is a pending update which should have taken effect, but it is not promoted by config-cell-type yet,
+    // so the active entity is still used and no TimeCell is required.
+    cell_data.extend(&[1u8; 32]);
+    cell_data.extend(&0u64.to_le_bytes());
+    template.push_cell(0, lock_script, type_script, Some(cell_data), Source::CellDep);
+
+    let witness = das_util::wrap_entity_witness_v2(DataType::ConfigCellAccount, entity);
+    template.outer_witnesses.push(util::bytes_to_hex(&witness));
+
+    push_input_normal_cell(&mut template, 0, CONFIG_LOCK_ARGS);
+    push_input_test_env_cell(&mut template);
+
+    test_tx(template.as_json());
+}
+
 #[test]
 fn parse_witness_raw_config() {
     let mut template = init("test_parse_witness_raw_config");
@@ -228,3 +248,87 @@ fn parse_witness_error_cells_hash() {
     push_input_test_env_cell(&mut template);
     challenge_tx(template.as_json(), ErrorCode::WitnessDataHashOrTypeMissMatch);
 }
+
+#[test]
+fn parse_witness_cells_lazily() {
+    let mut template = init("test_parse_witness_cells_lazily");
+
+    let (cell, entity) = gen_account_cell(None);
+    template.push_cell_json_with_entity(cell, Source::CellDep, DataType::AccountCellData, 3, Some(entity), None);
+
+    push_input_test_env_cell(&mut template);
+    test_tx(template.as_json());
+}
+
+#[test]
+fn parse_witness_cells_lazily_with_unread_cells() {
+    let mut template = init("test_parse_witness_cells_lazily");
+
+    let (cell, entity) = gen_account_cell(None);
+    template.push_cell_json_with_entity(cell, Source::CellDep, DataType::AccountCellData, 3, Some(entity), None);
+    // Simulate there are some cells whose witnesses are never read, they should only be indexed but not decoded.
+    for _ in 0..3 {
+        let (cell, entity) = gen_account_cell(Some(String::from(
+            "0x0000000000000000000000000000000000000000000000000000000000000000",
+        )));
+        template.push_cell_json_with_entity(cell, Source::CellDep, DataType::AccountCellData, 3, Some(entity), None);
+    }
+
+    push_input_test_env_cell(&mut template);
+    test_tx(template.as_json());
+}
+
+#[test]
+fn parse_witness_lazily_error_cells_data_type() {
+    let mut template = init("test_parse_witness_cells_lazily");
+
+    let (cell, entity) = gen_account_cell(None);
+    template.push_cell_json_with_entity(cell, Source::CellDep, DataType::IncomeCellData, 3, Some(entity), None);
+
+    push_input_test_env_cell(&mut template);
+    challenge_tx(template.as_json(), ErrorCode::WitnessDataHashOrTypeMissMatch);
+}
+
+#[test]
+fn parse_witness_lazily_error_cells_hash() {
+    let mut template = init("test_parse_witness_cells_lazily");
+
+    let (cell, entity) = gen_account_cell(Some(String::from(
+        "0x0000000000000000000000000000000000000000000000000000000000000000",
+    )));
+    template.push_cell_json_with_entity(cell, Source::CellDep, DataType::AccountCellData, 3, Some(entity), None);
+
+    push_input_test_env_cell(&mut template);
+    challenge_tx(template.as_json(), ErrorCode::WitnessDataHashOrTypeMissMatch);
+}
+
+fn gen_tx_with_unread_cells(action: &str) -> TemplateGenerator {
+    let mut template = init(action);
+
+    // Only the first cell is read by the test-env, the others are pushed to make the cost of decoding witnesses visible.
+    for _ in 0..11 {
+        let (cell, entity) = gen_account_cell(None);
+        template.push_cell_json_with_entity(cell, Source::CellDep, DataType::AccountCellData, 3, Some(entity), None);
+    }
+
+    push_input_test_env_cell(&mut template);
+    template
+}
+
+#[test]
+fn parse_witness_cells_lazily_cost_less_cycles() {
+    let eager_cycles = test_tx_and_get_cycles(gen_tx_with_unread_cells("test_parse_witness_cells").as_json());
+    let lazy_cycles = test_tx_and_get_cycles(gen_tx_with_unread_cells("test_parse_witness_cells_lazily").as_json());
+
+    println!(
+        "Cycles of parsing cells eagerly: {}, lazily: {}",
+        eager_cycles, lazy_cycles
+    );
+    // Only one of the cells is read, so the lazy mode should skip decoding and hashing the other witnesses.
+    assert!(
+        lazy_cycles < eager_cycles,
+        "Parsing cells lazily should cost less cycles than parsing them eagerly. (eager: {}, lazy: {})",
+        eager_cycles,
+        lazy_cycles
+    );
+}